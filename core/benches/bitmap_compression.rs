@@ -0,0 +1,57 @@
+use std::io::Write;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use flate2::{write::ZlibEncoder, Compression};
+
+const WIDTH: usize = 1920;
+const HEIGHT: usize = 1080;
+
+// gradients with some noise, an image of only one color would compress unrealistically well
+fn image_data() -> Vec<u8> {
+    let mut data = Vec::with_capacity(WIDTH * HEIGHT * 4);
+    let mut random: u32 = 0x12345678;
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            // xorshift, a fixed seed keeps the runs comparable
+            random ^= random << 13;
+            random ^= random >> 17;
+            random ^= random << 5;
+            let noise = (random % 8) as u8;
+            data.push(((x * 255 / WIDTH) as u8).wrapping_add(noise));
+            data.push((y * 255 / HEIGHT) as u8);
+            data.push(((x + y) % 256) as u8);
+            data.push(255);
+        }
+    }
+    data
+}
+
+// the same way the export compresses bitmaps for DefineBitsLossless
+fn compress(data: &[u8], compression: Compression) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), compression);
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn bitmap_compression(c: &mut Criterion) {
+    let data = image_data();
+    let mut group = c.benchmark_group("bitmap_compression");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    // compressing a full hd image takes a while, the default of 100 samples would take minutes
+    group.sample_size(10);
+    for (name, compression) in [
+        ("fast", Compression::fast()),
+        ("level_6", Compression::new(6)),
+        ("best", Compression::best()),
+    ] {
+        let compressed_size = compress(&data, compression).len();
+        println!("{}: {} bytes", name, compressed_size);
+        group.bench_with_input(BenchmarkId::from_parameter(name), &data, |b, data| {
+            b.iter(|| compress(data, compression))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bitmap_compression);
+criterion_main!(benches);
@@ -1,26 +1,37 @@
 use std::{
     any::Any,
+    collections::{HashMap, HashSet},
+    hash::{DefaultHasher, Hash, Hasher},
+    ops::RangeInclusive,
     path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
 };
 
-use image::{DynamicImage, GenericImage, ImageReader};
+use image::{DynamicImage, GenericImage, ImageDecoder, ImageReader};
 use serde::{Deserialize, Serialize};
 use swf::{Color, Fixed16, Matrix, Twips};
 
 use self::export::export_movie_to_swf;
 
+mod archive;
+pub mod bounds;
+mod error;
 mod export;
-pub use self::export::FontContainer;
+pub use self::error::{BitmapLoadError, FlitsCoreError, TooManyFramesError, ValidationError};
+pub use self::export::{
+    CompatibilityWarning, ExportManifest, ExportOptions, ExportResult, FontContainer,
+    ManifestCharacter,
+};
 pub mod run;
 
 pub type SymbolIndex = usize;
 pub type SymbolIndexOrRoot = Option<SymbolIndex>;
 pub type PlacedSymbolIndex = usize;
 
-// this is hardcoded because otherwise the entire application needs to understand
-// what features are available in what version
+// the editor always uses the features of this version, exporting with a lower version
+// only gives warnings about the features that version doesn't support
 // TODO: switch to latest as2 version instead of latest version?
-const SWF_VERSION: u8 = 43; // latest flash player version
+pub const SWF_VERSION: u8 = 43; // latest flash player version
 
 #[derive(Serialize, Deserialize)]
 pub struct Movie {
@@ -28,6 +39,8 @@ pub struct Movie {
 
     pub symbols: Vec<Symbol>,
     pub root: Vec<PlaceSymbol>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub root_scripts: Vec<FrameScript>,
 }
 impl Default for Movie {
     fn default() -> Self {
@@ -40,31 +53,119 @@ impl Movie {
             properties,
             symbols: vec![],
             root: vec![],
+            root_scripts: vec![],
         }
     }
-    pub fn load(path: PathBuf) -> Result<Movie, Box<dyn std::error::Error>> {
+    pub fn load(path: PathBuf) -> Result<Movie, FlitsCoreError> {
+        let (movie, issues) = Movie::load_with_issues(path)?;
+        for issue in issues {
+            eprintln!("Warning: {}", issue);
+        }
+        Ok(movie)
+    }
+    // also returns the problems with the project, placed symbols of symbols that don't exist are removed
+    pub fn load_with_issues(path: PathBuf) -> Result<(Movie, Vec<ProjectIssue>), FlitsCoreError> {
         let directory = path.parent().unwrap();
         let file = std::fs::File::open(path.clone())?;
         let mut movie: Movie = serde_json::from_reader(file)?;
-        movie.reload_assets(directory);
+        let mut issues = movie.remove_invalid_placed_symbols();
+        issues.extend(movie.reload_assets(directory));
+        issues.extend(movie.find_issues());
 
-        Ok(movie)
+        Ok((movie, issues))
+    }
+
+    // removes placed symbols with a symbol index that is out of range,
+    // this happens when the project file is edited by hand
+    fn remove_invalid_placed_symbols(&mut self) -> Vec<ProjectIssue> {
+        let mut issues = vec![];
+        let symbol_count = self.symbols.len();
+        let containers: Vec<SymbolIndexOrRoot> = std::iter::once(None)
+            .chain(
+                self.symbols
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, symbol)| matches!(symbol, Symbol::MovieClip(_)))
+                    .map(|(symbol_index, _)| Some(symbol_index)),
+            )
+            .collect();
+        for container in containers {
+            let container_name = match container {
+                Some(container) => self.symbols[container].name(),
+                None => "Scene".into(),
+            };
+            let placed_symbols = self.get_placed_symbols_mut(container);
+            // iterate in reverse so removing doesn't change the indexes we still need to check
+            for placed_symbol_index in (0..placed_symbols.len()).rev() {
+                let symbol_index = placed_symbols[placed_symbol_index].symbol_index;
+                if symbol_index >= symbol_count {
+                    placed_symbols.remove(placed_symbol_index);
+                    issues.push(ProjectIssue::InvalidSymbolIndex {
+                        container,
+                        container_name: container_name.clone(),
+                        placed_symbol_index,
+                        symbol_index,
+                    });
+                }
+            }
+        }
+        issues
     }
 
-    pub fn reload_assets(&mut self, directory: &Path) {
+    // returns the bitmaps that were moved, their path is changed so the project has to be saved
+    // and the bitmaps that had a color profile, they might look different in the editor than in the image editor
+    pub fn reload_assets(&mut self, directory: &Path) -> Vec<ProjectIssue> {
+        let mut issues = vec![];
+        // existing bitmaps are loaded first, so bitmaps that were moved to the assets directory
+        // get their path updated instead of being imported as a new bitmap
+        for (symbol_index, symbol) in self.symbols.iter_mut().enumerate() {
+            let Symbol::Bitmap(bitmap) = symbol else {
+                continue;
+            };
+            if let Some(old_path) = bitmap.update_moved_path(directory) {
+                issues.push(ProjectIssue::MovedBitmap {
+                    symbol_index,
+                    name: bitmap.properties.name.clone(),
+                    old_path,
+                    new_path: bitmap.properties.path.clone(),
+                });
+            }
+            if bitmap.cache_image_with_profile_info(directory) {
+                issues.push(ProjectIssue::DiscardedColorProfile {
+                    symbol_index,
+                    name: bitmap.properties.name.clone(),
+                });
+            }
+        }
+
         self.add_unimported_assets(directory);
 
-        for symbol in self.symbols.iter_mut() {
+        for (symbol_index, symbol) in self.symbols.iter_mut().enumerate() {
             let Symbol::Bitmap(bitmap) = symbol else {
                 continue;
             };
-            bitmap.cache_image(directory);
+            if let BitmapCacheStatus::Uncached = bitmap.cache {
+                if bitmap.cache_image_with_profile_info(directory) {
+                    issues.push(ProjectIssue::DiscardedColorProfile {
+                        symbol_index,
+                        name: bitmap.properties.name.clone(),
+                    });
+                }
+            }
         }
+        issues
     }
 
     fn add_unimported_assets(&mut self, directory: &Path) {
         let asset_dir = directory.join("assets");
-        std::fs::create_dir_all(asset_dir.clone()).unwrap();
+        if let Err(err) = std::fs::create_dir_all(asset_dir.clone()) {
+            eprintln!(
+                "Warning: unable to create assets directory '{}': {}",
+                asset_dir.display(),
+                err
+            );
+            return;
+        }
 
         let mut existing_assets: Vec<String> = self
             .symbols
@@ -72,17 +173,46 @@ impl Movie {
             .filter_map(|symbol| match symbol {
                 Symbol::Bitmap(bitmap) => Some(bitmap.properties.path.clone()),
                 Symbol::Font(font) => Some(font.path.clone()),
+                Symbol::Sound(sound) => Some(sound.path.clone()),
                 _ => None,
             })
             .collect();
 
-        let fs_assets = std::fs::read_dir(asset_dir).unwrap();
+        // an unreadable assets directory (permissions, disconnected network drive)
+        // shouldn't stop the project from loading, the existing symbols still work
+        let fs_assets = match std::fs::read_dir(asset_dir.clone()) {
+            Ok(fs_assets) => fs_assets,
+            Err(err) => {
+                eprintln!(
+                    "Warning: unable to read assets directory '{}': {}",
+                    asset_dir.display(),
+                    err
+                );
+                return;
+            }
+        };
         for fs_asset in fs_assets {
-            let file = fs_asset.unwrap();
-            let file_name = file.file_name().into_string().unwrap();
+            let file = match fs_asset {
+                Ok(file) => file,
+                Err(err) => {
+                    eprintln!("Warning: unable to read asset: {}", err);
+                    continue;
+                }
+            };
+            let file_name = match file.file_name().into_string() {
+                Ok(file_name) => file_name,
+                Err(file_name) => {
+                    eprintln!(
+                        "Warning: skipping asset with non utf-8 filename: {:?}",
+                        file_name
+                    );
+                    continue;
+                }
+            };
             let is_image = file_name.ends_with(".png");
-            let is_font = file_name.ends_with(".ttf");
-            if !is_image && !is_font {
+            let is_font = file_name.ends_with(".ttf") || file_name.ends_with(".otf");
+            let is_sound = file_name.ends_with(".mp3") || file_name.ends_with(".wav");
+            if !is_image && !is_font && !is_sound {
                 continue;
             }
             let existing_index = existing_assets
@@ -99,6 +229,7 @@ impl Movie {
                             name: file_name.clone(),
                             path: file_name,
                             animation: None,
+                            do_not_export: false,
                         },
                         cache: BitmapCacheStatus::Uncached,
                     }));
@@ -108,29 +239,274 @@ impl Movie {
                         characters: FontCharacters {
                             ascii: true,
                             additional_characters: String::new(),
+                            only_used: false,
                         },
                     }))
+                } else if is_sound {
+                    self.symbols.push(Symbol::Sound(FlitsSound {
+                        name: file_name.clone(),
+                        path: file_name,
+                    }))
                 }
             }
         }
     }
 
-    pub fn save(&self, path: &Path) {
+    pub fn save(&self, path: &Path) -> Result<(), FlitsCoreError> {
         let file = std::fs::File::options()
             .write(true)
             .create(true)
             .truncate(true)
-            .open(path)
-            .unwrap();
-        serde_json::to_writer(file, self).unwrap();
+            .open(path)?;
+        serde_json::to_writer(file, self)?;
+        Ok(())
     }
 
     pub fn export(
         &self,
         project_directory: PathBuf,
         swf_path: PathBuf,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        export_movie_to_swf(self, project_directory, swf_path)
+    ) -> Result<(), FlitsCoreError> {
+        export_movie_to_swf(self, project_directory, swf_path, &ExportOptions::default())?;
+        Ok(())
+    }
+    pub fn export_with_options(
+        &self,
+        project_directory: PathBuf,
+        swf_path: PathBuf,
+        options: &ExportOptions,
+    ) -> Result<ExportResult, FlitsCoreError> {
+        export_movie_to_swf(self, project_directory, swf_path, options)
+    }
+    // exports on a separate thread, the result is sent before the progress channel is closed
+    pub fn export_in_background(
+        &self,
+        project_directory: PathBuf,
+        swf_path: PathBuf,
+        options: ExportOptions,
+    ) -> Result<Receiver<Result<ExportResult, String>>, FlitsCoreError> {
+        // the bitmap cache can't be sent to another thread, so the thread gets a copy without it
+        // the export loads the bitmaps from disk anyway
+        let project = serde_json::to_value(self)?;
+        let (result_sender, result_receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = serde_json::from_value::<Movie>(project)
+                .map_err(|err| err.into())
+                .and_then(|movie| {
+                    export_movie_to_swf(&movie, project_directory, swf_path, &options)
+                })
+                .map_err(|err| err.to_string());
+            _ = result_sender.send(result);
+            // closes the progress channel
+            drop(options);
+        });
+        Ok(result_receiver)
+    }
+
+    // a zip with the exported swf and the assets, for sharing the movie
+    pub fn export_archive(
+        &self,
+        project_directory: &Path,
+        swf_path: &Path,
+        archive_path: &Path,
+    ) -> Result<(), FlitsCoreError> {
+        archive::create_archive(self, project_directory, swf_path, archive_path)
+    }
+
+    // a copy of the symbol that shares nothing with the original
+    // the symbols placed in a movieclip and the fonts of texts are copied as well and added to
+    // the movie, the returned copy uses those copies instead of the originals
+    pub fn clone_symbol_deep(&mut self, symbol_index: SymbolIndex) -> Symbol {
+        let mut copies = HashMap::new();
+        let mut clone = self.symbols[symbol_index].clone_without_cache();
+        match &mut clone {
+            Symbol::MovieClip(movieclip) => {
+                self.clone_placed_symbols_deep(
+                    symbol_index,
+                    &mut movieclip.place_symbols,
+                    &mut copies,
+                );
+            }
+            Symbol::StaticText(static_text) => {
+                static_text.font_index =
+                    self.clone_font_deep(symbol_index, static_text.font_index, &mut copies);
+            }
+            _ => {}
+        }
+        clone
+    }
+    fn clone_placed_symbols_deep(
+        &mut self,
+        root_symbol_index: SymbolIndex,
+        place_symbols: &mut Vec<PlaceSymbol>,
+        copies: &mut HashMap<SymbolIndex, SymbolIndex>,
+    ) {
+        for place_symbol in place_symbols {
+            place_symbol.symbol_index =
+                self.copy_symbol_deep(root_symbol_index, place_symbol.symbol_index, copies);
+            // text fields can use other fonts in their spans
+            if let Some(text) = &mut place_symbol.text {
+                for span in &mut text.spans {
+                    span.font_symbol_index =
+                        self.copy_symbol_deep(root_symbol_index, span.font_symbol_index, copies);
+                }
+            }
+        }
+    }
+    fn clone_font_deep(
+        &mut self,
+        root_symbol_index: SymbolIndex,
+        font_index: Option<SymbolIndex>,
+        copies: &mut HashMap<SymbolIndex, SymbolIndex>,
+    ) -> Option<SymbolIndex> {
+        font_index.map(|font_index| self.copy_symbol_deep(root_symbol_index, font_index, copies))
+    }
+    // returns the index of the copy, symbols that are used multiple times are only copied once
+    fn copy_symbol_deep(
+        &mut self,
+        root_symbol_index: SymbolIndex,
+        symbol_index: SymbolIndex,
+        copies: &mut HashMap<SymbolIndex, SymbolIndex>,
+    ) -> SymbolIndex {
+        // the copy of the root isn't in the movie yet, so a movieclip that contains itself
+        // keeps containing the original
+        if symbol_index == root_symbol_index {
+            return symbol_index;
+        }
+        if let Some(copy_index) = copies.get(&symbol_index) {
+            return *copy_index;
+        }
+        let copy_index = self.symbols.len();
+        self.symbols
+            .push(self.symbols[symbol_index].clone_without_cache());
+        // inserted before copying the contents so movieclips that contain each other don't recurse forever
+        copies.insert(symbol_index, copy_index);
+        match &mut self.symbols[copy_index] {
+            Symbol::MovieClip(movieclip) => {
+                let mut place_symbols = std::mem::take(&mut movieclip.place_symbols);
+                self.clone_placed_symbols_deep(root_symbol_index, &mut place_symbols, copies);
+                if let Symbol::MovieClip(movieclip) = &mut self.symbols[copy_index] {
+                    movieclip.place_symbols = place_symbols;
+                }
+            }
+            Symbol::StaticText(static_text) => {
+                let font_index = static_text.font_index;
+                let font_index = self.clone_font_deep(root_symbol_index, font_index, copies);
+                if let Symbol::StaticText(static_text) = &mut self.symbols[copy_index] {
+                    static_text.font_index = font_index;
+                }
+            }
+            _ => {}
+        }
+        copy_index
+    }
+
+    // the number of times a symbol is placed in the root and in movieclips
+    pub fn usage_count(&self, symbol_index: SymbolIndex) -> usize {
+        let count = |placed_symbols: &Vec<PlaceSymbol>| {
+            placed_symbols
+                .iter()
+                .map(|place_symbol| {
+                    // fonts can also be used by the spans of a text field
+                    let span_count = match &place_symbol.text {
+                        Some(text) => text
+                            .spans
+                            .iter()
+                            .filter(|span| span.font_symbol_index == symbol_index)
+                            .count(),
+                        None => 0,
+                    };
+                    (place_symbol.symbol_index == symbol_index) as usize + span_count
+                })
+                .sum::<usize>()
+        };
+        count(&self.root)
+            + self
+                .symbols
+                .iter()
+                .map(|symbol| match symbol {
+                    Symbol::MovieClip(movieclip) => count(&movieclip.place_symbols),
+                    Symbol::StaticText(static_text) => {
+                        (static_text.font_index == Some(symbol_index)) as usize
+                    }
+                    _ => 0,
+                })
+                .sum::<usize>()
+    }
+
+    // the characters in all the text fields that use this font
+    pub fn collect_used_characters(&self, font_index: SymbolIndex) -> HashSet<char> {
+        let mut characters = HashSet::new();
+        for symbol in &self.symbols {
+            if let Symbol::StaticText(static_text) = symbol {
+                if static_text.font_index == Some(font_index) {
+                    characters.extend(
+                        static_text
+                            .text
+                            .chars()
+                            .filter(|character| !character.is_control()),
+                    );
+                }
+            }
+        }
+        let placed_symbol_lists = std::iter::once(&self.root).chain(
+            self.symbols.iter().filter_map(|symbol| match symbol {
+                Symbol::MovieClip(movieclip) => Some(&movieclip.place_symbols),
+                _ => None,
+            }),
+        );
+        for placed_symbols in placed_symbol_lists {
+            for place_symbol in placed_symbols {
+                let Some(text) = &place_symbol.text else {
+                    continue;
+                };
+                // spans replace the text, and every span has its own font
+                if !text.spans.is_empty() {
+                    for span in &text.spans {
+                        if span.font_symbol_index == font_index {
+                            characters.extend(
+                                span.text
+                                    .chars()
+                                    .filter(|character| !character.is_control()),
+                            );
+                        }
+                    }
+                    continue;
+                }
+                if place_symbol.symbol_index != font_index {
+                    continue;
+                }
+                let mut is_in_tag = false;
+                for character in text.text.chars() {
+                    // html tags aren't shown, so they don't need glyphs
+                    if text.is_html && character == '<' {
+                        is_in_tag = true;
+                    } else if text.is_html && character == '>' {
+                        is_in_tag = false;
+                    } else if !is_in_tag && !character.is_control() {
+                        characters.insert(character);
+                    }
+                }
+            }
+        }
+        characters
+    }
+
+    // the characters of the font that end up in the swf
+    pub fn exported_font_characters(&self, font_index: SymbolIndex) -> String {
+        let Symbol::Font(font) = &self.symbols[font_index] else {
+            return String::new();
+        };
+        if font.characters.only_used {
+            let mut characters: Vec<char> = self
+                .collect_used_characters(font_index)
+                .into_iter()
+                .collect();
+            characters.sort();
+            characters.into_iter().collect()
+        } else {
+            font.characters.characters()
+        }
     }
 
     pub fn get_placed_symbols(&self, symbol_index: SymbolIndexOrRoot) -> &Vec<PlaceSymbol> {
@@ -160,11 +536,133 @@ impl Movie {
         }
     }
 
-    pub fn num_frames(&self) -> u16 {
+    // problems that the editor should tell the user about before they start editing
+    pub fn find_issues(&self) -> Vec<ProjectIssue> {
+        let mut issues = vec![];
+        for (symbol_index, symbol) in self.symbols.iter().enumerate() {
+            if let Symbol::Bitmap(Bitmap {
+                cache: BitmapCacheStatus::Invalid(error),
+                ..
+            }) = symbol
+            {
+                issues.push(ProjectIssue::InvalidBitmap {
+                    symbol_index,
+                    name: symbol.name(),
+                    error: error.clone(),
+                });
+            }
+        }
+        issues
+    }
+
+    // the number of frames of the exported swf, a u32 so frames of a timeline can be added without
+    // overflowing, validate_num_frames() checks if it fits in the swf header
+    pub fn num_frames(&self) -> u32 {
         match self.properties.preloader {
             PreloaderType::None => 1,
             PreloaderType::StartAfterLoading => 2,
             PreloaderType::WithPlayButton => 3,
+            PreloaderType::CustomGraphic { .. } => 2,
+        }
+    }
+    // the number of frames for the swf header
+    pub fn validate_num_frames(&self) -> Result<u16, TooManyFramesError> {
+        Self::header_num_frames(self.num_frames())
+    }
+    fn header_num_frames(num_frames: u32) -> Result<u16, TooManyFramesError> {
+        u16::try_from(num_frames).map_err(|_| TooManyFramesError { num_frames })
+    }
+    pub fn validate_dimensions(&self) -> Result<(), ValidationError> {
+        let MovieProperties { width, height, .. } = self.properties;
+        // also catches NaN, which isn't in the range
+        if MovieProperties::SIZE_RANGE.contains(&width)
+            && MovieProperties::SIZE_RANGE.contains(&height)
+        {
+            Ok(())
+        } else {
+            Err(ValidationError::InvalidDimensions { width, height })
+        }
+    }
+    pub fn validate_frame_rate(&self) -> Result<(), ValidationError> {
+        let frame_rate = self.properties.frame_rate;
+        // a frame rate of 0 becomes Fixed8::ZERO, which players don't handle well
+        if MovieProperties::FRAME_RATE_RANGE.contains(&frame_rate) {
+            Ok(())
+        } else {
+            Err(ValidationError::InvalidFrameRate(frame_rate))
+        }
+    }
+}
+
+pub enum ProjectIssue {
+    // the image is missing or can't be loaded
+    InvalidBitmap {
+        symbol_index: SymbolIndex,
+        name: String,
+        error: BitmapLoadError,
+    },
+    // a placed symbol referred to a symbol that doesn't exist and was removed
+    InvalidSymbolIndex {
+        container: SymbolIndexOrRoot,
+        container_name: String,
+        placed_symbol_index: PlacedSymbolIndex,
+        symbol_index: SymbolIndex,
+    },
+    // the image was found somewhere else in the assets directory and the path was changed
+    MovedBitmap {
+        symbol_index: SymbolIndex,
+        name: String,
+        old_path: String,
+        new_path: String,
+    },
+    // the image has a color profile, the pixels are used as if they are srgb
+    DiscardedColorProfile {
+        symbol_index: SymbolIndex,
+        name: String,
+    },
+}
+impl ProjectIssue {
+    // where the editor should go to show the issue
+    pub fn symbol_index(&self) -> SymbolIndexOrRoot {
+        match self {
+            ProjectIssue::InvalidBitmap { symbol_index, .. } => Some(*symbol_index),
+            ProjectIssue::InvalidSymbolIndex { container, .. } => *container,
+            ProjectIssue::MovedBitmap { symbol_index, .. } => Some(*symbol_index),
+            ProjectIssue::DiscardedColorProfile { symbol_index, .. } => Some(*symbol_index),
+        }
+    }
+}
+impl std::fmt::Display for ProjectIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProjectIssue::InvalidBitmap { name, error, .. } => {
+                write!(f, "Bitmap '{}' can't be loaded: {}", name, error)
+            }
+            ProjectIssue::InvalidSymbolIndex {
+                container_name,
+                placed_symbol_index,
+                symbol_index,
+                ..
+            } => write!(
+                f,
+                "Removed placed symbol {} in '{}' because it refers to symbol {}, which doesn't exist",
+                placed_symbol_index, container_name, symbol_index
+            ),
+            ProjectIssue::MovedBitmap {
+                name,
+                old_path,
+                new_path,
+                ..
+            } => write!(
+                f,
+                "Bitmap '{}' was not found at '{}', its path was changed to '{}'",
+                name, old_path, new_path
+            ),
+            ProjectIssue::DiscardedColorProfile { name, .. } => write!(
+                f,
+                "The color profile of bitmap '{}' is ignored, because Flash Player doesn't support color profiles",
+                name
+            ),
         }
     }
 }
@@ -176,6 +674,16 @@ pub struct MovieProperties {
     pub frame_rate: f32,
     pub background_color: EditorColor,
     pub preloader: PreloaderType,
+    #[serde(default)]
+    pub preloader_style: PreloaderStyle,
+    #[serde(default = "latest_swf_version")]
+    pub swf_version: u8,
+}
+impl MovieProperties {
+    // in pixels, a size of 0 would make the stage impossible to show
+    pub const SIZE_RANGE: RangeInclusive<f64> = 1.0..=10000.0;
+    // in frames per second
+    pub const FRAME_RATE_RANGE: RangeInclusive<f32> = 0.01..=120.0;
 }
 impl Default for MovieProperties {
     fn default() -> Self {
@@ -191,10 +699,12 @@ impl Default for MovieProperties {
                 a: 255,
             },
             preloader: PreloaderType::None,
+            preloader_style: PreloaderStyle::default(),
+            swf_version: SWF_VERSION,
         }
     }
 }
-#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Hash)]
 pub struct EditorColor {
     pub r: u8,
     pub g: u8,
@@ -208,6 +718,18 @@ impl EditorColor {
         b: 0,
         a: 255,
     };
+    pub const GRAY: Self = EditorColor {
+        r: 128,
+        g: 128,
+        b: 128,
+        a: 255,
+    };
+    pub const WHITE: Self = EditorColor {
+        r: 255,
+        g: 255,
+        b: 255,
+        a: 255,
+    };
 }
 impl Into<Color> for EditorColor {
     fn into(self) -> Color {
@@ -224,6 +746,10 @@ pub enum PreloaderType {
     None,
     StartAfterLoading,
     WithPlayButton,
+    // a movieclip from the library is shown instead of the loading bar,
+    // its _xscale is set to the loaded percentage
+    // the movieclip is referred to by index like placed symbols, so renaming it is fine
+    CustomGraphic { movieclip_index: SymbolIndex },
 }
 impl ToString for PreloaderType {
     fn to_string(&self) -> String {
@@ -231,6 +757,25 @@ impl ToString for PreloaderType {
             PreloaderType::None => "None".into(),
             PreloaderType::StartAfterLoading => "Start after loading".into(),
             PreloaderType::WithPlayButton => "With play button".into(),
+            PreloaderType::CustomGraphic { .. } => "Custom graphic".into(),
+        }
+    }
+}
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct PreloaderStyle {
+    pub bar_color: EditorColor,
+    pub background_color: EditorColor,
+    // the width of the loading bar relative to the stage width
+    pub bar_width_fraction: f32,
+    pub bar_height: f32,
+}
+impl Default for PreloaderStyle {
+    fn default() -> Self {
+        PreloaderStyle {
+            bar_color: EditorColor::WHITE,
+            background_color: EditorColor::GRAY,
+            bar_width_fraction: 0.85,
+            bar_height: 2.0,
         }
     }
 }
@@ -240,6 +785,8 @@ pub enum Symbol {
     Bitmap(Bitmap),
     MovieClip(MovieClip),
     Font(FlitsFont),
+    Sound(FlitsSound),
+    StaticText(StaticTextProperties),
 }
 
 impl Symbol {
@@ -248,6 +795,8 @@ impl Symbol {
             Symbol::Bitmap(bitmap) => bitmap.properties.name.clone(),
             Symbol::MovieClip(movieclip) => movieclip.properties.name.clone(),
             Symbol::Font(font) => font.path.clone(),
+            Symbol::Sound(sound) => sound.name.clone(),
+            Symbol::StaticText(static_text) => static_text.name.clone(),
         }
     }
     pub fn is_invalid(&self) -> bool {
@@ -259,11 +808,42 @@ impl Symbol {
             _ => false,
         }
     }
+    pub fn is_exported(&self) -> bool {
+        match self {
+            Symbol::Bitmap(bitmap) => !bitmap.properties.do_not_export,
+            Symbol::MovieClip(movieclip) => !movieclip.properties.do_not_export,
+            Symbol::Font(_) => true,
+            Symbol::Sound(_) => true,
+            Symbol::StaticText(_) => true,
+        }
+    }
+    // changes when the properties of the symbol or the contents of its asset file change
+    pub fn content_hash(&self, directory: &Path) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        // the json contains everything that is saved in the project
+        serde_json::to_string(self)
+            .unwrap_or_default()
+            .hash(&mut hasher);
+        let asset_path = match self {
+            Symbol::Bitmap(bitmap) => Some(&bitmap.properties.path),
+            Symbol::Font(font) => Some(&font.path),
+            Symbol::Sound(sound) => Some(&sound.path),
+            Symbol::MovieClip(_) | Symbol::StaticText(_) => None,
+        };
+        if let Some(asset_path) = asset_path {
+            std::fs::read(directory.join("assets").join(asset_path))
+                .ok()
+                .hash(&mut hasher);
+        }
+        hasher.finish()
+    }
     pub fn type_name(&self) -> &str {
         match self {
             Symbol::Bitmap(_) => "Bitmap",
             Symbol::MovieClip(_) => "MovieClip",
             Symbol::Font(_) => "Font",
+            Symbol::Sound(_) => "Sound",
+            Symbol::StaticText(_) => "Static text",
         }
     }
     pub fn clone_without_cache(&self) -> Self {
@@ -276,6 +856,8 @@ impl Symbol {
             }),
             Symbol::MovieClip(movieclip) => Symbol::MovieClip(movieclip.clone()),
             Symbol::Font(font) => Symbol::Font(font.clone()),
+            Symbol::Sound(sound) => Symbol::Sound(sound.clone()),
+            Symbol::StaticText(static_text) => Symbol::StaticText(static_text.clone()),
         }
     }
 }
@@ -287,11 +869,21 @@ pub struct Bitmap {
     pub cache: BitmapCacheStatus,
 }
 impl Bitmap {
+    // color profiles are discarded, the pixels are used as if they are srgb
+    // because flash player and ruffle don't support color profiles either
     pub fn cache_image(&mut self, directory: &Path) {
+        self.cache_image_with_profile_info(directory);
+    }
+    // same as cache_image(), but returns whether the image had a color profile that was discarded
+    pub fn cache_image_with_profile_info(&mut self, directory: &Path) -> bool {
         let asset_directory = directory.join("assets");
-        self.cache = match ImageReader::open(asset_directory.join(self.properties.path.clone())) {
-            Ok(reader) => match reader.decode() {
-                Ok(mut image) => match &self.properties.animation {
+        let mut had_color_profile = false;
+        self.cache = match Self::decode_image(&asset_directory.join(&self.properties.path)) {
+            Ok((image, has_color_profile)) => {
+                had_color_profile = has_color_profile;
+                // the export uses 8 bit rgba as well
+                let mut image = DynamicImage::ImageRgba8(image.into_rgba8());
+                match &self.properties.animation {
                     None => BitmapCacheStatus::Cached(CachedBitmap {
                         image,
                         bitmap_handle: None,
@@ -316,11 +908,70 @@ impl Bitmap {
                             bitmap_handle: None,
                         })
                     }
-                },
-                Err(err) => BitmapCacheStatus::Invalid(err.to_string()),
-            },
-            Err(err) => BitmapCacheStatus::Invalid(err.to_string()),
+                }
+            }
+            Err(err) => BitmapCacheStatus::Invalid(err),
         };
+        had_color_profile
+    }
+    // changes the path when the image doesn't exist anymore but a file with the same name is
+    // somewhere else in the assets directory, returns the old path when it was changed
+    pub fn update_moved_path(&mut self, directory: &Path) -> Option<String> {
+        let asset_directory = directory.join("assets");
+        if asset_directory.join(&self.properties.path).exists() {
+            return None;
+        }
+        let moved_path = Self::find_moved_image(&asset_directory, &self.properties.path)?;
+        Some(std::mem::replace(&mut self.properties.path, moved_path))
+    }
+    // looks for a file with the same name anywhere in the assets directory
+    // when there are multiple, the one closest to the original depth is used
+    fn find_moved_image(asset_directory: &Path, path: &str) -> Option<String> {
+        let original_path = Path::new(path);
+        let file_name = original_path.file_name()?;
+        let original_depth = original_path.components().count();
+        let mut found_paths = vec![];
+        Self::find_files_named(asset_directory, file_name, &mut found_paths);
+        found_paths
+            .into_iter()
+            .filter_map(|found_path| {
+                let relative_path = found_path.strip_prefix(asset_directory).ok()?;
+                let depth = relative_path.components().count();
+                Some((
+                    depth.abs_diff(original_depth),
+                    path_with_forward_slashes(relative_path),
+                ))
+            })
+            // sorting by path as well so the result doesn't depend on the order of the file system
+            .min()
+            .map(|(_, relative_path)| relative_path)
+    }
+    fn find_files_named(directory: &Path, file_name: &std::ffi::OsStr, found: &mut Vec<PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(directory) else {
+            return;
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            // symlinks are not followed to avoid loops
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                Self::find_files_named(&entry.path(), file_name, found);
+            } else if file_type.is_file() && entry.file_name() == file_name {
+                found.push(entry.path());
+            }
+        }
+    }
+    fn decode_image(path: &Path) -> Result<(DynamicImage, bool), BitmapLoadError> {
+        let reader =
+            ImageReader::open(path).map_err(|err| BitmapLoadError::from_io_error(err, path))?;
+        let mut decoder = reader
+            .into_decoder()
+            .map_err(|err| BitmapLoadError::from_image_error(err, path))?;
+        let has_color_profile = matches!(decoder.icc_profile(), Ok(Some(_)));
+        let image = DynamicImage::from_decoder(decoder)
+            .map_err(|err| BitmapLoadError::from_image_error(err, path))?;
+        Ok((image, has_color_profile))
     }
     pub fn invalidate_cache(&mut self) {
         self.cache = BitmapCacheStatus::Uncached;
@@ -342,6 +993,8 @@ pub struct BitmapProperties {
     pub path: String,
     #[serde(default)]
     pub animation: Option<Animation>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub do_not_export: bool,
 }
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct Animation {
@@ -357,10 +1010,12 @@ pub enum BitmapCacheStatus {
     #[default]
     Uncached,
     Cached(CachedBitmap),
-    Invalid(String),
+    Invalid(BitmapLoadError),
 }
 pub struct CachedBitmap {
     pub image: DynamicImage,
+    // the renderer frees the texture when the handle is dropped, so removing the bitmap
+    // or changing the cache status frees it without needing to tell the renderer
     pub bitmap_handle: Option<Box<dyn BitmapHandle>>,
 }
 pub trait BitmapHandle {
@@ -372,12 +1027,51 @@ pub struct MovieClip {
     pub properties: MovieClipProperties,
     pub place_symbols: Vec<PlaceSymbol>,
 }
+impl MovieClip {
+    // an empty movieclip with the default properties
+    pub fn new(name: impl Into<String>) -> MovieClip {
+        MovieClip {
+            properties: MovieClipProperties {
+                name: name.into(),
+                class_name: "".into(),
+                do_not_export: false,
+                sound_actions: vec![],
+            },
+            place_symbols: vec![],
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct MovieClipProperties {
     pub name: String,
     #[serde(default, skip_serializing_if = "is_default")]
     pub class_name: String,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub do_not_export: bool,
+    // sounds that start or stop when the movieclip is shown
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sound_actions: Vec<SoundAction>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct SoundAction {
+    pub sound_index: SymbolIndex,
+    pub action: SoundActionType,
+}
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub enum SoundActionType {
+    Start,
+    // doesn't start the sound again if it's already playing
+    StartIfNotPlaying,
+    Stop,
+}
+
+// actionscript 2 code that runs when the frame is shown
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct FrameScript {
+    pub name: String,
+    pub code: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -388,22 +1082,40 @@ pub struct PlaceSymbol {
     #[serde(default, skip_serializing_if = "is_default")]
     pub instance_name: String,
     pub text: Option<Box<TextProperties>>,
+    // only used for bitmaps
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub smoothing: bool,
+    // only used for bitmaps in the editor, swf files don't have this
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub pixel_snapping: PixelSnappingMode,
 }
 impl PlaceSymbol {
-    pub fn from_transform(
-        exisiting_place_symbol: PlaceSymbol,
-        transform: EditorTransform,
-    ) -> PlaceSymbol {
+    // placed at the origin without a name, text fields also need text properties
+    pub fn new(symbol_index: SymbolIndex) -> PlaceSymbol {
         PlaceSymbol {
-            symbol_index: exisiting_place_symbol.symbol_index,
-            transform,
-            instance_name: exisiting_place_symbol.instance_name,
-            text: exisiting_place_symbol.text,
+            symbol_index,
+            transform: EditorTransform::default(),
+            instance_name: "".into(),
+            text: None,
+            smoothing: false,
+            pixel_snapping: PixelSnappingMode::default(),
         }
     }
+    // moves the other fields instead of cloning them, the text can be large
+    pub fn with_transform(self, transform: EditorTransform) -> PlaceSymbol {
+        PlaceSymbol { transform, ..self }
+    }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum PixelSnappingMode {
+    #[default]
+    Never,
+    Always,
+    Auto,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct EditorTransform {
     pub x: f64,
     pub y: f64,
@@ -414,6 +1126,46 @@ pub struct EditorTransform {
     pub y_scale: f64,
 }
 
+// the identity transform, the symbol is placed at the origin without scaling
+impl Default for EditorTransform {
+    fn default() -> Self {
+        EditorTransform {
+            x: 0.0,
+            y: 0.0,
+            x_scale: 1.0,
+            y_scale: 1.0,
+        }
+    }
+}
+
+impl EditorTransform {
+    // t = 0 is self, t = 1 is other
+    pub fn lerp(&self, other: &Self, t: f64) -> Self {
+        let lerp = |from: f64, to: f64| from + (to - from) * t;
+        EditorTransform {
+            x: lerp(self.x, other.x),
+            y: lerp(self.y, other.y),
+            x_scale: lerp(self.x_scale, other.x_scale),
+            y_scale: lerp(self.y_scale, other.y_scale),
+        }
+    }
+    pub fn add_offset(&self, dx: f64, dy: f64) -> Self {
+        EditorTransform {
+            x: self.x + dx,
+            y: self.y + dy,
+            ..self.clone()
+        }
+    }
+    // scales the transform as if the pivot stays in place, e.g. when scaling from a corner
+    pub fn scale_around(&self, pivot_x: f64, pivot_y: f64, sx: f64, sy: f64) -> Self {
+        EditorTransform {
+            x: pivot_x + (self.x - pivot_x) * sx,
+            y: pivot_y + (self.y - pivot_y) * sy,
+            x_scale: self.x_scale * sx,
+            y_scale: self.y_scale * sy,
+        }
+    }
+}
 impl Into<Matrix> for EditorTransform {
     fn into(self) -> Matrix {
         create_box(
@@ -441,6 +1193,78 @@ fn create_box(
     }
 }
 
+// how a tween moves between two keyframes
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub enum EasingType {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    // the two control points of a cubic bezier from (0, 0) to (1, 1), like css cubic-bezier()
+    // the x of the control points is clamped between 0 and 1, like css only allows those values
+    Custom([(f64, f64); 2]),
+}
+impl EasingType {
+    // maps the progress of the tween (0 to 1) to the eased progress
+    pub fn apply(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            EasingType::Linear => t,
+            EasingType::EaseIn => t * t,
+            EasingType::EaseOut => t * (2.0 - t),
+            EasingType::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            }
+            EasingType::Custom([(x1, y1), (x2, y2)]) => {
+                let x1 = x1.clamp(0.0, 1.0);
+                let x2 = x2.clamp(0.0, 1.0);
+                let bezier = |p1: f64, p2: f64, s: f64| {
+                    3.0 * (1.0 - s) * (1.0 - s) * s * p1 + 3.0 * (1.0 - s) * s * s * p2 + s * s * s
+                };
+                // find the curve parameter for this x with bisection
+                // this works because x only increases, which is why the control points are clamped
+                let (mut low, mut high) = (0.0, 1.0);
+                for _ in 0..32 {
+                    let middle = (low + high) / 2.0;
+                    if bezier(x1, x2, middle) < t {
+                        low = middle;
+                    } else {
+                        high = middle;
+                    }
+                }
+                bezier(*y1, *y2, (low + high) / 2.0)
+            }
+        }
+    }
+}
+
+// the transform at progress t (0 to 1) of a tween from start to end
+pub fn interpolate_transform(
+    start: &EditorTransform,
+    end: &EditorTransform,
+    t: f64,
+    easing: &EasingType,
+) -> EditorTransform {
+    start.lerp(end, easing.apply(t))
+}
+
+// paths in the project and in zip files always use forward slashes, also on windows
+pub fn path_with_forward_slashes(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn latest_swf_version() -> u8 {
+    SWF_VERSION
+}
+
 fn one() -> f64 {
     1.0
 }
@@ -454,6 +1278,27 @@ fn is_default<T: Default + PartialEq>(t: &T) -> bool {
     t == &T::default()
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct FlitsSound {
+    // the name used to play the sound from code with attachSound
+    pub name: String,
+    pub path: String,
+}
+
+// text that can't be changed at runtime, cheaper than a text field for labels
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct StaticTextProperties {
+    pub name: String,
+    pub text: String,
+    // none when the font was removed
+    pub font_index: Option<SymbolIndex>,
+    pub size: f64,
+    pub color: EditorColor,
+    // the position of the top left of the text inside the symbol
+    pub x: f64,
+    pub y: f64,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct FlitsFont {
     pub path: String,
@@ -463,6 +1308,9 @@ pub struct FlitsFont {
 pub struct FontCharacters {
     pub ascii: bool,
     pub additional_characters: String,
+    // export only the characters used in text fields instead of the ones above
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub only_used: bool,
 }
 impl FontCharacters {
     pub fn characters(&self) -> String {
@@ -494,6 +1342,12 @@ pub struct TextProperties {
     pub is_html: bool,
     pub is_multiline: bool,
     pub word_wrap: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub underline: bool,
+
+    // when there are spans they are used instead of text, size and color
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spans: Vec<TextSpan>,
 }
 impl TextProperties {
     pub fn new() -> Self {
@@ -511,10 +1365,50 @@ impl TextProperties {
             is_html: false,
             is_multiline: false,
             word_wrap: false,
+            underline: false,
+
+            spans: vec![],
         }
     }
 }
+// f64 doesn't implement hash, so hash the bits of the numbers instead
+impl Hash for TextProperties {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.text.hash(state);
+        self.width.to_bits().hash(state);
+        self.height.to_bits().hash(state);
+        self.size.to_bits().hash(state);
+        self.color.hash(state);
+        self.align.hash(state);
+        self.editable.hash(state);
+        self.selectable.hash(state);
+        self.is_password.hash(state);
+        self.is_html.hash(state);
+        self.is_multiline.hash(state);
+        self.word_wrap.hash(state);
+        self.underline.hash(state);
+        self.spans.hash(state);
+    }
+}
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct TextSpan {
+    pub text: String,
+    pub font_symbol_index: SymbolIndex,
+    pub size: f64,
+    pub color: EditorColor,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub underline: bool,
+}
+impl Hash for TextSpan {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.text.hash(state);
+        self.font_symbol_index.hash(state);
+        self.size.to_bits().hash(state);
+        self.color.hash(state);
+        self.underline.hash(state);
+    }
+}
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Hash)]
 pub enum TextAlign {
     Left,
     Right,
@@ -531,3 +1425,358 @@ impl Into<swf::TextAlign> for TextAlign {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transform(x: f64, y: f64, x_scale: f64, y_scale: f64) -> EditorTransform {
+        EditorTransform {
+            x,
+            y,
+            x_scale,
+            y_scale,
+        }
+    }
+
+    #[test]
+    fn path_with_forward_slashes_joins_all_components() {
+        let path: PathBuf = ["images", "player", "idle.png"].iter().collect();
+        assert_eq!(path_with_forward_slashes(&path), "images/player/idle.png");
+    }
+
+    #[test]
+    fn lerp_interpolates_every_component() {
+        let start = transform(0.0, 10.0, 1.0, 2.0);
+        let end = transform(100.0, -10.0, 3.0, 2.0);
+        assert_eq!(start.lerp(&end, 0.0), start);
+        assert_eq!(start.lerp(&end, 1.0), end);
+        assert_eq!(start.lerp(&end, 0.5), transform(50.0, 0.0, 2.0, 2.0));
+    }
+
+    #[test]
+    fn default_transform_is_the_identity() {
+        let matrix: Matrix = EditorTransform::default().into();
+        assert_eq!(matrix, Matrix::IDENTITY);
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{} is not close to {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn easing_starts_at_0_and_ends_at_1() {
+        for easing in [
+            EasingType::Linear,
+            EasingType::EaseIn,
+            EasingType::EaseOut,
+            EasingType::EaseInOut,
+            EasingType::Custom([(0.25, 0.1), (0.25, 1.0)]),
+        ] {
+            assert_close(easing.apply(0.0), 0.0);
+            assert_close(easing.apply(1.0), 1.0);
+            // progress outside of the tween is clamped
+            assert_close(easing.apply(-1.0), 0.0);
+            assert_close(easing.apply(2.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn easing_curves() {
+        assert_close(EasingType::Linear.apply(0.25), 0.25);
+        assert_close(EasingType::EaseIn.apply(0.5), 0.25);
+        assert_close(EasingType::EaseOut.apply(0.5), 0.75);
+        assert_close(EasingType::EaseInOut.apply(0.25), 0.125);
+        assert_close(EasingType::EaseInOut.apply(0.5), 0.5);
+        assert_close(EasingType::EaseInOut.apply(0.75), 0.875);
+    }
+
+    #[test]
+    fn custom_easing_is_a_cubic_bezier() {
+        // control points on the diagonal are linear
+        let linear = EasingType::Custom([(1.0 / 3.0, 1.0 / 3.0), (2.0 / 3.0, 2.0 / 3.0)]);
+        for t in [0.1, 0.5, 0.9] {
+            assert_close(linear.apply(t), t);
+        }
+        // css 'ease'
+        let ease = EasingType::Custom([(0.25, 0.1), (0.25, 1.0)]);
+        assert_close(ease.apply(0.5), 0.802403);
+    }
+
+    #[test]
+    fn custom_easing_clamps_the_x_of_the_control_points() {
+        let out_of_range = EasingType::Custom([(-1.0, 0.0), (2.0, 1.0)]);
+        let clamped = EasingType::Custom([(0.0, 0.0), (1.0, 1.0)]);
+        let mut previous = 0.0;
+        for i in 0..=20 {
+            let t = i as f64 / 20.0;
+            let eased = out_of_range.apply(t);
+            assert_close(eased, clamped.apply(t));
+            assert!(eased >= previous);
+            previous = eased;
+        }
+    }
+
+    #[test]
+    fn interpolate_transform_uses_the_easing() {
+        let start = transform(0.0, 0.0, 1.0, 1.0);
+        let end = transform(100.0, 0.0, 1.0, 1.0);
+        assert_eq!(
+            interpolate_transform(&start, &end, 0.5, &EasingType::EaseIn),
+            transform(25.0, 0.0, 1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn add_offset_only_moves() {
+        let moved = transform(10.0, 20.0, 2.0, 3.0).add_offset(5.0, -20.0);
+        assert_eq!(moved, transform(15.0, 0.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn scale_around_keeps_the_pivot_in_place() {
+        let scaled = transform(10.0, 10.0, 1.0, 2.0).scale_around(0.0, 20.0, 2.0, 0.5);
+        assert_eq!(scaled, transform(20.0, 15.0, 2.0, 1.0));
+
+        // scaling around its own position only changes the scale
+        let scaled = transform(10.0, 10.0, 1.0, 1.0).scale_around(10.0, 10.0, 3.0, 3.0);
+        assert_eq!(scaled, transform(10.0, 10.0, 3.0, 3.0));
+    }
+
+    #[test]
+    fn num_frames_includes_the_preloader() {
+        let mut movie = Movie::default();
+        assert_eq!(movie.validate_num_frames().unwrap(), 1);
+        movie.properties.preloader = PreloaderType::WithPlayButton;
+        assert_eq!(movie.validate_num_frames().unwrap(), 3);
+    }
+
+    #[test]
+    fn num_frames_must_fit_in_the_swf_header() {
+        assert_eq!(Movie::header_num_frames(65535).unwrap(), 65535);
+        let err = Movie::header_num_frames(65536).unwrap_err();
+        assert_eq!(err.num_frames, 65536);
+        assert_eq!(
+            err.to_string(),
+            "The movie has 65536 frames, the maximum is 65535"
+        );
+    }
+
+    #[test]
+    fn zero_or_negative_dimensions_are_invalid() {
+        let mut movie = Movie::default();
+        assert!(movie.validate_dimensions().is_ok());
+        movie.properties.width = 0.0;
+        assert!(movie.validate_dimensions().is_err());
+        movie.properties.width = 640.0;
+        movie.properties.height = -10.0;
+        assert!(movie.validate_dimensions().is_err());
+    }
+
+    #[test]
+    fn saving_a_smaller_project_replaces_the_whole_file() {
+        let directory =
+            std::env::temp_dir().join(format!("flits-save-truncate-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("movie.json");
+
+        let mut movie = Movie::default();
+        for name in ["first", "second"] {
+            movie.symbols.push(Symbol::MovieClip(MovieClip::new(name)));
+        }
+        movie.save(&path).unwrap();
+        // the second save writes less json than the first, old bytes would make it invalid
+        movie.symbols.pop();
+        movie.save(&path).unwrap();
+
+        let result = Movie::load(path);
+        _ = std::fs::remove_dir_all(&directory);
+        assert_eq!(result.unwrap().symbols.len(), 1);
+    }
+
+    #[test]
+    fn reloading_assets_changes_the_path_of_moved_bitmaps() {
+        let directory =
+            std::env::temp_dir().join(format!("flits-moved-bitmap-{}", std::process::id()));
+        std::fs::create_dir_all(directory.join("assets/images")).unwrap();
+        image::RgbaImage::new(4, 4)
+            .save(directory.join("assets/images/square.png"))
+            .unwrap();
+        let mut movie = Movie::default();
+        movie.symbols.push(Symbol::Bitmap(Bitmap {
+            properties: BitmapProperties {
+                name: "square".into(),
+                path: "square.png".into(),
+                animation: None,
+                do_not_export: false,
+            },
+            cache: BitmapCacheStatus::Uncached,
+        }));
+
+        let issues = movie.reload_assets(&directory);
+        _ = std::fs::remove_dir_all(&directory);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].to_string(),
+            "Bitmap 'square' was not found at 'square.png', its path was changed to 'images/square.png'"
+        );
+        let Symbol::Bitmap(bitmap) = &movie.symbols[0] else {
+            unreachable!();
+        };
+        assert!(matches!(bitmap.cache, BitmapCacheStatus::Cached(_)));
+    }
+
+    #[test]
+    fn reloading_assets_reports_discarded_color_profiles() {
+        use image::ImageEncoder;
+
+        let directory =
+            std::env::temp_dir().join(format!("flits-color-profile-{}", std::process::id()));
+        std::fs::create_dir_all(directory.join("assets")).unwrap();
+        let mut png = vec![];
+        let mut encoder = image::codecs::png::PngEncoder::new(&mut png);
+        encoder.set_icc_profile(vec![0; 128]).unwrap();
+        encoder
+            .write_image(&[0; 4 * 4 * 4], 4, 4, image::ExtendedColorType::Rgba8)
+            .unwrap();
+        std::fs::write(directory.join("assets/profile.png"), png).unwrap();
+        image::RgbaImage::new(4, 4)
+            .save(directory.join("assets/plain.png"))
+            .unwrap();
+        let mut movie = Movie::default();
+
+        let issues = movie.reload_assets(&directory);
+        _ = std::fs::remove_dir_all(&directory);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].to_string(),
+            "The color profile of bitmap 'profile.png' is ignored, because Flash Player doesn't support color profiles"
+        );
+    }
+
+    #[test]
+    fn frame_rate_must_be_between_0_01_and_120() {
+        let mut movie = Movie::default();
+        assert!(movie.validate_frame_rate().is_ok());
+        for frame_rate in [0.0, -1.0, 121.0, f32::NAN] {
+            movie.properties.frame_rate = frame_rate;
+            assert!(movie.validate_frame_rate().is_err());
+        }
+    }
+
+    fn placed_symbol_indices(symbol: &Symbol) -> Vec<SymbolIndex> {
+        let Symbol::MovieClip(movieclip) = symbol else {
+            panic!("not a movieclip");
+        };
+        movieclip
+            .place_symbols
+            .iter()
+            .map(|place_symbol| place_symbol.symbol_index)
+            .collect()
+    }
+
+    fn font(path: &str) -> Symbol {
+        Symbol::Font(FlitsFont {
+            path: path.into(),
+            characters: FontCharacters {
+                ascii: true,
+                additional_characters: "".into(),
+                only_used: false,
+            },
+        })
+    }
+
+    #[test]
+    fn clone_symbol_deep_copies_a_child_used_twice_once() {
+        let mut movie = Movie::default();
+        movie
+            .symbols
+            .push(Symbol::MovieClip(MovieClip::new("child")));
+        movie.symbols.push(Symbol::MovieClip(MovieClip {
+            place_symbols: vec![PlaceSymbol::new(0), PlaceSymbol::new(0)],
+            ..MovieClip::new("clip")
+        }));
+
+        let clone = movie.clone_symbol_deep(1);
+        assert_eq!(movie.symbols.len(), 3);
+        assert_eq!(placed_symbol_indices(&clone), vec![2, 2]);
+        // the original still places the original child
+        assert_eq!(placed_symbol_indices(&movie.symbols[1]), vec![0, 0]);
+    }
+
+    #[test]
+    fn clone_symbol_deep_of_a_movieclip_that_contains_itself() {
+        let mut movie = Movie::default();
+        movie.symbols.push(Symbol::MovieClip(MovieClip {
+            place_symbols: vec![PlaceSymbol::new(0), PlaceSymbol::new(1)],
+            ..MovieClip::new("clip")
+        }));
+        movie.symbols.push(Symbol::MovieClip(MovieClip {
+            place_symbols: vec![PlaceSymbol::new(0)],
+            ..MovieClip::new("clip")
+        }));
+
+        let clone = movie.clone_symbol_deep(0);
+        assert_eq!(movie.symbols.len(), 3);
+        // the copy isn't in the movie, so it contains the original and a copy of the child
+        assert_eq!(placed_symbol_indices(&clone), vec![0, 2]);
+        assert_eq!(placed_symbol_indices(&movie.symbols[2]), vec![0]);
+    }
+
+    #[test]
+    fn clone_symbol_deep_copies_the_fonts_of_spans_and_static_texts() {
+        let mut movie = Movie::default();
+        movie.symbols.push(font("first.ttf"));
+        movie.symbols.push(font("second.ttf"));
+        movie.symbols.push(Symbol::StaticText(StaticTextProperties {
+            name: "static text".into(),
+            text: "static".into(),
+            font_index: Some(0),
+            size: 12.0,
+            color: EditorColor::BLACK,
+            x: 0.0,
+            y: 0.0,
+        }));
+        let mut text_properties = TextProperties::new();
+        text_properties.spans.push(TextSpan {
+            text: "span".into(),
+            font_symbol_index: 1,
+            size: 12.0,
+            color: EditorColor::BLACK,
+            underline: false,
+        });
+        let text_field = PlaceSymbol {
+            text: Some(Box::new(text_properties)),
+            ..PlaceSymbol::new(0)
+        };
+        movie.symbols.push(Symbol::MovieClip(MovieClip {
+            place_symbols: vec![text_field, PlaceSymbol::new(2)],
+            ..MovieClip::new("clip")
+        }));
+
+        let clone = movie.clone_symbol_deep(3);
+        // the text field font, the span font and the static text
+        assert_eq!(movie.symbols.len(), 7);
+        assert_eq!(placed_symbol_indices(&clone), vec![4, 6]);
+        let Symbol::MovieClip(movieclip) = &clone else {
+            unreachable!();
+        };
+        let spans = &movieclip.place_symbols[0].text.as_ref().unwrap().spans;
+        assert_eq!(spans[0].font_symbol_index, 5);
+        // the static text uses the same copy of the font as the text field
+        let Symbol::StaticText(static_text) = &movie.symbols[6] else {
+            panic!("not a static text");
+        };
+        assert_eq!(static_text.font_index, Some(4));
+
+        let Symbol::StaticText(static_text) = movie.clone_symbol_deep(2) else {
+            panic!("not a static text");
+        };
+        assert_eq!(static_text.font_index, Some(7));
+    }
+}
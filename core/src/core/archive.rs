@@ -0,0 +1,62 @@
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use crate::{path_with_forward_slashes, FlitsCoreError, Movie, Symbol};
+
+const README: &str = "This archive was created with Flits Editor.
+
+output.swf is the movie. It can be opened with Ruffle (https://ruffle.rs)
+or any other Flash Player. The assets directory contains the images,
+fonts and sounds the movie was made with.
+";
+
+// writes a zip with the exported swf and the assets used by the movie
+pub(super) fn create_archive(
+    movie: &Movie,
+    project_directory: &Path,
+    swf_path: &Path,
+    archive_path: &Path,
+) -> Result<(), FlitsCoreError> {
+    let mut zip = ZipWriter::new(File::create(archive_path)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file("output.swf", options)?;
+    zip.write_all(&std::fs::read(swf_path)?)?;
+
+    let asset_directory = project_directory.join("assets");
+    for asset_path in asset_paths(movie) {
+        let contents = std::fs::read(asset_directory.join(&asset_path))
+            .map_err(|err| format!("Unable to read asset '{}': {}", asset_path.display(), err))?;
+        let name = path_with_forward_slashes(&asset_path);
+        zip.start_file(format!("assets/{}", name), options)?;
+        zip.write_all(&contents)?;
+    }
+
+    zip.start_file("README.txt", options)?;
+    zip.write_all(README.as_bytes())?;
+
+    zip.finish()?;
+    Ok(())
+}
+
+fn asset_paths(movie: &Movie) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = movie
+        .symbols
+        .iter()
+        .filter_map(|symbol| match symbol {
+            Symbol::Bitmap(bitmap) => Some(bitmap.properties.path.clone().into()),
+            Symbol::Font(font) => Some(font.path.clone().into()),
+            Symbol::Sound(sound) => Some(sound.path.clone().into()),
+            Symbol::MovieClip(_) | Symbol::StaticText(_) => None,
+        })
+        .collect();
+    // multiple symbols can use the same file
+    paths.sort();
+    paths.dedup();
+    paths
+}
@@ -0,0 +1,159 @@
+use crate::{PlaceSymbol, StaticTextProperties};
+
+// an axis aligned rectangle in pixels
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Bounds {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+impl Bounds {
+    pub fn from_points(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+        Bounds {
+            min_x: x1.min(x2),
+            min_y: y1.min(y2),
+            max_x: x1.max(x2),
+            max_y: y1.max(y2),
+        }
+    }
+    // the union of the bounds of the placed symbols, None if none of them have bounds
+    pub fn from_placed_symbols<'a>(
+        placed_symbols: impl IntoIterator<Item = &'a PlaceSymbol>,
+        bounds_of_placed_symbol: impl Fn(&PlaceSymbol) -> Option<Bounds>,
+    ) -> Option<Self> {
+        placed_symbols
+            .into_iter()
+            .filter_map(bounds_of_placed_symbol)
+            .reduce(|total_bounds, bounds| total_bounds.union(&bounds))
+    }
+    // TODO: use the font metrics instead of estimating the size
+    pub fn of_static_text(static_text: &StaticTextProperties) -> Self {
+        let longest_line = static_text
+            .text
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let line_count = static_text.text.lines().count().max(1);
+        Bounds {
+            min_x: static_text.x,
+            min_y: static_text.y,
+            max_x: static_text.x + longest_line as f64 * static_text.size * 0.6,
+            max_y: static_text.y + line_count as f64 * static_text.size * 1.2,
+        }
+    }
+
+    pub fn contains(&self, other: &Self) -> bool {
+        other.min_x >= self.min_x
+            && other.min_y >= self.min_y
+            && other.max_x <= self.max_x
+            && other.max_y <= self.max_y
+    }
+    pub fn contains_point(&self, x: f64, y: f64) -> bool {
+        x > self.min_x && y > self.min_y && x < self.max_x && y < self.max_y
+    }
+    pub fn union(&self, other: &Self) -> Self {
+        Bounds {
+            min_x: self.min_x.min(other.min_x),
+            min_y: self.min_y.min(other.min_y),
+            max_x: self.max_x.max(other.max_x),
+            max_y: self.max_y.max(other.max_y),
+        }
+    }
+    // None when the bounds don't overlap
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let intersection = Bounds {
+            min_x: self.min_x.max(other.min_x),
+            min_y: self.min_y.max(other.min_y),
+            max_x: self.max_x.min(other.max_x),
+            max_y: self.max_y.min(other.max_y),
+        };
+        if intersection.min_x > intersection.max_x || intersection.min_y > intersection.max_y {
+            return None;
+        }
+        Some(intersection)
+    }
+    pub fn width(&self) -> f64 {
+        self.max_x - self.min_x
+    }
+    pub fn height(&self) -> f64 {
+        self.max_y - self.min_y
+    }
+    pub fn area(&self) -> f64 {
+        self.width() * self.height()
+    }
+    pub fn center(&self) -> (f64, f64) {
+        (
+            (self.min_x + self.max_x) / 2.0,
+            (self.min_y + self.max_y) / 2.0,
+        )
+    }
+    // grows the bounds by amount on every side, a negative amount shrinks them
+    pub fn expand(&self, amount: f64) -> Self {
+        Bounds {
+            min_x: self.min_x - amount,
+            min_y: self.min_y - amount,
+            max_x: self.max_x + amount,
+            max_y: self.max_y + amount,
+        }
+    }
+    // the axis aligned bounds of rect after rotating it around the origin
+    pub fn rotate_and_bound(rect: Bounds, angle_degrees: f64) -> Bounds {
+        let (sin, cos) = angle_degrees.to_radians().sin_cos();
+        let rotate = |x: f64, y: f64| (x * cos - y * sin, x * sin + y * cos);
+        let corners = [
+            rotate(rect.min_x, rect.min_y),
+            rotate(rect.max_x, rect.min_y),
+            rotate(rect.max_x, rect.max_y),
+            rotate(rect.min_x, rect.max_y),
+        ];
+        corners.iter().skip(1).fold(
+            Bounds::from_points(corners[0].0, corners[0].1, corners[0].0, corners[0].1),
+            |bounds, (x, y)| bounds.union(&Bounds::from_points(*x, *y, *x, *y)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_bounds_eq(actual: Bounds, expected: Bounds) {
+        let epsilon = 1e-9;
+        assert!(
+            (actual.min_x - expected.min_x).abs() < epsilon
+                && (actual.min_y - expected.min_y).abs() < epsilon
+                && (actual.max_x - expected.max_x).abs() < epsilon
+                && (actual.max_y - expected.max_y).abs() < epsilon,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn rotating_by_zero_keeps_the_bounds() {
+        let rect = Bounds::from_points(-10.0, -5.0, 30.0, 15.0);
+        assert_bounds_eq(Bounds::rotate_and_bound(rect, 0.0), rect);
+    }
+
+    #[test]
+    fn rotating_a_quarter_turn_swaps_width_and_height() {
+        let rect = Bounds::from_points(0.0, 0.0, 100.0, 50.0);
+        assert_bounds_eq(
+            Bounds::rotate_and_bound(rect, 90.0),
+            Bounds::from_points(-50.0, 0.0, 0.0, 100.0),
+        );
+    }
+
+    #[test]
+    fn rotated_bounds_contain_the_corners() {
+        let rect = Bounds::from_points(-1.0, -1.0, 1.0, 1.0);
+        let half_diagonal = 2.0_f64.sqrt();
+        assert_bounds_eq(
+            Bounds::rotate_and_bound(rect, 45.0),
+            Bounds::from_points(-half_diagonal, -half_diagonal, half_diagonal, half_diagonal),
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::SymbolIndex;
+
+#[derive(Debug, Error)]
+pub enum FlitsCoreError {
+    #[error("{0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Invalid project file: {0}")]
+    JsonParseError(#[from] serde_json::Error),
+    #[error("Error decoding '{path}': {source}")]
+    ImageDecodeError {
+        path: String,
+        #[source]
+        source: image::ImageError,
+    },
+    #[error("{}", .0.iter().map(|error| error.to_string()).collect::<Vec<_>>().join("\n"))]
+    ExportValidationError(Vec<ValidationError>),
+    #[error("{0}")]
+    FontError(String),
+    #[error("Project is too large: exceeded 65535 SWF characters")]
+    CharacterIdExhausted,
+    #[error("{0}")]
+    SwfError(#[from] swf::error::Error),
+    #[error("{0}")]
+    ArchiveError(#[from] zip::result::ZipError),
+    // errors that only need a message, like unsupported files or mtasc output
+    #[error("{0}")]
+    Other(String),
+}
+impl From<String> for FlitsCoreError {
+    fn from(message: String) -> Self {
+        FlitsCoreError::Other(message)
+    }
+}
+impl From<&str> for FlitsCoreError {
+    fn from(message: &str) -> Self {
+        FlitsCoreError::Other(message.into())
+    }
+}
+impl From<std::str::Utf8Error> for FlitsCoreError {
+    fn from(error: std::str::Utf8Error) -> Self {
+        FlitsCoreError::Other(error.to_string())
+    }
+}
+impl From<hound::Error> for FlitsCoreError {
+    fn from(error: hound::Error) -> Self {
+        FlitsCoreError::Other(error.to_string())
+    }
+}
+impl From<puremp3::Error> for FlitsCoreError {
+    fn from(error: puremp3::Error) -> Self {
+        FlitsCoreError::Other(error.to_string())
+    }
+}
+
+// problems with the movie found before building the swf
+#[derive(Debug, Error)]
+pub enum ValidationError {
+    #[error("Preloader graphic {0} is not a movieclip")]
+    PreloaderMovieclipNotFound(SymbolIndex),
+    #[error(
+        "Invalid stage size {width}x{height}, the width and height must be between 1 and 10000"
+    )]
+    InvalidDimensions { width: f64, height: f64 },
+    #[error("Invalid frame rate {0}, the frame rate must be between 0.01 and 120")]
+    InvalidFrameRate(f32),
+    #[error(transparent)]
+    TooManyFrames(#[from] TooManyFramesError),
+}
+
+// the swf header stores the number of frames as a u16
+#[derive(Debug, Error)]
+#[error("The movie has {num_frames} frames, the maximum is 65535")]
+pub struct TooManyFramesError {
+    pub num_frames: u32,
+}
+
+// why a bitmap couldn't be loaded, so the editor can suggest how to fix it
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum BitmapLoadError {
+    #[error("File not found: '{}'", .0.display())]
+    FileNotFound(PathBuf),
+    #[error("Unsupported image format: {0}")]
+    UnsupportedFormat(String),
+    #[error("Unable to decode image: {0}")]
+    DecodeError(String),
+    #[error("Permission denied: '{}'", .0.display())]
+    PermissionDenied(PathBuf),
+}
+impl BitmapLoadError {
+    pub(crate) fn from_io_error(error: std::io::Error, path: &Path) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::NotFound => BitmapLoadError::FileNotFound(path.to_path_buf()),
+            std::io::ErrorKind::PermissionDenied => {
+                BitmapLoadError::PermissionDenied(path.to_path_buf())
+            }
+            _ => BitmapLoadError::DecodeError(error.to_string()),
+        }
+    }
+    pub(crate) fn from_image_error(error: image::ImageError, path: &Path) -> Self {
+        match error {
+            image::ImageError::IoError(error) => BitmapLoadError::from_io_error(error, path),
+            image::ImageError::Unsupported(error) => {
+                BitmapLoadError::UnsupportedFormat(error.to_string())
+            }
+            _ => BitmapLoadError::DecodeError(error.to_string()),
+        }
+    }
+}
@@ -1,38 +1,81 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Sender},
+};
 
 use font::build_font;
+use rayon::prelude::*;
 use swf::*;
 use text_field::build_text_field;
 use typed_arena::Arena;
 
 use self::{
     as2::compile_as2,
-    audio::build_audio,
-    bitmap::build_bitmap,
+    audio::build_sound,
+    bitmap::{build_bitmap, compress_bitmap, smoothed_bitmap_character_id, CompressedBitmap},
+    compatibility::CompatibilityChecker,
+    manifest::write_manifest,
     movieclip::{build_movieclip_inner, build_movieclip_outer},
-    preloader::build_preloader,
+    preloader::{build_preloader, symbol_dependencies},
+    static_text::build_static_text,
 };
 
 use super::{
-    FlitsFont, Movie, PlaceSymbol, PreloaderType, Symbol, SymbolIndex, TextProperties, SWF_VERSION,
+    BitmapProperties, FlitsCoreError, FlitsFont, Movie, PlaceSymbol, PreloaderType, Symbol,
+    SymbolIndex, TextProperties, ValidationError,
 };
 
 mod as2;
 mod audio;
 mod bitmap;
+mod compatibility;
 mod font;
+mod manifest;
 mod movieclip;
 mod preloader;
+mod static_text;
 mod text_field;
 
+#[derive(Clone, Default)]
+pub struct ExportOptions {
+    // leave out symbols that aren't placed anywhere
+    pub prune_unused: bool,
+    // receives the progress of the export from 0 to 1
+    pub progress: Option<Sender<f32>>,
+}
+
+pub use self::compatibility::CompatibilityWarning;
+pub use self::manifest::{ExportManifest, ManifestCharacter};
+
+pub struct ExportResult {
+    // names of the symbols that were left out because they aren't used
+    pub pruned_symbols: Vec<String>,
+    // problems that don't stop the export
+    pub warnings: Vec<String>,
+    // features that are used but not supported by the swf version of the movie
+    pub compatibility_warnings: Vec<CompatibilityWarning>,
+}
+
 pub fn export_movie_to_swf<'a>(
     movie: &Movie,
     project_directory: PathBuf,
     swf_path: PathBuf,
-) -> Result<(), Box<dyn std::error::Error>> {
+    options: &ExportOptions,
+) -> Result<ExportResult, FlitsCoreError> {
+    let errors: Vec<ValidationError> = [movie.validate_dimensions(), movie.validate_frame_rate()]
+        .into_iter()
+        .filter_map(Result::err)
+        .collect();
+    if !errors.is_empty() {
+        return Err(FlitsCoreError::ExportValidationError(errors));
+    }
+    let num_frames = movie
+        .validate_num_frames()
+        .map_err(|err| FlitsCoreError::ExportValidationError(vec![err.into()]))?;
     let header = Header {
         compression: Compression::Zlib,
-        version: SWF_VERSION,
+        version: movie.properties.swf_version,
         stage_size: Rectangle {
             x_min: Twips::from_pixels(0.0),
             x_max: Twips::from_pixels(movie.properties.width),
@@ -40,28 +83,87 @@ pub fn export_movie_to_swf<'a>(
             y_max: Twips::from_pixels(movie.properties.height),
         },
         frame_rate: Fixed8::from_f32(movie.properties.frame_rate),
-        num_frames: movie.num_frames(),
+        num_frames,
     };
     let mut tags = vec![Tag::SetBackgroundColor(
         movie.properties.background_color.clone().into(),
     )];
 
+    let report_progress = |progress: f32| {
+        if let Some(sender) = &options.progress {
+            // nobody listening anymore is fine, the export continues
+            _ = sender.send(progress);
+        }
+    };
+
     let mut swf_builder = SwfBuilder::new();
     let arenas = Arenas::new();
-    if movie.properties.preloader != PreloaderType::None {
-        build_preloader(
-            movie.properties.preloader.clone(),
-            &mut swf_builder,
-            &arenas,
-            movie.properties.width,
-            movie.properties.height,
-        )?;
-    }
+    // the custom graphic of the preloader and everything it uses
+    // has to be defined before the preloader frame
+    let preloader_graphic_index = match &movie.properties.preloader {
+        PreloaderType::CustomGraphic { movieclip_index } => {
+            if !matches!(
+                movie.symbols.get(*movieclip_index),
+                Some(Symbol::MovieClip(_))
+            ) {
+                return Err(FlitsCoreError::ExportValidationError(vec![
+                    ValidationError::PreloaderMovieclipNotFound(*movieclip_index),
+                ]));
+            }
+            Some(*movieclip_index)
+        }
+        _ => None,
+    };
+    let preloader_symbols = match preloader_graphic_index {
+        Some(symbol_index) => symbol_dependencies(&movie.symbols, symbol_index),
+        None => HashSet::new(),
+    };
+    let pruned_symbols: HashSet<SymbolIndex> = if options.prune_unused {
+        let used_symbols = used_symbols(movie, &preloader_symbols);
+        (0..movie.symbols.len())
+            .filter(|symbol_index| !used_symbols.contains(symbol_index))
+            .collect()
+    } else {
+        HashSet::new()
+    };
+    let excluded_symbols: HashSet<SymbolIndex> = (0..movie.symbols.len())
+        .filter(|symbol_index| {
+            pruned_symbols.contains(symbol_index) || !movie.symbols[*symbol_index].is_exported()
+        })
+        .collect();
+    let warnings = not_exported_symbol_warnings(movie, &excluded_symbols);
+    swf_builder.state.excluded_symbols = excluded_symbols;
     build_library(
-        &movie.symbols,
+        movie,
         &mut swf_builder,
         &arenas,
         project_directory.clone(),
+        &report_progress,
+        &preloader_symbols,
+        |swf_builder| {
+            if movie.properties.preloader == PreloaderType::None {
+                return Ok(());
+            }
+            let preloader_graphic_id = match preloader_graphic_index {
+                Some(symbol_index) => Some(
+                    *swf_builder
+                        .state
+                        .symbol_index_to_character_id
+                        .get(&symbol_index)
+                        .ok_or("The movieclip of the preloader is not exported")?,
+                ),
+                None => None,
+            };
+            build_preloader(
+                movie.properties.preloader.clone(),
+                &movie.properties.preloader_style,
+                preloader_graphic_id,
+                swf_builder,
+                &arenas,
+                movie.properties.width,
+                movie.properties.height,
+            )
+        },
     )?;
     build_placed_symbols_of_root(&movie.root, &mut swf_builder, &arenas)?;
 
@@ -69,55 +171,245 @@ pub fn export_movie_to_swf<'a>(
         tags.push(tag);
     }
 
+    let mut compatibility_checker = CompatibilityChecker::new(movie.properties.swf_version);
+    compatibility_checker.check_header(&header);
+    compatibility_checker.check_tags(&tags);
+
     let file = std::fs::File::create(swf_path.clone())?;
     let writer = std::io::BufWriter::new(file);
     swf::write_swf(&header, &tags, writer)?;
+    report_progress(0.9);
 
-    compile_as2(
+    let has_classes = compile_as2(
         &movie,
         &swf_builder.state.symbol_index_to_character_id,
         project_directory,
-        swf_path,
+        swf_path.clone(),
     )?;
+    if has_classes {
+        // mtasc compiles against the version 8 standard library
+        compatibility_checker.require("ActionScript 2 classes", 8);
+    }
 
-    Ok(())
+    // mtasc changes the swf, so the size is only known now
+    let swf_size = std::fs::metadata(&swf_path)?.len();
+    let manifest = movie.export_manifest(&swf_builder.state.symbol_index_to_character_id, swf_size);
+    write_manifest(&manifest, &swf_path)?;
+
+    report_progress(1.0);
+
+    let mut pruned_symbols: Vec<SymbolIndex> = pruned_symbols.into_iter().collect();
+    pruned_symbols.sort();
+    Ok(ExportResult {
+        pruned_symbols: pruned_symbols
+            .into_iter()
+            .map(|symbol_index| movie.symbols[symbol_index].name())
+            .collect(),
+        warnings,
+        compatibility_warnings: compatibility_checker.into_warnings(),
+    })
+}
+
+// the symbols that can be reached from the root, from code or from the preloader
+fn used_symbols(movie: &Movie, preloader_symbols: &HashSet<SymbolIndex>) -> HashSet<SymbolIndex> {
+    let placed_on_root = movie.root.iter().flat_map(|place_symbol| {
+        // fonts can also be used by the spans of a text field
+        let span_fonts = place_symbol
+            .text
+            .iter()
+            .flat_map(|text| text.spans.iter().map(|span| span.font_symbol_index));
+        std::iter::once(place_symbol.symbol_index).chain(span_fonts)
+    });
+    // movieclips with a class are probably used from code
+    // and sounds can only be played from code or from sound actions
+    let used_from_code = movie
+        .symbols
+        .iter()
+        .enumerate()
+        .filter(|(_, symbol)| match symbol {
+            Symbol::MovieClip(movieclip) => !movieclip.properties.class_name.is_empty(),
+            Symbol::Sound(_) => true,
+            _ => false,
+        })
+        .map(|(symbol_index, _)| symbol_index);
+    let mut used_symbols = preloader_symbols.clone();
+    for symbol_index in placed_on_root.chain(used_from_code) {
+        if !used_symbols.contains(&symbol_index) {
+            used_symbols.extend(symbol_dependencies(&movie.symbols, symbol_index));
+        }
+    }
+    used_symbols
+}
+
+// placed symbols of symbols that aren't exported are left out, warn about that
+fn not_exported_symbol_warnings(
+    movie: &Movie,
+    excluded_symbols: &HashSet<SymbolIndex>,
+) -> Vec<String> {
+    let mut warnings = vec![];
+    let mut check_placed_symbols = |container_name: &str, placed_symbols: &Vec<PlaceSymbol>| {
+        for place_symbol in placed_symbols {
+            let symbol = &movie.symbols[place_symbol.symbol_index];
+            if !symbol.is_exported() {
+                warnings.push(format!(
+                    "'{}' is placed in '{}' but is marked as do not export",
+                    symbol.name(),
+                    container_name
+                ));
+            }
+        }
+    };
+    check_placed_symbols("Scene", &movie.root);
+    for (symbol_index, symbol) in movie.symbols.iter().enumerate() {
+        if let Symbol::MovieClip(movieclip) = symbol {
+            if !excluded_symbols.contains(&symbol_index) {
+                check_placed_symbols(&movieclip.properties.name, &movieclip.place_symbols);
+            }
+        }
+    }
+    warnings
+}
+
+// compressing the bitmaps takes most of the time, so it happens on all cores at once
+// the rest of the export counts as the last 20% of the progress
+fn compress_bitmaps<'a>(
+    symbols: impl Iterator<Item = (SymbolIndex, &'a Symbol)>,
+    directory: &Path,
+    report_progress: &dyn Fn(f32),
+) -> Result<HashMap<SymbolIndex, CompressedBitmap>, FlitsCoreError> {
+    let bitmaps: Vec<(SymbolIndex, &BitmapProperties)> = symbols
+        .filter_map(|(symbol_index, symbol)| match symbol {
+            Symbol::Bitmap(bitmap) => Some((symbol_index, &bitmap.properties)),
+            _ => None,
+        })
+        .collect();
+    let bitmap_count = bitmaps.len().max(1) as f32;
+    let (done_sender, done_receiver) = mpsc::channel();
+    let compressed_bitmaps = std::thread::scope(|scope| {
+        let compressing = scope.spawn(|| {
+            bitmaps
+                .par_iter()
+                .map_with(done_sender, |done_sender, (symbol_index, properties)| {
+                    let compressed_bitmap = compress_bitmap(properties, directory);
+                    _ = done_sender.send(());
+                    compressed_bitmap.map(|compressed_bitmap| (*symbol_index, compressed_bitmap))
+                })
+                .collect::<Result<HashMap<_, _>, _>>()
+        });
+        // the channel closes when all bitmaps are done
+        for (done_count, ()) in done_receiver.iter().enumerate() {
+            report_progress((done_count + 1) as f32 / bitmap_count * 0.8);
+        }
+        compressing
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })?;
+    Ok(compressed_bitmaps)
 }
 
 fn build_library<'a>(
-    symbols: &Vec<Symbol>,
+    movie: &Movie,
     swf_builder: &mut SwfBuilder<'a>,
     arenas: &'a Arenas,
     directory: PathBuf,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let mut symbol_index: SymbolIndex = 0;
-    for symbol in symbols {
+    report_progress: &dyn Fn(f32),
+    // the custom graphic of the preloader and everything it uses
+    preloader_symbols: &HashSet<SymbolIndex>,
+    // called after the symbols of the preloader are built and before the rest
+    build_preloader_frame: impl FnOnce(&mut SwfBuilder<'a>) -> Result<(), FlitsCoreError>,
+) -> Result<(), FlitsCoreError> {
+    let excluded_symbols = &swf_builder.state.excluded_symbols;
+    let (graphic_symbols, other_symbols): (Vec<_>, Vec<_>) = movie
+        .symbols
+        .iter()
+        .enumerate()
+        .filter(|(symbol_index, _)| !excluded_symbols.contains(symbol_index))
+        .partition(|(symbol_index, _)| preloader_symbols.contains(symbol_index));
+
+    let mut compressed_bitmaps = compress_bitmaps(
+        graphic_symbols.iter().chain(&other_symbols).copied(),
+        &directory,
+        report_progress,
+    )?;
+
+    // the preloader frame comes before the rest of the library, so its graphic has to be defined first
+    build_symbols(
+        movie,
+        &graphic_symbols,
+        &mut compressed_bitmaps,
+        swf_builder,
+        arenas,
+        &directory,
+    )?;
+    build_preloader_frame(swf_builder)?;
+    build_symbols(
+        movie,
+        &other_symbols,
+        &mut compressed_bitmaps,
+        swf_builder,
+        arenas,
+        &directory,
+    )
+}
+
+fn build_symbols<'a>(
+    movie: &Movie,
+    symbols: &[(SymbolIndex, &Symbol)],
+    compressed_bitmaps: &mut HashMap<SymbolIndex, CompressedBitmap>,
+    swf_builder: &mut SwfBuilder<'a>,
+    arenas: &'a Arenas,
+    directory: &Path,
+) -> Result<(), FlitsCoreError> {
+    // build everything except movieclips first, so the tags that define them always come
+    // before the movieclips that use them, regardless of the order of the symbols
+    for (symbol_index, symbol) in symbols.iter().copied() {
         match symbol {
             Symbol::Bitmap(bitmap) => {
-                build_bitmap(symbol_index, bitmap, swf_builder, arenas, directory.clone())?
-            }
-            Symbol::MovieClip(movieclip) => {
-                build_movieclip_outer(symbol_index, movieclip, swf_builder)?
-            }
-            Symbol::Font(font) => {
-                build_font(symbol_index, font, swf_builder, arenas, directory.clone())?
+                let compressed_bitmap = compressed_bitmaps
+                    .remove(&symbol_index)
+                    .ok_or("Bitmap was not compressed")?;
+                build_bitmap(symbol_index, bitmap, compressed_bitmap, swf_builder, arenas)?
             }
+            Symbol::Font(font) => build_font(
+                symbol_index,
+                font,
+                movie.exported_font_characters(symbol_index),
+                swf_builder,
+                arenas,
+                directory.to_path_buf(),
+            )?,
+            Symbol::Sound(sound) => build_sound(
+                symbol_index,
+                sound,
+                swf_builder,
+                arenas,
+                directory.to_path_buf(),
+            )?,
+            Symbol::MovieClip(_) | Symbol::StaticText(_) => {}
+        }
+    }
+
+    // static text uses the glyphs of the fonts, so build it after all the fonts
+    for (symbol_index, symbol) in symbols.iter().copied() {
+        if let Symbol::StaticText(static_text) = symbol {
+            build_static_text(symbol_index, static_text, swf_builder)?
+        }
+    }
+
+    // movieclips can place each other, so assign all their character ids before creating their inner tags
+    for (symbol_index, symbol) in symbols.iter().copied() {
+        if let Symbol::MovieClip(movieclip) = symbol {
+            build_movieclip_outer(symbol_index, movieclip, swf_builder)?
         }
-        symbol_index += 1;
     }
 
     // create the inner tags of movieclips after we've assigned all the character ids to make sure
     // the character ids for all the symbols exist
-    symbol_index = 0;
-    for symbol in symbols {
-        match symbol {
-            Symbol::MovieClip(movieclip) => {
-                build_movieclip_inner(symbol_index, movieclip, swf_builder, arenas)?
-            }
-            _ => {}
+    for (symbol_index, symbol) in symbols.iter().copied() {
+        if let Symbol::MovieClip(movieclip) = symbol {
+            build_movieclip_inner(symbol_index, movieclip, swf_builder, arenas)?
         }
-        symbol_index += 1;
     }
-    build_audio(swf_builder, &arenas, directory)?;
     Ok(())
 }
 
@@ -127,9 +419,16 @@ struct SwfBuilder<'a> {
 }
 #[derive(Clone)]
 struct SwfBuilderState {
-    character_id_counter: CharacterId,
+    // larger than a CharacterId so running out of ids can be detected
+    character_id_counter: u32,
     symbol_index_to_character_id: HashMap<SymbolIndex, CharacterId>,
     symbol_index_to_tag_index: HashMap<SymbolIndex, usize>,
+    // bitmaps only get a smoothed shape when a placed symbol uses it
+    symbol_index_to_smoothed_character_id: HashMap<SymbolIndex, CharacterId>,
+    // the family name of the font, text spans use this to refer to the font
+    symbol_index_to_font_name: HashMap<SymbolIndex, String>,
+    // pruned symbols and symbols marked as do not export
+    excluded_symbols: HashSet<SymbolIndex>,
 }
 
 impl<'a> SwfBuilder<'a> {
@@ -140,6 +439,9 @@ impl<'a> SwfBuilder<'a> {
                 character_id_counter: 1,
                 symbol_index_to_character_id: HashMap::new(),
                 symbol_index_to_tag_index: HashMap::new(),
+                symbol_index_to_smoothed_character_id: HashMap::new(),
+                symbol_index_to_font_name: HashMap::new(),
+                excluded_symbols: HashSet::new(),
             },
         }
     }
@@ -149,10 +451,11 @@ impl<'a> SwfBuilder<'a> {
             state,
         }
     }
-    fn next_character_id(&mut self) -> CharacterId {
-        let character_id = self.state.character_id_counter;
+    fn next_character_id(&mut self) -> Result<CharacterId, FlitsCoreError> {
+        let character_id = CharacterId::try_from(self.state.character_id_counter)
+            .map_err(|_| FlitsCoreError::CharacterIdExhausted)?;
         self.state.character_id_counter += 1;
-        character_id
+        Ok(character_id)
     }
 }
 
@@ -186,7 +489,7 @@ fn build_placed_symbols_of_root<'a>(
     placed_symbols: &Vec<PlaceSymbol>,
     swf_builder: &mut SwfBuilder<'a>,
     arenas: &'a Arenas,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<(), FlitsCoreError> {
     let mut tags = vec![];
     for tag in get_placed_symbols_tags(placed_symbols, swf_builder, arenas)? {
         tags.push(tag);
@@ -200,10 +503,17 @@ fn get_placed_symbols_tags<'a>(
     placed_symbols: &Vec<PlaceSymbol>,
     swf_builder: &mut SwfBuilder<'a>,
     arenas: &'a Arenas,
-) -> Result<Vec<Tag<'a>>, Box<dyn std::error::Error>> {
+) -> Result<Vec<Tag<'a>>, FlitsCoreError> {
     let mut i = 0;
     let mut tags = vec![];
     for place_symbol in placed_symbols {
+        if swf_builder
+            .state
+            .excluded_symbols
+            .contains(&place_symbol.symbol_index)
+        {
+            continue;
+        }
         let mut matrix: Matrix = place_symbol.transform.clone().into();
         let tag_index = swf_builder
             .state
@@ -234,9 +544,17 @@ fn get_placed_symbols_tags<'a>(
                 )
             })?;
 
+        if place_symbol.smoothing {
+            if let Some(smoothed_character_id) =
+                smoothed_bitmap_character_id(place_symbol.symbol_index, swf_builder)?
+            {
+                character_id = smoothed_character_id;
+            }
+        }
+
         if let Some(text) = &place_symbol.text {
             // change the character id to the text field instead of the font
-            character_id = build_text_field(character_id, text, swf_builder, arenas);
+            character_id = build_text_field(character_id, text, swf_builder, arenas)?;
 
             // use the coordinates as the center of text fields instead of the top left
             // we can't just set the bounds right because Ruffle selections get glitchy when
@@ -318,7 +636,7 @@ impl FontContainer {
         &mut self,
         fonts: &Vec<(usize, FlitsFont)>,
         directory: PathBuf,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), FlitsCoreError> {
         let mut swf_fonts = Vec::new();
 
         let mut swf_builder = SwfBuilder::from_state(self.swf_builder_state.clone());
@@ -327,6 +645,9 @@ impl FontContainer {
             build_font(
                 *font_symbol_index,
                 &font,
+                // the editor shows the characters chosen for the font,
+                // even when only the used ones are exported
+                font.characters.characters(),
                 &mut swf_builder,
                 &self.arenas,
                 directory.clone(),
@@ -365,18 +686,33 @@ impl FontContainer {
         &'a mut self,
         font_symbol_index: usize,
         text: TextProperties,
-    ) -> Result<EditText<'a>, Box<dyn std::error::Error>> {
+    ) -> Result<EditText<'a>, FlitsCoreError> {
+        // the state is thrown away afterwards, otherwise every conversion would use up a character id
+        // and the ids would run out after converting text fields for a while
         let mut swf_builder = SwfBuilder::from_state(self.swf_builder_state.clone());
 
         let font_character_id = swf_builder.state.symbol_index_to_character_id[&font_symbol_index];
-        build_text_field(font_character_id, &text, &mut swf_builder, &self.arenas);
+        build_text_field(font_character_id, &text, &mut swf_builder, &self.arenas)?;
         if let swf::Tag::DefineEditText(edit_text) = swf_builder.tags.last().unwrap() {
-            self.swf_builder_state = swf_builder.state;
             return Ok(*edit_text.clone());
         }
 
-        self.swf_builder_state = swf_builder.state;
-
         Err("EditText is not the last tag".into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{FlitsCoreError, SwfBuilder};
+
+    #[test]
+    fn character_ids_run_out_after_65535() {
+        let mut swf_builder = SwfBuilder::new();
+        swf_builder.state.character_id_counter = u16::MAX as u32;
+        assert_eq!(swf_builder.next_character_id().unwrap(), u16::MAX);
+        assert!(matches!(
+            swf_builder.next_character_id(),
+            Err(FlitsCoreError::CharacterIdExhausted)
+        ));
+    }
+}
@@ -1,18 +1,35 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 use swf::{
     avm1::types::{Action, ConstantPool, Push},
     CharacterId, SwfStr, Tag,
 };
 
-use crate::core::{Movie, Symbol, SymbolIndex};
+use crate::core::{FlitsCoreError, FrameScript, Movie, Symbol, SymbolIndex};
+
+// every export gets its own directory for the root scripts, editors in other tabs can export at the same time
+static NEXT_ROOT_SCRIPTS_DIR_ID: AtomicU64 = AtomicU64::new(0);
+
+// the generated classes are only needed while compiling, the directory is removed when this is dropped
+// so it is also removed when writing a class or running mtasc fails
+struct TempDir(PathBuf);
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        _ = std::fs::remove_dir_all(&self.0);
+    }
+}
 
+// returns true if there were classes or root scripts to compile
 pub(super) fn compile_as2(
     movie: &Movie,
     symbol_index_to_character_id: &HashMap<SymbolIndex, CharacterId>,
     project_directory: PathBuf,
     swf_path: PathBuf,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<bool, FlitsCoreError> {
     let dependencies_dir = std::env::current_exe()?
         .parent()
         .ok_or("Editor executable is not in a directory")?
@@ -40,8 +57,30 @@ pub(super) fn compile_as2(
         at_least_one_file = true;
     }
 
+    // mtasc only compiles classes, so every root script becomes a class with a static function
+    let root_scripts_dir = TempDir(std::env::temp_dir().join(format!(
+        "flits-root-scripts-{}-{}",
+        std::process::id(),
+        NEXT_ROOT_SCRIPTS_DIR_ID.fetch_add(1, Ordering::Relaxed)
+    )));
+    if !movie.root_scripts.is_empty() {
+        std::fs::create_dir_all(&root_scripts_dir.0)?;
+        command.arg("-cp").arg(&root_scripts_dir.0);
+        for (script_index, script) in movie.root_scripts.iter().enumerate() {
+            let file_name = format!("{}.as", root_script_class_name(script_index));
+            std::fs::write(
+                root_scripts_dir.0.join(&file_name),
+                root_script_class(script_index, script),
+            )?;
+            command.arg(file_name);
+            at_least_one_file = true;
+        }
+    }
+
     if at_least_one_file {
-        let output = command.output().map_err(|err| match err.kind() {
+        let output = command.output();
+        drop(root_scripts_dir);
+        let output = output.map_err(|err| match err.kind() {
             std::io::ErrorKind::NotFound => {
                 "Could not find mtasc executable. There is supposed to be a 'dependencies' directory in the same directory as this program with the mtasc executable.".into()
             }
@@ -163,10 +202,72 @@ pub(super) fn compile_as2(
             }
         }
 
+        // run the root scripts after the classes are registered and the symbols are placed
+        let mut root_script_action_datas = vec![];
+        for script_index in 0..movie.root_scripts.len() {
+            root_script_action_datas
+                .push(root_script_action_data(script_index, swf.header.version())?);
+        }
+        for action_data in &root_script_action_datas {
+            // -1 because of ShowFrame
+            swf.tags
+                .insert(swf.tags.len() - 1, Tag::DoAction(action_data));
+        }
+
         // write the new version
         let file = std::fs::File::create(swf_path)?;
         let writer = std::io::BufWriter::new(file);
         swf::write_swf(&swf.header.swf_header(), &swf.tags, writer)?;
     }
-    Ok(())
+    Ok(at_least_one_file)
+}
+
+fn root_script_class_name(script_index: usize) -> String {
+    format!("FlitsRootScript{}", script_index)
+}
+
+fn root_script_class(script_index: usize, script: &FrameScript) -> String {
+    // the code is on its own lines, so mtasc errors only have a line offset
+    format!(
+        "// generated from the root script '{}'\nclass {} {{\nstatic function run(root:MovieClip):Void {{\n{}\n}}\n}}\n",
+        script.name,
+        root_script_class_name(script_index),
+        script.code
+    )
+}
+
+// calls FlitsRootScriptN.run(_root)
+fn root_script_action_data(
+    script_index: usize,
+    swf_version: u8,
+) -> Result<Vec<u8>, FlitsCoreError> {
+    let class_name = root_script_class_name(script_index);
+    let mut action_data: Vec<u8> = vec![];
+    let mut action_writer = swf::avm1::write::Writer::new(&mut action_data, swf_version);
+    action_writer.write_action(&Action::ConstantPool(ConstantPool {
+        strings: vec![
+            SwfStr::from_utf8_str("_root"),
+            SwfStr::from_utf8_str(&class_name),
+            SwfStr::from_utf8_str("run"),
+        ],
+    }))?;
+    action_writer.write_action(&Action::Push(Push {
+        values: vec![swf::avm1::types::Value::ConstantPool(0)],
+    }))?;
+    action_writer.write_action(&Action::GetVariable)?;
+    action_writer.write_action(&Action::Push(Push {
+        values: vec![
+            swf::avm1::types::Value::Int(1),
+            swf::avm1::types::Value::ConstantPool(1),
+        ],
+    }))?;
+    action_writer.write_action(&Action::GetVariable)?;
+    action_writer.write_action(&Action::Push(Push {
+        values: vec![swf::avm1::types::Value::ConstantPool(2)],
+    }))?;
+    action_writer.write_action(&Action::CallMethod)?;
+    action_writer.write_action(&Action::Pop)?;
+    // Flash player crashes without this end action, see: https://github.com/ruffle-rs/ruffle/issues/18560
+    action_writer.write_action(&Action::End)?;
+    Ok(action_data)
 }
@@ -1,42 +1,74 @@
 use std::{io::Read, path::PathBuf};
 
-use swf::{AudioCompression, ExportedAsset, Sound, SoundFormat, Tag};
+use swf::{
+    AudioCompression, CharacterId, ExportedAsset, Sound, SoundEvent, SoundFormat, SoundInfo,
+    StartSound, Tag,
+};
+
+use crate::{FlitsCoreError, FlitsSound, SoundAction, SoundActionType, SymbolIndex};
 
 use super::{Arenas, SwfBuilder};
 
-pub(super) fn build_audio<'a>(
+pub(super) fn build_sound<'a>(
+    symbol_index: SymbolIndex,
+    sound: &FlitsSound,
     swf_builder: &mut SwfBuilder<'a>,
     arenas: &'a Arenas,
     directory: PathBuf,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let asset_dir = directory.join("assets");
-    let fs_assets = std::fs::read_dir(asset_dir)?;
-    for fs_asset in fs_assets {
-        let file = fs_asset?;
-        let file_name = file
-            .file_name()
-            .into_string()
-            .map_err(|original_os_string| {
-                format!("Non utf-8 filename: '{:?}'", original_os_string)
-            })?;
-        if file_name.ends_with(".mp3") {
-            build_mp3(swf_builder, arenas, file, file_name.clone())
-                .map_err(|err| format!("Error decoding '{}': {}", file_name, err))?;
-        } else if file_name.ends_with(".wav") {
-            build_wav(swf_builder, arenas, file, file_name.clone())
-                .map_err(|err| format!("Error decoding '{}': {}", file_name, err))?;
-        }
+) -> Result<(), FlitsCoreError> {
+    let path = directory.join("assets").join(&sound.path);
+    let character_id = if sound.path.ends_with(".mp3") {
+        build_mp3(swf_builder, arenas, path, sound.name.clone())
+    } else if sound.path.ends_with(".wav") {
+        build_wav(swf_builder, arenas, path, sound.name.clone())
+    } else {
+        Err("Only mp3 and wav files are supported".into())
     }
+    .map_err(|err| format!("Error decoding '{}': {}", sound.path, err))?;
+    swf_builder
+        .state
+        .symbol_index_to_character_id
+        .insert(symbol_index, character_id);
     Ok(())
 }
 
+pub(super) fn get_sound_action_tags<'a>(
+    sound_actions: &[SoundAction],
+    swf_builder: &SwfBuilder<'a>,
+) -> Vec<Tag<'a>> {
+    sound_actions
+        .iter()
+        // sounds that aren't exported can't be started
+        .filter_map(|sound_action| {
+            let character_id = swf_builder
+                .state
+                .symbol_index_to_character_id
+                .get(&sound_action.sound_index)?;
+            Some(Tag::StartSound(StartSound {
+                id: *character_id,
+                sound_info: Box::new(SoundInfo {
+                    event: match sound_action.action {
+                        SoundActionType::Start => SoundEvent::Event,
+                        SoundActionType::StartIfNotPlaying => SoundEvent::Start,
+                        SoundActionType::Stop => SoundEvent::Stop,
+                    },
+                    in_sample: None,
+                    out_sample: None,
+                    num_loops: 1,
+                    envelope: None,
+                }),
+            }))
+        })
+        .collect()
+}
+
 fn build_wav<'a>(
     swf_builder: &mut SwfBuilder<'a>,
     arenas: &'a Arenas,
-    file: std::fs::DirEntry,
-    file_name: String,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let reader = hound::WavReader::open(file.path())?;
+    path: PathBuf,
+    name: String,
+) -> Result<CharacterId, FlitsCoreError> {
+    let reader = hound::WavReader::open(path)?;
     let duration = reader.duration();
     let spec = reader.spec();
 
@@ -72,7 +104,7 @@ fn build_wav<'a>(
     let mut data: Vec<u8> = vec![];
     // use the underlying reader because we just want the data instead of decoding it ourselves
     reader.into_inner().read_to_end(&mut data)?;
-    let character_id = swf_builder.next_character_id();
+    let character_id = swf_builder.next_character_id()?;
     swf_builder.tags.push(Tag::DefineSound(Box::new(Sound {
         id: character_id,
         format: SoundFormat {
@@ -86,18 +118,18 @@ fn build_wav<'a>(
     })));
     swf_builder.tags.push(Tag::ExportAssets(vec![ExportedAsset {
         id: character_id,
-        name: arenas.alloc_swf_string(file_name),
+        name: arenas.alloc_swf_string(name),
     }]));
-    Ok(())
+    Ok(character_id)
 }
 
 fn build_mp3<'a>(
     swf_builder: &mut SwfBuilder<'a>,
     arenas: &'a Arenas,
-    file: std::fs::DirEntry,
-    file_name: String,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let data: Vec<u8> = std::fs::read(file.path())?;
+    path: PathBuf,
+    name: String,
+) -> Result<CharacterId, FlitsCoreError> {
+    let data: Vec<u8> = std::fs::read(path)?;
     // TODO: swfmill adds padding to the data, but it seems to work in Flash player without that padding?
     // see: https://github.com/djcsdy/swfmill/blob/master/src/swft/swft_import_mp3.cpp#L213
     let (header, samples) = puremp3::read_mp3(data.as_slice())?;
@@ -127,7 +159,7 @@ fn build_mp3<'a>(
     // TODO: this decodes the whole mp3 just to get the sample count
     // this is inefficient, it should just read the frame data
     let duration = samples.count();
-    let character_id = swf_builder.next_character_id();
+    let character_id = swf_builder.next_character_id()?;
     swf_builder.tags.push(Tag::DefineSound(Box::new(Sound {
         id: character_id,
         format: SoundFormat {
@@ -142,7 +174,7 @@ fn build_mp3<'a>(
     })));
     swf_builder.tags.push(Tag::ExportAssets(vec![ExportedAsset {
         id: character_id,
-        name: arenas.alloc_swf_string(file_name),
+        name: arenas.alloc_swf_string(name),
     }]));
-    Ok(())
+    Ok(character_id)
 }
@@ -1,92 +1,95 @@
-use std::{io::Write, path::PathBuf};
+use std::{io::Write, path::Path};
 
 use image::{EncodableLayout, ImageReader};
 use swf::{
     avm1::types::{Action, Push},
-    BitmapFormat, DefineBitsLossless, ExportedAsset, FillStyle, Fixed16, Matrix, PlaceObject,
-    PlaceObjectAction, Point, PointDelta, Rectangle, Shape, ShapeFlag, ShapeRecord, ShapeStyles,
-    Sprite, StyleChangeData, Tag, Twips,
+    BitmapFormat, CharacterId, DefineBitsLossless, ExportedAsset, FillStyle, Fixed16, Matrix,
+    PlaceObject, PlaceObjectAction, Point, PointDelta, Rectangle, Shape, ShapeFlag, ShapeRecord,
+    ShapeStyles, Sprite, StyleChangeData, Tag, Twips,
 };
 
-use crate::core::{Bitmap, SymbolIndex, SWF_VERSION};
+use crate::core::{Bitmap, BitmapProperties, FlitsCoreError, SymbolIndex, SWF_VERSION};
 
 use super::{Arenas, SwfBuilder};
 
-pub(super) fn build_bitmap<'a>(
-    symbol_index: SymbolIndex,
-    bitmap: &Bitmap,
-    swf_builder: &mut SwfBuilder<'a>,
-    arenas: &'a Arenas,
-    directory: PathBuf,
-) -> Result<(), Box<dyn std::error::Error>> {
-    // TODO: the images are probably already loaded when exporting a movie you are editing, maybe reuse that?
-    let img = ImageReader::open(
-        directory
-            .join("assets")
-            .join(bitmap.properties.path.clone()),
-    )
-    .map_err(|err| match err.kind() {
-        std::io::ErrorKind::NotFound => {
-            format!("File not found: '{}'", bitmap.properties.path.clone())
+// the zlib compressed pixels of every frame of a bitmap
+// compressing doesn't need the swf builder, so bitmaps can be compressed in parallel before building the tags
+pub(super) struct CompressedBitmap {
+    frame_width: u32,
+    frame_height: u32,
+    frames: Vec<Vec<u8>>,
+}
+
+// FlitsCoreError can't be sent between threads because the swf errors can't,
+// so compressing has its own error that is converted afterwards
+pub(super) enum CompressBitmapError {
+    Decode {
+        path: String,
+        source: image::ImageError,
+    },
+    Io(std::io::Error),
+    Other(String),
+}
+impl From<std::io::Error> for CompressBitmapError {
+    fn from(error: std::io::Error) -> Self {
+        CompressBitmapError::Io(error)
+    }
+}
+impl From<CompressBitmapError> for FlitsCoreError {
+    fn from(error: CompressBitmapError) -> Self {
+        match error {
+            CompressBitmapError::Decode { path, source } => {
+                FlitsCoreError::ImageDecodeError { path, source }
+            }
+            CompressBitmapError::Io(error) => FlitsCoreError::IoError(error),
+            CompressBitmapError::Other(message) => FlitsCoreError::Other(message),
         }
-        _ => format!(
-            "Unable to open file: '{}' Reason: {}",
-            bitmap.properties.path.clone(),
-            err
-        ),
-    })?
-    .decode()
-    .map_err(|err| {
-        format!(
-            "Error decoding '{}': {}",
-            bitmap.properties.path.clone(),
-            err
-        )
-    })?;
+    }
+}
+
+pub(super) fn compress_bitmap(
+    properties: &BitmapProperties,
+    directory: &Path,
+) -> Result<CompressedBitmap, CompressBitmapError> {
+    // TODO: the images are probably already loaded when exporting a movie you are editing, maybe reuse that?
+    let img = ImageReader::open(directory.join("assets").join(properties.path.clone()))
+        .map_err(|err| {
+            CompressBitmapError::Other(match err.kind() {
+                std::io::ErrorKind::NotFound => {
+                    format!("File not found: '{}'", properties.path.clone())
+                }
+                _ => format!(
+                    "Unable to open file: '{}' Reason: {}",
+                    properties.path.clone(),
+                    err
+                ),
+            })
+        })?
+        .decode()
+        .map_err(|err| CompressBitmapError::Decode {
+            path: properties.path.clone(),
+            source: err,
+        })?;
 
-    let frame_count = match &bitmap.properties.animation {
+    let frame_count = match &properties.animation {
         None => 1,
         Some(animation) => animation.frame_count,
     };
-    let frames_per_animation_frame = match &bitmap.properties.animation {
-        None => 1,
-        Some(animation) => animation.frame_delay + 1,
-    };
     let image_width = img.width();
     let image_height = img.height();
     if frame_count > image_width {
-        return Err(format!(
+        return Err(CompressBitmapError::Other(format!(
             "Animation has more frames than width, causing frames to be less than 1 pixel: '{}'",
-            bitmap.properties.path.clone()
-        )
-        .into());
+            properties.path.clone()
+        )));
     }
     let frame_width = image_width / frame_count;
     let frame_height = image_height;
     let rgba8 = img.into_rgba8();
     let image_data = &mut rgba8.as_bytes().to_owned();
-    // convert to argb
-    for i in 0..image_width {
-        for j in 0..image_height {
-            let index: usize = ((i + j * image_width) * 4) as usize;
-            let r = image_data[index];
-            let g = image_data[index + 1];
-            let b = image_data[index + 2];
-            let a = image_data[index + 3];
-
-            // Flash player expects premultiplied alpha
-            // see: https://open-flash.github.io/mirrors/swf-spec-19.pdf
-            // Chapter 8 -> DefineBitsLossless2 -> ALPHACOLORMAPDATA
-            // quote: "The RGB data must already be multiplied bythe alpha channel value."
-            // (original includes typo)
-            let a_float = a as f32 / 255.0;
-            image_data[index] = a;
-            image_data[index + 1] = (r as f32 * a_float) as u8;
-            image_data[index + 2] = (g as f32 * a_float) as u8;
-            image_data[index + 3] = (b as f32 * a_float) as u8;
-        }
-    }
+    premultiply_alpha(image_data);
 
+    let mut frames = Vec::with_capacity(frame_count as usize);
     for frame_nr in 0..frame_count {
         let compressed_image_data_buffer = Vec::new();
         let mut encoder = flate2::write::ZlibEncoder::new(
@@ -125,9 +128,35 @@ pub(super) fn build_bitmap<'a>(
             let mut zeros = vec![0; 1024 - compressed_image_data.len()];
             compressed_image_data.append(&mut zeros);
         }
+        frames.push(compressed_image_data);
+    }
+    Ok(CompressedBitmap {
+        frame_width,
+        frame_height,
+        frames,
+    })
+}
 
-        let bitmap_id = swf_builder.next_character_id();
-        let shape_id = swf_builder.next_character_id();
+pub(super) fn build_bitmap<'a>(
+    symbol_index: SymbolIndex,
+    bitmap: &Bitmap,
+    compressed_bitmap: CompressedBitmap,
+    swf_builder: &mut SwfBuilder<'a>,
+    arenas: &'a Arenas,
+) -> Result<(), FlitsCoreError> {
+    let CompressedBitmap {
+        frame_width,
+        frame_height,
+        frames,
+    } = compressed_bitmap;
+    let frame_count = frames.len() as u32;
+    let frames_per_animation_frame = match &bitmap.properties.animation {
+        None => 1,
+        Some(animation) => animation.frame_delay + 1,
+    };
+    for compressed_image_data in frames {
+        let bitmap_id = swf_builder.next_character_id()?;
+        let shape_id = swf_builder.next_character_id()?;
         if frame_count == 1 {
             swf_builder
                 .state
@@ -219,7 +248,7 @@ pub(super) fn build_bitmap<'a>(
     }
 
     if frame_count > 1 {
-        let movieclip_id = swf_builder.next_character_id();
+        let movieclip_id = swf_builder.next_character_id()?;
         swf_builder
             .state
             .symbol_index_to_character_id
@@ -312,3 +341,90 @@ pub(super) fn build_bitmap<'a>(
 
     Ok(())
 }
+
+// smoothing is part of the fill style of the shape, so placed symbols with smoothing need a copy of the shape
+// returns None for animated bitmaps, those don't support smoothing
+pub(super) fn smoothed_bitmap_character_id(
+    symbol_index: SymbolIndex,
+    swf_builder: &mut SwfBuilder,
+) -> Result<Option<CharacterId>, FlitsCoreError> {
+    if let Some(character_id) = swf_builder
+        .state
+        .symbol_index_to_smoothed_character_id
+        .get(&symbol_index)
+    {
+        return Ok(Some(*character_id));
+    }
+    let Some(tag_index) = swf_builder
+        .state
+        .symbol_index_to_tag_index
+        .get(&symbol_index)
+        .copied()
+    else {
+        return Ok(None);
+    };
+    // the shape comes right after the bitmap
+    let mut shape = match swf_builder.tags.get(tag_index + 1) {
+        Some(Tag::DefineShape(shape)) => shape.clone(),
+        _ => return Ok(None),
+    };
+    for fill_style in &mut shape.styles.fill_styles {
+        if let FillStyle::Bitmap { is_smoothed, .. } = fill_style {
+            *is_smoothed = true;
+        }
+    }
+    let character_id = swf_builder.next_character_id()?;
+    shape.id = character_id;
+    swf_builder
+        .state
+        .symbol_index_to_smoothed_character_id
+        .insert(symbol_index, character_id);
+    swf_builder.tags.push(Tag::DefineShape(shape));
+    Ok(Some(character_id))
+}
+
+// converts rgba to argb with premultiplied alpha, which is what DefineBitsLossless2 expects
+// see: https://open-flash.github.io/mirrors/swf-spec-19.pdf
+// Chapter 8 -> DefineBitsLossless2 -> ALPHACOLORMAPDATA
+// quote: "The RGB data must already be multiplied bythe alpha channel value."
+// (original includes typo)
+fn premultiply_alpha(data: &mut [u8]) {
+    for pixel in data.chunks_exact_mut(4) {
+        let [r, g, b, a] = [pixel[0], pixel[1], pixel[2], pixel[3]];
+        // rounded instead of truncated, otherwise colors get slightly darker
+        let multiply = |channel: u8| ((channel as u16 * a as u16 + 127) / 255) as u8;
+        pixel[0] = a;
+        pixel[1] = multiply(r);
+        pixel[2] = multiply(g);
+        pixel[3] = multiply(b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::premultiply_alpha;
+
+    #[test]
+    fn premultiplies_alpha_and_converts_to_argb() {
+        let mut data = vec![
+            // opaque
+            255, 128, 0, 255, //
+            // fully transparent
+            255, 255, 255, 0, //
+            // half transparent
+            255, 128, 10, 128, //
+            // almost transparent
+            200, 100, 50, 1,
+        ];
+        premultiply_alpha(&mut data);
+        assert_eq!(
+            data,
+            vec![
+                255, 255, 128, 0, //
+                0, 0, 0, 0, //
+                128, 128, 64, 5, //
+                1, 1, 0, 0,
+            ]
+        );
+    }
+}
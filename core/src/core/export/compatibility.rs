@@ -0,0 +1,105 @@
+use swf::{AudioCompression, Compression, FillStyle, Header, Tag};
+
+pub struct CompatibilityWarning {
+    pub feature: String,
+    pub min_version: u8,
+}
+impl std::fmt::Display for CompatibilityWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} requires swf version {} or higher",
+            self.feature, self.min_version
+        )
+    }
+}
+
+// looks at the tags that are actually exported, so it doesn't need to know about every editor feature
+pub(super) struct CompatibilityChecker {
+    swf_version: u8,
+    warnings: Vec<CompatibilityWarning>,
+}
+impl CompatibilityChecker {
+    pub fn new(swf_version: u8) -> Self {
+        CompatibilityChecker {
+            swf_version,
+            warnings: vec![],
+        }
+    }
+
+    pub fn require(&mut self, feature: &str, min_version: u8) {
+        if self.swf_version >= min_version {
+            return;
+        }
+        // only warn once per feature
+        if self
+            .warnings
+            .iter()
+            .any(|warning| warning.feature == feature)
+        {
+            return;
+        }
+        self.warnings.push(CompatibilityWarning {
+            feature: feature.into(),
+            min_version,
+        });
+    }
+
+    pub fn check_header(&mut self, header: &Header) {
+        match header.compression {
+            Compression::None => {}
+            Compression::Zlib => self.require("Zlib compression", 6),
+            Compression::Lzma => self.require("LZMA compression", 13),
+        }
+    }
+
+    pub fn check_tags(&mut self, tags: &[Tag]) {
+        for tag in tags {
+            match tag {
+                Tag::DefineBitsLossless(bitmap) if bitmap.version == 2 => {
+                    self.require("Bitmaps with transparency", 3)
+                }
+                Tag::DefineShape(shape) => {
+                    match shape.version {
+                        2 => self.require("DefineShape2", 2),
+                        3 => self.require("DefineShape3", 3),
+                        4 => self.require("DefineShape4", 8),
+                        _ => {}
+                    }
+                    for fill_style in &shape.styles.fill_styles {
+                        // bitmap fills were always smoothed before version 8
+                        if let FillStyle::Bitmap {
+                            is_smoothed: false, ..
+                        } = fill_style
+                        {
+                            self.require("Bitmaps without smoothing", 8);
+                        }
+                    }
+                }
+                Tag::DefineSprite(_) => self.require("Movieclips", 3),
+                Tag::PlaceObject(place_object) => match place_object.version {
+                    2 => self.require("PlaceObject2", 3),
+                    3 => self.require("PlaceObject3 (blend modes and filters)", 8),
+                    _ => {}
+                },
+                Tag::DefineEditText(_) => self.require("Text fields", 4),
+                Tag::DefineFont2(font) => match font.version {
+                    3 => self.require("DefineFont3", 8),
+                    _ => self.require("DefineFont2", 3),
+                },
+                Tag::DefineSound(sound) => {
+                    if matches!(sound.format.compression, AudioCompression::Mp3) {
+                        self.require("MP3 sounds", 4);
+                    }
+                }
+                Tag::ExportAssets(_) => self.require("Exported symbols", 5),
+                Tag::DoAction(_) => self.require("Actions", 3),
+                _ => {}
+            }
+        }
+    }
+
+    pub fn into_warnings(self) -> Vec<CompatibilityWarning> {
+        self.warnings
+    }
+}
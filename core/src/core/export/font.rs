@@ -3,32 +3,40 @@ use std::path::PathBuf;
 use font_to_swf::{FontAllocator, FontSwfBuilder};
 
 use super::{Arenas, SwfBuilder};
-use crate::{FlitsFont, SymbolIndex};
+use crate::{FlitsCoreError, FlitsFont, SymbolIndex};
 
 // adapted from: https://github.com/djcsdy/swfmill/blob/53d769029adc9d817972e1ccd648b7b335bf78b7/src/swft/swft_import_ttf.cpp#L289
 pub(super) fn build_font<'a>(
     symbol_index: SymbolIndex,
     font: &FlitsFont,
+    characters: String,
     swf_builder: &mut SwfBuilder<'a>,
     arenas: &'a Arenas,
     directory: PathBuf,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let character_id = swf_builder.next_character_id();
+) -> Result<(), FlitsCoreError> {
+    let character_id = swf_builder.next_character_id()?;
     swf_builder
         .state
         .symbol_index_to_character_id
         .insert(symbol_index, character_id);
-    font_to_swf::font_to_swf(
+    let font_name = font_to_swf::font_to_swf(
         // i want the name of the file, not the font inside
         // this only gets used for ExportAssets, the name of the font is set to the font family
         // even when referencing it in AS you still need the family name, not the name in ExportAssets
         font.path.clone(),
         directory.join("assets").join(font.path.clone()),
-        font.characters.characters(),
+        characters,
         character_id,
         swf_builder,
         arenas,
-    )?;
+    )
+    .map_err(|err| {
+        FlitsCoreError::FontError(format!("Error converting '{}': {}", font.path, err))
+    })?;
+    swf_builder
+        .state
+        .symbol_index_to_font_name
+        .insert(symbol_index, font_name);
     // TODO: check for 2 fonts with the same family and bold+italic?
 
     Ok(())
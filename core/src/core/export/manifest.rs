@@ -0,0 +1,110 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+use swf::CharacterId;
+
+use crate::{FlitsCoreError, Movie, MovieProperties, SymbolIndex};
+
+// written next to the swf, so tools can know what's in it without parsing it
+#[derive(Serialize)]
+pub struct ExportManifest {
+    // seconds since the unix epoch
+    pub exported_at: u64,
+    pub swf_size: u64,
+    // the number of symbols of each type, including symbols that weren't exported
+    pub symbol_counts: BTreeMap<String, usize>,
+    // only the symbols that were exported, sorted by symbol index
+    pub characters: Vec<ManifestCharacter>,
+    pub properties: MovieProperties,
+}
+
+// a list instead of a map because symbol names don't have to be unique
+#[derive(Serialize)]
+pub struct ManifestCharacter {
+    pub name: String,
+    pub symbol_index: SymbolIndex,
+    pub character_id: CharacterId,
+}
+
+impl Movie {
+    pub fn export_manifest(
+        &self,
+        symbol_index_to_character_id: &HashMap<SymbolIndex, CharacterId>,
+        swf_size: u64,
+    ) -> ExportManifest {
+        let mut symbol_counts = BTreeMap::new();
+        for symbol in &self.symbols {
+            *symbol_counts
+                .entry(symbol.type_name().to_string())
+                .or_insert(0) += 1;
+        }
+        let mut characters: Vec<ManifestCharacter> = symbol_index_to_character_id
+            .iter()
+            .map(|(symbol_index, character_id)| ManifestCharacter {
+                name: self.symbols[*symbol_index].name(),
+                symbol_index: *symbol_index,
+                character_id: *character_id,
+            })
+            .collect();
+        characters.sort_by_key(|character| character.symbol_index);
+        ExportManifest {
+            exported_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0),
+            swf_size,
+            symbol_counts,
+            characters,
+            properties: self.properties.clone(),
+        }
+    }
+}
+
+// output.swf gets output.swf.json
+pub(super) fn write_manifest(
+    manifest: &ExportManifest,
+    swf_path: &Path,
+) -> Result<(), FlitsCoreError> {
+    let mut manifest_path = swf_path.as_os_str().to_owned();
+    manifest_path.push(".json");
+    let file = std::fs::File::create(manifest_path)?;
+    serde_json::to_writer_pretty(std::io::BufWriter::new(file), manifest)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MovieClip, Symbol};
+
+    use super::*;
+
+    #[test]
+    fn symbols_with_the_same_name_are_all_listed() {
+        let mut movie = Movie::default();
+        movie
+            .symbols
+            .push(Symbol::MovieClip(MovieClip::new("clip")));
+        movie
+            .symbols
+            .push(Symbol::MovieClip(MovieClip::new("clip")));
+        let symbol_index_to_character_id = HashMap::from([(1, 2), (0, 1)]);
+
+        let manifest = movie.export_manifest(&symbol_index_to_character_id, 0);
+        let characters: Vec<_> = manifest
+            .characters
+            .iter()
+            .map(|character| {
+                (
+                    character.name.as_str(),
+                    character.symbol_index,
+                    character.character_id,
+                )
+            })
+            .collect();
+        assert_eq!(characters, vec![("clip", 0, 1), ("clip", 1, 2)]);
+    }
+}
@@ -1,15 +1,15 @@
 use swf::{ExportedAsset, Sprite, Tag};
 
-use crate::core::{MovieClip, SymbolIndex};
+use crate::core::{FlitsCoreError, MovieClip, SymbolIndex};
 
-use super::{get_placed_symbols_tags, Arenas, SwfBuilder};
+use super::{audio::get_sound_action_tags, get_placed_symbols_tags, Arenas, SwfBuilder};
 
 pub(super) fn build_movieclip_outer(
     symbol_index: SymbolIndex,
     _movieclip: &MovieClip,
     swf_builder: &mut SwfBuilder,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let character_id = swf_builder.next_character_id();
+) -> Result<(), FlitsCoreError> {
+    let character_id = swf_builder.next_character_id()?;
     swf_builder
         .state
         .symbol_index_to_character_id
@@ -31,8 +31,12 @@ pub(super) fn build_movieclip_inner<'a>(
     movieclip: &MovieClip,
     swf_builder: &mut SwfBuilder<'a>,
     arenas: &'a Arenas,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let inner_tags = get_placed_symbols_tags(&movieclip.place_symbols, swf_builder, arenas)?;
+) -> Result<(), FlitsCoreError> {
+    let mut inner_tags = get_placed_symbols_tags(&movieclip.place_symbols, swf_builder, arenas)?;
+    inner_tags.extend(get_sound_action_tags(
+        &movieclip.properties.sound_actions,
+        swf_builder,
+    ));
     let tag = &mut swf_builder.tags[swf_builder.state.symbol_index_to_tag_index[&symbol_index]];
     let Tag::DefineSprite(define_sprite_tag) = tag else {
         return Err(format!(
@@ -1,51 +1,66 @@
 use swf::{
     avm1::types::{Action, ConstantPool, DefineFunction2, FunctionFlags, If, Push, StoreRegister},
-    BlendMode, Button, ButtonAction, ButtonActionCondition, ButtonRecord, ButtonState, Color,
-    ColorTransform, FillStyle, Matrix, PlaceObject, PlaceObjectAction, Point, PointDelta,
-    Rectangle, RemoveObject, Shape, ShapeFlag, ShapeRecord, ShapeStyles, Sprite, StyleChangeData,
-    SwfStr, Tag, Twips,
+    BlendMode, Button, ButtonAction, ButtonActionCondition, ButtonRecord, ButtonState, CharacterId,
+    Color, ColorTransform, EditText, FillStyle, Font, FontFlag, Matrix, PlaceObject,
+    PlaceObjectAction, Point, PointDelta, Rectangle, RemoveObject, Shape, ShapeFlag, ShapeRecord,
+    ShapeStyles, Sprite, StyleChangeData, SwfStr, Tag, Twips,
 };
 
-use crate::core::{PreloaderType, SWF_VERSION};
+use std::collections::HashSet;
+
+use crate::core::{
+    FlitsCoreError, PreloaderStyle, PreloaderType, Symbol, SymbolIndex, SWF_VERSION,
+};
 
 use super::{Arenas, SwfBuilder};
 
+const LOADING_TEXT_SIZE: f64 = 12.0;
+const LOADING_TEXT_WIDTH: f64 = 100.0;
+const LOADING_TEXT_HEIGHT: f64 = 20.0;
+
 pub(super) fn build_preloader<'a>(
     preloader_type: PreloaderType,
+    style: &PreloaderStyle,
+    // the movieclip that is shown instead of the loading bar
+    custom_graphic_id: Option<CharacterId>,
     swf_builder: &mut SwfBuilder<'a>,
     arenas: &'a Arenas,
     stage_width: f64,
     stage_height: f64,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let background_id = swf_builder.next_character_id();
-    let loading_bar_background_id = swf_builder.next_character_id();
-    let loading_bar_foreground_id = swf_builder.next_character_id();
-    let loading_bar_clip_id = swf_builder.next_character_id();
+) -> Result<(), FlitsCoreError> {
+    let background_id = swf_builder.next_character_id()?;
+    let loading_bar_background_id = swf_builder.next_character_id()?;
+    let loading_bar_foreground_id = swf_builder.next_character_id()?;
+    let loading_bar_clip_id = swf_builder.next_character_id()?;
+    let loading_text_font_id = swf_builder.next_character_id()?;
+    let loading_text_id = swf_builder.next_character_id()?;
     let center_matrix = Matrix::translate(
         Twips::from_pixels(stage_width as f64 / 2.0),
         Twips::from_pixels(stage_height as f64 / 2.0),
     );
-    let loading_bar_width = stage_width * 0.85;
-    let loading_bar_height = 2.0;
+    let loading_bar_width = stage_width * style.bar_width_fraction as f64;
+    let loading_bar_height = style.bar_height as f64;
     let loading_bar_matrix = center_matrix
         * Matrix::translate(
             Twips::from_pixels(loading_bar_width / -2.0),
             Twips::from_pixels(loading_bar_height / -2.0),
         );
-    swf_builder.tags.extend(create_rectangle(
-        background_id,
-        stage_width,
-        stage_height,
-        Color::BLACK,
-        Matrix::IDENTITY,
-    ));
-    swf_builder.tags.extend(create_rectangle(
-        loading_bar_background_id,
-        loading_bar_width,
-        loading_bar_height,
-        Color::GRAY,
-        loading_bar_matrix,
-    ));
+    if custom_graphic_id.is_none() {
+        swf_builder.tags.extend(create_rectangle(
+            background_id,
+            stage_width,
+            stage_height,
+            Color::BLACK,
+            Matrix::IDENTITY,
+        ));
+        swf_builder.tags.extend(create_rectangle(
+            loading_bar_background_id,
+            loading_bar_width,
+            loading_bar_height,
+            style.background_color.clone().into(),
+            loading_bar_matrix,
+        ));
+    }
 
     let mut action_data: Vec<u8> = vec![];
     let mut action_writer = swf::avm1::write::Writer::new(&mut action_data, SWF_VERSION);
@@ -57,6 +72,9 @@ pub(super) fn build_preloader<'a>(
             SwfStr::from_utf8_str("getBytesLoaded"),
             SwfStr::from_utf8_str("getBytesTotal"),
             SwfStr::from_utf8_str("gotoAndStop"),
+            SwfStr::from_utf8_str("loading_text"),
+            SwfStr::from_utf8_str("text"),
+            SwfStr::from_utf8_str("%"),
         ],
     });
     action_writer.write_action(&action)?;
@@ -130,6 +148,34 @@ pub(super) fn build_preloader<'a>(
     on_enter_frame_action_writer.write_action(&action)?;
     let action = Action::SetMember;
     on_enter_frame_action_writer.write_action(&action)?;
+    // loading_text.text = int(percentage) + "%"
+    // this does nothing when there is no loading text, like with a custom graphic
+    let action = Action::Push(Push {
+        // loading_text
+        values: vec![swf::avm1::types::Value::ConstantPool(6)],
+    });
+    on_enter_frame_action_writer.write_action(&action)?;
+    let action = Action::GetVariable;
+    on_enter_frame_action_writer.write_action(&action)?;
+    let action = Action::Push(Push {
+        values: vec![
+            // text
+            swf::avm1::types::Value::ConstantPool(7),
+            swf::avm1::types::Value::Register(2),
+        ],
+    });
+    on_enter_frame_action_writer.write_action(&action)?;
+    let action = Action::ToInteger;
+    on_enter_frame_action_writer.write_action(&action)?;
+    let action = Action::Push(Push {
+        // %
+        values: vec![swf::avm1::types::Value::ConstantPool(8)],
+    });
+    on_enter_frame_action_writer.write_action(&action)?;
+    let action = Action::Add2;
+    on_enter_frame_action_writer.write_action(&action)?;
+    let action = Action::SetMember;
+    on_enter_frame_action_writer.write_action(&action)?;
     let action = Action::Push(Push {
         values: vec![
             swf::avm1::types::Value::Register(2),
@@ -175,24 +221,18 @@ pub(super) fn build_preloader<'a>(
     let action = Action::End;
     action_writer.write_action(&action)?;
 
-    swf_builder.tags.extend(vec![
-        define_rectangle(
-            loading_bar_foreground_id,
-            loading_bar_width,
-            loading_bar_height,
-            Color::WHITE,
-        ),
-        Tag::DefineSprite(Sprite {
-            id: loading_bar_clip_id,
-            num_frames: 1,
-            tags: vec![Tag::PlaceObject(Box::new(PlaceObject {
+    if let Some(custom_graphic_id) = custom_graphic_id {
+        // the same name as the loading bar, so the same script scales it
+        swf_builder
+            .tags
+            .push(Tag::PlaceObject(Box::new(PlaceObject {
                 version: 2,
-                action: PlaceObjectAction::Place(loading_bar_foreground_id),
-                depth: 1,
-                matrix: Some(Matrix::IDENTITY),
+                action: PlaceObjectAction::Place(custom_graphic_id),
+                depth: loading_bar_clip_id,
+                matrix: Some(center_matrix),
                 color_transform: None,
                 ratio: None,
-                name: None,
+                name: Some("loading_bar".into()),
                 clip_depth: None,
                 class_name: None,
                 filters: None,
@@ -203,27 +243,71 @@ pub(super) fn build_preloader<'a>(
                 is_bitmap_cached: None,
                 is_visible: Some(true),
                 amf_data: None,
-            }))],
-        }),
-        Tag::PlaceObject(Box::new(PlaceObject {
-            version: 2,
-            action: PlaceObjectAction::Place(loading_bar_clip_id),
-            depth: loading_bar_clip_id,
-            matrix: Some(loading_bar_matrix),
-            color_transform: None,
-            ratio: None,
-            name: Some("loading_bar".into()),
-            clip_depth: None,
-            class_name: None,
-            filters: None,
-            background_color: None,
-            blend_mode: None,
-            clip_actions: None,
-            has_image: true,
-            is_bitmap_cached: None,
-            is_visible: Some(true),
-            amf_data: None,
-        })),
+            })));
+    } else {
+        swf_builder.tags.extend(vec![
+            define_rectangle(
+                loading_bar_foreground_id,
+                loading_bar_width,
+                loading_bar_height,
+                style.bar_color.clone().into(),
+            ),
+            Tag::DefineSprite(Sprite {
+                id: loading_bar_clip_id,
+                num_frames: 1,
+                tags: vec![Tag::PlaceObject(Box::new(PlaceObject {
+                    version: 2,
+                    action: PlaceObjectAction::Place(loading_bar_foreground_id),
+                    depth: 1,
+                    matrix: Some(Matrix::IDENTITY),
+                    color_transform: None,
+                    ratio: None,
+                    name: None,
+                    clip_depth: None,
+                    class_name: None,
+                    filters: None,
+                    background_color: None,
+                    blend_mode: None,
+                    clip_actions: None,
+                    has_image: true,
+                    is_bitmap_cached: None,
+                    is_visible: Some(true),
+                    amf_data: None,
+                }))],
+            }),
+            Tag::PlaceObject(Box::new(PlaceObject {
+                version: 2,
+                action: PlaceObjectAction::Place(loading_bar_clip_id),
+                depth: loading_bar_clip_id,
+                matrix: Some(loading_bar_matrix),
+                color_transform: None,
+                ratio: None,
+                name: Some("loading_bar".into()),
+                clip_depth: None,
+                class_name: None,
+                filters: None,
+                background_color: None,
+                blend_mode: None,
+                clip_actions: None,
+                has_image: true,
+                is_bitmap_cached: None,
+                is_visible: Some(true),
+                amf_data: None,
+            })),
+        ]);
+        build_loading_text(
+            loading_text_font_id,
+            loading_text_id,
+            style,
+            swf_builder,
+            center_matrix
+                * Matrix::translate(
+                    Twips::from_pixels(LOADING_TEXT_WIDTH / -2.0),
+                    Twips::from_pixels(loading_bar_height / -2.0 - LOADING_TEXT_HEIGHT),
+                ),
+        );
+    }
+    swf_builder.tags.extend(vec![
         Tag::DoAction(arenas.data.alloc(action_data)),
         Tag::ShowFrame,
     ]);
@@ -251,9 +335,9 @@ pub(super) fn build_preloader<'a>(
         let action = Action::End;
         play_button_action_writer.write_action(&action)?;
 
-        let play_button_shape_id = swf_builder.next_character_id();
-        let play_button_shape_over_id = swf_builder.next_character_id();
-        let play_button_id = swf_builder.next_character_id();
+        let play_button_shape_id = swf_builder.next_character_id()?;
+        let play_button_shape_over_id = swf_builder.next_character_id()?;
+        let play_button_id = swf_builder.next_character_id()?;
         swf_builder.tags.extend(vec![
             define_play_button_shape(play_button_shape_id, 32.0, 32.0, Color::WHITE),
             define_play_button_shape(play_button_shape_over_id, 32.0, 32.0, Color::GRAY),
@@ -310,20 +394,31 @@ pub(super) fn build_preloader<'a>(
             Tag::ShowFrame,
         ]);
     }
-    swf_builder.tags.extend(vec![
-        Tag::RemoveObject(RemoveObject {
-            depth: background_id,
-            character_id: Some(background_id),
-        }),
-        Tag::RemoveObject(RemoveObject {
-            depth: loading_bar_background_id,
-            character_id: Some(loading_bar_background_id),
-        }),
-        Tag::RemoveObject(RemoveObject {
+    if let Some(custom_graphic_id) = custom_graphic_id {
+        swf_builder.tags.push(Tag::RemoveObject(RemoveObject {
             depth: loading_bar_clip_id,
-            character_id: Some(loading_bar_clip_id),
-        }),
-    ]);
+            character_id: Some(custom_graphic_id),
+        }));
+    } else {
+        swf_builder.tags.extend(vec![
+            Tag::RemoveObject(RemoveObject {
+                depth: background_id,
+                character_id: Some(background_id),
+            }),
+            Tag::RemoveObject(RemoveObject {
+                depth: loading_bar_background_id,
+                character_id: Some(loading_bar_background_id),
+            }),
+            Tag::RemoveObject(RemoveObject {
+                depth: loading_bar_clip_id,
+                character_id: Some(loading_bar_clip_id),
+            }),
+            Tag::RemoveObject(RemoveObject {
+                depth: loading_text_id,
+                character_id: Some(loading_text_id),
+            }),
+        ]);
+    }
     if let PreloaderType::WithPlayButton = preloader_type {
         // remove play button
         swf_builder.tags.push(Tag::RemoveObject(RemoveObject {
@@ -334,6 +429,103 @@ pub(super) fn build_preloader<'a>(
     Ok(())
 }
 
+// the symbol and all the symbols it uses
+pub(super) fn symbol_dependencies(
+    symbols: &[Symbol],
+    symbol_index: SymbolIndex,
+) -> HashSet<SymbolIndex> {
+    let mut dependencies = HashSet::new();
+    let mut to_visit = vec![symbol_index];
+    while let Some(symbol_index) = to_visit.pop() {
+        if !dependencies.insert(symbol_index) {
+            continue;
+        }
+        match &symbols[symbol_index] {
+            Symbol::MovieClip(movieclip) => {
+                for place_symbol in &movieclip.place_symbols {
+                    to_visit.push(place_symbol.symbol_index);
+                    if let Some(text) = &place_symbol.text {
+                        to_visit.extend(text.spans.iter().map(|span| span.font_symbol_index));
+                    }
+                }
+                to_visit.extend(
+                    movieclip
+                        .properties
+                        .sound_actions
+                        .iter()
+                        .map(|sound_action| sound_action.sound_index),
+                );
+            }
+            Symbol::StaticText(static_text) => to_visit.extend(static_text.font_index),
+            Symbol::Bitmap(_) | Symbol::Font(_) | Symbol::Sound(_) => {}
+        }
+    }
+    dependencies
+}
+
+// the percentage is shown above the loading bar
+fn build_loading_text(
+    font_id: CharacterId,
+    edit_text_id: CharacterId,
+    style: &PreloaderStyle,
+    swf_builder: &mut SwfBuilder,
+    matrix: Matrix,
+) {
+    // the fonts of the movie are defined after the preloader frame,
+    // so the text uses a device font to not have to wait for them to load
+    swf_builder.tags.push(Tag::DefineFont2(Box::new(Font {
+        version: 2,
+        id: font_id,
+        name: SwfStr::from_utf8_str("_sans"),
+        language: swf::Language::Unknown,
+        layout: None,
+        glyphs: vec![],
+        flags: FontFlag::empty(),
+    })));
+    let edit_text = EditText::new()
+        .with_id(edit_text_id)
+        .with_font_id(font_id, Twips::from_pixels(LOADING_TEXT_SIZE))
+        .with_bounds(Rectangle {
+            x_min: Twips::from_pixels(0.0),
+            x_max: Twips::from_pixels(LOADING_TEXT_WIDTH),
+            y_min: Twips::from_pixels(0.0),
+            y_max: Twips::from_pixels(LOADING_TEXT_HEIGHT),
+        })
+        .with_color(Some(style.bar_color.clone().into()))
+        .with_layout(Some(swf::TextLayout {
+            align: swf::TextAlign::Center,
+            left_margin: Twips::ZERO,
+            right_margin: Twips::ZERO,
+            indent: Twips::ZERO,
+            leading: Twips::ZERO,
+        }))
+        .with_initial_text(Some(SwfStr::from_utf8_str("0%")))
+        .with_is_read_only(true)
+        .with_is_selectable(false);
+    swf_builder.tags.extend(vec![
+        Tag::DefineEditText(Box::new(edit_text)),
+        Tag::PlaceObject(Box::new(PlaceObject {
+            version: 2,
+            action: PlaceObjectAction::Place(edit_text_id),
+            depth: edit_text_id,
+            matrix: Some(matrix),
+            color_transform: None,
+            ratio: None,
+            name: Some("loading_text".into()),
+            clip_depth: None,
+            class_name: None,
+            filters: None,
+            background_color: None,
+            blend_mode: None,
+            clip_actions: None,
+            has_image: true,
+            is_bitmap_cached: None,
+            is_visible: Some(true),
+            amf_data: None,
+        })),
+    ]);
+}
+
 fn create_rectangle<'a>(
     shape_id: u16,
     width: f64,
@@ -0,0 +1,104 @@
+use swf::{GlyphEntry, Matrix, Rectangle, Tag, Text, TextRecord, Twips};
+
+use crate::core::{FlitsCoreError, StaticTextProperties, SymbolIndex};
+
+use super::SwfBuilder;
+
+// the glyphs and layout of exported fonts are scaled to this em square
+const FONT_EM_SQUARE: f64 = 1024.0;
+
+pub(super) fn build_static_text(
+    symbol_index: SymbolIndex,
+    static_text: &StaticTextProperties,
+    swf_builder: &mut SwfBuilder,
+) -> Result<(), FlitsCoreError> {
+    let font_index = static_text
+        .font_index
+        .ok_or_else(|| format!("Static text '{}' doesn't have a font", static_text.name))?;
+    let font_character_id = *swf_builder
+        .state
+        .symbol_index_to_character_id
+        .get(&font_index)
+        .ok_or_else(|| {
+            format!(
+                "The font of static text '{}' is not exported",
+                static_text.name
+            )
+        })?;
+    let font = swf_builder
+        .tags
+        .iter()
+        .find_map(|tag| match tag {
+            Tag::DefineFont2(font) if font.id == font_character_id => Some(font),
+            _ => None,
+        })
+        .ok_or("Font of static text not found")?;
+
+    let scale = static_text.size / FONT_EM_SQUARE;
+    let (ascent, descent, leading) = match &font.layout {
+        Some(layout) => (
+            layout.ascent as f64 * scale,
+            layout.descent as f64 * scale,
+            layout.leading as f64 * scale,
+        ),
+        None => (static_text.size, 0.0, 0.0),
+    };
+    let line_height = ascent + descent + leading;
+
+    // static text doesn't wrap, so every line gets its own record
+    let mut records = vec![];
+    let mut width: f64 = 0.0;
+    for (line_index, line) in static_text.text.lines().enumerate() {
+        let mut glyphs = vec![];
+        let mut line_width = 0.0;
+        for character in line.chars() {
+            let glyph_index = font
+                .glyphs
+                .iter()
+                .position(|glyph| glyph.code == character as u16)
+                .ok_or_else(|| {
+                    format!(
+                        "The font of static text '{}' doesn't have the character '{}'",
+                        static_text.name, character
+                    )
+                })?;
+            let advance = font.glyphs[glyph_index].advance as f64 * scale;
+            glyphs.push(GlyphEntry {
+                index: glyph_index as u32,
+                advance: Twips::from_pixels(advance).get(),
+            });
+            line_width += advance;
+        }
+        width = width.max(line_width);
+        records.push(TextRecord {
+            // the font, color and height carry over to the next records
+            font_id: (line_index == 0).then_some(font_character_id),
+            color: (line_index == 0).then(|| static_text.color.clone().into()),
+            x_offset: Some(Twips::from_pixels(static_text.x)),
+            y_offset: Some(Twips::from_pixels(
+                static_text.y + ascent + line_index as f64 * line_height,
+            )),
+            height: (line_index == 0).then_some(Twips::from_pixels(static_text.size)),
+            glyphs,
+        });
+    }
+    let line_count = records.len().max(1) as f64;
+
+    let character_id = swf_builder.next_character_id()?;
+    swf_builder
+        .state
+        .symbol_index_to_character_id
+        .insert(symbol_index, character_id);
+    swf_builder.tags.push(Tag::DefineText(Box::new(Text {
+        id: character_id,
+        bounds: Rectangle {
+            x_min: Twips::from_pixels(static_text.x),
+            x_max: Twips::from_pixels(static_text.x + width),
+            y_min: Twips::from_pixels(static_text.y),
+            y_max: Twips::from_pixels(static_text.y + line_height * line_count),
+        },
+        matrix: Matrix::IDENTITY,
+        records,
+    })));
+    Ok(())
+}
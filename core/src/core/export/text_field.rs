@@ -1,6 +1,6 @@
 use swf::{CharacterId, EditText, Rectangle, Tag, Twips};
 
-use crate::TextProperties;
+use crate::{FlitsCoreError, TextProperties, TextSpan};
 
 use super::{Arenas, SwfBuilder};
 
@@ -9,8 +9,21 @@ pub(super) fn build_text_field<'a>(
     text: &TextProperties,
     swf_builder: &mut SwfBuilder<'a>,
     arenas: &'a Arenas,
-) -> CharacterId {
-    let edit_text_id = swf_builder.next_character_id();
+) -> Result<CharacterId, FlitsCoreError> {
+    let edit_text_id = swf_builder.next_character_id()?;
+    let (initial_text, is_html) = if !text.spans.is_empty() {
+        (spans_to_html(&text.spans, swf_builder), true)
+    } else if text.underline {
+        // edit text tags don't have an underline flag, only html can do it
+        let html = if text.is_html {
+            text.text.clone()
+        } else {
+            escape_html(&text.text).replace('\n', "<br>")
+        };
+        (format!("<u>{}</u>", html), true)
+    } else {
+        (text.text.clone(), text.is_html)
+    };
     let edit_text = EditText::new()
         .with_id(edit_text_id)
         .with_font_id(font_character_id, Twips::from_pixels(text.size))
@@ -29,11 +42,11 @@ pub(super) fn build_text_field<'a>(
             leading: Twips::ZERO,
         }))
         // TODO: check if the font supports all the characters in the initial text?
-        .with_initial_text(Some(arenas.alloc_swf_string(text.text.clone())))
+        .with_initial_text(Some(arenas.alloc_swf_string(initial_text)))
         .with_is_read_only(!text.editable)
         .with_is_selectable(text.selectable)
         .with_is_password(text.is_password)
-        .with_is_html(text.is_html)
+        .with_is_html(is_html)
         .with_is_multiline(text.is_multiline)
         .with_is_word_wrap(text.word_wrap)
         .with_use_outlines(true); // enables embedded fonts
@@ -41,5 +54,36 @@ pub(super) fn build_text_field<'a>(
         .tags
         .push(Tag::DefineEditText(Box::new(edit_text)));
 
-    edit_text_id
+    Ok(edit_text_id)
+}
+
+fn spans_to_html(spans: &[TextSpan], swf_builder: &SwfBuilder) -> String {
+    let mut html = String::new();
+    for span in spans {
+        html.push_str("<font");
+        // without a face the font of the text field is used
+        if let Some(font_name) = swf_builder
+            .state
+            .symbol_index_to_font_name
+            .get(&span.font_symbol_index)
+        {
+            html.push_str(&format!(" face=\"{}\"", escape_html(font_name)));
+        }
+        let mut span_html = escape_html(&span.text).replace('\n', "<br>");
+        if span.underline {
+            span_html = format!("<u>{}</u>", span_html);
+        }
+        html.push_str(&format!(
+            " size=\"{}\" color=\"#{:02X}{:02X}{:02X}\">{}</font>",
+            span.size, span.color.r, span.color.g, span.color.b, span_html
+        ));
+    }
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
 }
@@ -1,33 +1,62 @@
 use std::{
     io::{BufRead, BufReader},
     path::PathBuf,
+    sync::Arc,
     thread::{self, JoinHandle},
 };
 
-use duct::cmd;
+use duct::{cmd, ReaderHandle};
+
+// a Ruffle process that is running a movie
+pub struct RunningMovie {
+    reader: Arc<ReaderHandle>,
+    join_handle: JoinHandle<()>,
+}
+impl RunningMovie {
+    // kills the Ruffle process, the end callback is still called
+    pub fn stop(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.reader.kill()?;
+        Ok(())
+    }
+    // waits until the Ruffle process has exited and all output has been handled
+    pub fn join(self) -> thread::Result<()> {
+        self.join_handle.join()
+    }
+}
 
 pub fn run_movie<T: Send + Clone + 'static>(
     swf_path: &PathBuf,
     output_arg: T,
     output_callback: fn(line: String, T) -> (),
-    end_callback: fn(T) -> (),
-) -> Result<JoinHandle<()>, Box<dyn std::error::Error>> {
+    end_callback: fn(exit_code: Option<i32>, T) -> (),
+) -> Result<RunningMovie, Box<dyn std::error::Error>> {
     // No need to add .exe on windows, Command does that automatically
     let ruffle_path = std::env::current_exe()?
         .parent()
         .ok_or("Editor executable is not in a directory")?
         .join("dependencies/ruffle");
-    let ruffle = cmd!(ruffle_path, swf_path);
+    // unchecked so a non-zero exit code is reported instead of being a read error
+    let ruffle = cmd!(ruffle_path, swf_path).unchecked();
 
-    let reader = BufReader::new(ruffle.stderr_to_stdout().reader()?);
+    let reader = Arc::new(ruffle.stderr_to_stdout().reader()?);
+    let thread_reader = reader.clone();
     let join_handle = thread::spawn(move || {
-        reader
+        BufReader::new(&*thread_reader)
             .lines()
-            .filter_map(|line| line.ok())
+            // reading fails after the process is killed, stop instead of retrying forever
+            .map_while(|line| line.ok())
             .for_each(|line| {
                 output_callback(line, output_arg.clone());
             });
-        end_callback(output_arg);
+        // the reader waits for the process when it reaches the end of the output
+        let exit_code = match thread_reader.try_wait() {
+            Ok(Some(output)) => output.status.code(),
+            _ => None,
+        };
+        end_callback(exit_code, output_arg);
     });
-    Ok(join_handle)
+    Ok(RunningMovie {
+        reader,
+        join_handle,
+    })
 }
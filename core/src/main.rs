@@ -10,14 +10,14 @@ fn main() {
     movie.export("example".into(), swf_path.into()).unwrap();
 
     println!("Running...");
-    let join_handle = flits_core::run::run_movie(
+    let running_movie = flits_core::run::run_movie(
         &swf_path.into(),
         (),
         |line, _| {
             println!("{}", line);
         },
-        |_| {},
+        |_, _| {},
     )
     .unwrap();
-    join_handle.join().unwrap();
+    running_movie.join().unwrap();
 }
@@ -0,0 +1,264 @@
+use std::path::PathBuf;
+
+use flits_core::{
+    Bitmap, BitmapProperties, EditorColor, ExportOptions, FlitsFont, FontCharacters, Movie,
+    MovieClip, PlaceSymbol, PreloaderType, StaticTextProperties, Symbol,
+};
+use swf::Tag;
+
+// a project directory in the temp directory, removed when the test is done
+struct TestProject {
+    directory: PathBuf,
+}
+impl TestProject {
+    fn new(name: &str) -> Self {
+        let directory =
+            std::env::temp_dir().join(format!("flits-export-test-{}-{}", std::process::id(), name));
+        std::fs::create_dir_all(directory.join("assets")).unwrap();
+        TestProject { directory }
+    }
+
+    // exports the movie and calls check with the parsed swf
+    fn export(&self, movie: &Movie, check: impl FnOnce(&swf::Swf)) {
+        let swf_path = self.directory.join("output.swf");
+        movie
+            .export(self.directory.clone(), swf_path.clone())
+            .unwrap();
+        let swf_file = std::fs::File::open(swf_path).unwrap();
+        let swf_buf = swf::decompress_swf(swf_file).unwrap();
+        let swf = swf::parse_swf(&swf_buf).unwrap();
+        check(&swf);
+    }
+}
+impl Drop for TestProject {
+    fn drop(&mut self) {
+        _ = std::fs::remove_dir_all(&self.directory);
+    }
+}
+
+fn count_tags(tags: &[Tag], matches: impl Fn(&Tag) -> bool) -> usize {
+    tags.iter().filter(|tag| matches(tag)).count()
+}
+
+#[test]
+fn exports_bitmap() {
+    let project = TestProject::new("bitmap");
+    image::RgbaImage::new(4, 4)
+        .save(project.directory.join("assets/square.png"))
+        .unwrap();
+    let mut movie = Movie::default();
+    movie.symbols.push(Symbol::Bitmap(Bitmap {
+        properties: BitmapProperties {
+            name: "square".into(),
+            path: "square.png".into(),
+            animation: None,
+            do_not_export: false,
+        },
+        cache: Default::default(),
+    }));
+    movie.root.push(PlaceSymbol::new(0));
+
+    project.export(&movie, |swf| {
+        assert_eq!(
+            count_tags(&swf.tags, |tag| matches!(tag, Tag::DefineBitsLossless(_))),
+            1
+        );
+        assert_eq!(
+            count_tags(&swf.tags, |tag| matches!(tag, Tag::PlaceObject(_))),
+            1
+        );
+    });
+}
+
+#[test]
+fn exports_movieclip() {
+    let project = TestProject::new("movieclip");
+    let mut movie = Movie::default();
+    movie
+        .symbols
+        .push(Symbol::MovieClip(MovieClip::new("clip")));
+    movie.root.push(PlaceSymbol::new(0));
+
+    project.export(&movie, |swf| {
+        assert_eq!(
+            count_tags(&swf.tags, |tag| matches!(tag, Tag::DefineSprite(_))),
+            1
+        );
+        assert_eq!(
+            count_tags(&swf.tags, |tag| matches!(tag, Tag::PlaceObject(_))),
+            1
+        );
+    });
+}
+
+#[test]
+fn exports_nested_movieclips() {
+    let project = TestProject::new("nested-movieclips");
+    let mut movie = Movie::default();
+    movie
+        .symbols
+        .push(Symbol::MovieClip(MovieClip::new("inner")));
+    movie.symbols.push(Symbol::MovieClip(MovieClip {
+        place_symbols: vec![PlaceSymbol::new(0)],
+        ..MovieClip::new("outer")
+    }));
+    movie.root.push(PlaceSymbol::new(1));
+
+    project.export(&movie, |swf| {
+        let sprites: Vec<&swf::Sprite> = swf
+            .tags
+            .iter()
+            .filter_map(|tag| match tag {
+                Tag::DefineSprite(sprite) => Some(sprite),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(sprites.len(), 2);
+        // the outer movieclip places the inner one
+        let inner_id = sprites[0].id;
+        assert!(sprites[1].tags.iter().any(|tag| match tag {
+            Tag::PlaceObject(place_object) => matches!(
+                place_object.action,
+                swf::PlaceObjectAction::Place(id) if id == inner_id
+            ),
+            _ => false,
+        }));
+    });
+}
+
+#[test]
+fn exports_preloader() {
+    let project = TestProject::new("preloader");
+    let mut movie = Movie::default();
+    movie.properties.preloader = PreloaderType::StartAfterLoading;
+
+    project.export(&movie, |swf| {
+        // the preloader frame comes before the frame with the movie
+        assert_eq!(swf.header.num_frames(), 2);
+        assert_eq!(
+            count_tags(&swf.tags, |tag| matches!(tag, Tag::ShowFrame)),
+            2
+        );
+        assert!(count_tags(&swf.tags, |tag| matches!(tag, Tag::DoAction(_))) > 0);
+    });
+}
+
+#[test]
+fn exports_custom_preloader_graphic_before_the_preloader_frame() {
+    let project = TestProject::new("preloader-graphic");
+    let mut movie = Movie::default();
+    movie
+        .symbols
+        .push(Symbol::MovieClip(MovieClip::new("game")));
+    movie
+        .symbols
+        .push(Symbol::MovieClip(MovieClip::new("loading")));
+    movie.root.push(PlaceSymbol::new(0));
+    movie.properties.preloader = PreloaderType::CustomGraphic { movieclip_index: 1 };
+
+    project.export(&movie, |swf| {
+        let preloader_frame_end = swf
+            .tags
+            .iter()
+            .position(|tag| matches!(tag, Tag::ShowFrame))
+            .unwrap();
+        let (preloader_frame, movie_frame) = swf.tags.split_at(preloader_frame_end);
+        // only the graphic is defined before the preloader frame, the rest comes after it
+        assert_eq!(
+            count_tags(preloader_frame, |tag| matches!(tag, Tag::DefineSprite(_))),
+            1
+        );
+        assert_eq!(
+            count_tags(movie_frame, |tag| matches!(tag, Tag::DefineSprite(_))),
+            1
+        );
+    });
+}
+
+#[test]
+fn exports_static_text() {
+    let project = TestProject::new("static-text");
+    // a minimal font with only the letter A
+    std::fs::copy(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../font-to-swf/tests/fonts/cubic.otf"),
+        project.directory.join("assets/cubic.otf"),
+    )
+    .unwrap();
+    let mut movie = Movie::default();
+    movie.symbols.push(Symbol::Font(FlitsFont {
+        path: "cubic.otf".into(),
+        characters: FontCharacters {
+            ascii: false,
+            additional_characters: "A".into(),
+            only_used: false,
+        },
+    }));
+    movie.symbols.push(Symbol::StaticText(StaticTextProperties {
+        name: "text".into(),
+        text: "AA".into(),
+        font_index: Some(0),
+        size: 12.0,
+        color: EditorColor::BLACK,
+        x: 0.0,
+        y: 0.0,
+    }));
+    movie.root.push(PlaceSymbol::new(1));
+
+    project.export(&movie, |swf| {
+        assert_eq!(
+            count_tags(&swf.tags, |tag| matches!(tag, Tag::DefineFont2(_))),
+            1
+        );
+        let text = swf
+            .tags
+            .iter()
+            .find_map(|tag| match tag {
+                Tag::DefineText(text) => Some(text),
+                _ => None,
+            })
+            .expect("the static text is exported as DefineText");
+        assert_eq!(text.records[0].glyphs.len(), 2);
+    });
+}
+
+#[test]
+fn prunes_movieclips_that_are_only_placed_in_unused_movieclips() {
+    let project = TestProject::new("prune-nested");
+    let mut movie = Movie::default();
+    movie
+        .symbols
+        .push(Symbol::MovieClip(MovieClip::new("used inner")));
+    movie.symbols.push(Symbol::MovieClip(MovieClip {
+        place_symbols: vec![PlaceSymbol::new(0)],
+        ..MovieClip::new("used outer")
+    }));
+    movie
+        .symbols
+        .push(Symbol::MovieClip(MovieClip::new("unused inner")));
+    movie.symbols.push(Symbol::MovieClip(MovieClip {
+        place_symbols: vec![PlaceSymbol::new(2)],
+        ..MovieClip::new("unused outer")
+    }));
+    movie.root.push(PlaceSymbol::new(1));
+
+    let swf_path = project.directory.join("output.swf");
+    let result = movie
+        .export_with_options(
+            project.directory.clone(),
+            swf_path.clone(),
+            &ExportOptions {
+                prune_unused: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(result.pruned_symbols, vec!["unused inner", "unused outer"]);
+
+    let swf_file = std::fs::File::open(swf_path).unwrap();
+    let swf_buf = swf::decompress_swf(swf_file).unwrap();
+    let swf = swf::parse_swf(&swf_buf).unwrap();
+    assert_eq!(
+        count_tags(&swf.tags, |tag| matches!(tag, Tag::DefineSprite(_))),
+        2
+    );
+}
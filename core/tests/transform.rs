@@ -0,0 +1,69 @@
+use flits_core::EditorTransform;
+use proptest::prelude::*;
+use swf::{Fixed16, Matrix, Twips};
+
+// the smallest steps swf matrices can store
+const TWIP_IN_PIXELS: f64 = 1.0 / 20.0;
+const FIXED16_STEP: f64 = 1.0 / 65536.0;
+
+fn to_matrix(x: f64, y: f64, x_scale: f64, y_scale: f64) -> Matrix {
+    EditorTransform {
+        x,
+        y,
+        x_scale,
+        y_scale,
+    }
+    .into()
+}
+
+proptest! {
+    #[test]
+    fn matrix_has_the_scale_and_position_of_the_transform(
+        x in -10000.0..10000.0f64,
+        y in -10000.0..10000.0f64,
+        x_scale in -100.0..100.0f64,
+        y_scale in -100.0..100.0f64,
+    ) {
+        let matrix = to_matrix(x, y, x_scale, y_scale);
+        // the raw values are the scale in 1/65536ths and the position in twips,
+        // rounded in either direction
+        prop_assert!((matrix.a.get() as f64 - x_scale / FIXED16_STEP).abs() < 1.0);
+        prop_assert!((matrix.d.get() as f64 - y_scale / FIXED16_STEP).abs() < 1.0);
+        // symbols can't be rotated or skewed
+        prop_assert_eq!(matrix.b.get(), 0);
+        prop_assert_eq!(matrix.c.get(), 0);
+        prop_assert!((matrix.tx.get() as f64 - x / TWIP_IN_PIXELS).abs() < 1.0);
+        prop_assert!((matrix.ty.get() as f64 - y / TWIP_IN_PIXELS).abs() < 1.0);
+    }
+
+    #[test]
+    fn scale_loses_at_most_one_fixed16_step(scale in -1000.0..1000.0f64) {
+        let matrix = to_matrix(0.0, 0.0, scale, scale);
+        prop_assert!((matrix.a.to_f64() - scale).abs() <= FIXED16_STEP);
+        prop_assert!((matrix.d.to_f64() - scale).abs() <= FIXED16_STEP);
+    }
+
+    // the stage can be up to 10000 pixels, but symbols can be placed far outside of it
+    #[test]
+    fn large_positions_lose_at_most_one_twip(
+        x in -1_000_000.0..1_000_000.0f64,
+        y in -1_000_000.0..1_000_000.0f64,
+    ) {
+        let matrix = to_matrix(x, y, 1.0, 1.0);
+        prop_assert!((matrix.tx.to_pixels() - x).abs() <= TWIP_IN_PIXELS);
+        prop_assert!((matrix.ty.to_pixels() - y).abs() <= TWIP_IN_PIXELS);
+    }
+}
+
+#[test]
+fn zero_scale_gives_a_degenerate_matrix() {
+    let matrix = to_matrix(10.0, 20.0, 0.0, 1.0);
+    assert_eq!(matrix.a, Fixed16::ZERO);
+    assert_eq!(matrix.d, Fixed16::ONE);
+    // the determinant is 0, so the symbol is flattened to a vertical line and can't be inverted
+    assert_eq!(
+        matrix.a.to_f64() * matrix.d.to_f64() - matrix.b.to_f64() * matrix.c.to_f64(),
+        0.0
+    );
+    assert_eq!(matrix.tx, Twips::from_pixels(10.0));
+}
@@ -0,0 +1,82 @@
+const REPOSITORY_URL: &str = "https://github.com/thisisjonathan/flits-editor";
+
+// the full list with license texts is generated by cargo-about in release.sh
+const THIRD_PARTY_CREDITS: &[(&str, &str)] = &[
+    ("Ruffle", "MIT OR Apache-2.0"),
+    ("egui", "MIT OR Apache-2.0"),
+    ("wgpu", "MIT OR Apache-2.0"),
+    ("winit", "Apache-2.0"),
+    ("image", "MIT OR Apache-2.0"),
+    ("ttf-parser", "MIT OR Apache-2.0"),
+    ("serde", "MIT OR Apache-2.0"),
+    ("undo", "MIT OR Apache-2.0"),
+    ("notify", "CC0-1.0"),
+    ("rfd", "MIT"),
+    ("MTASC", "GPL"),
+];
+
+pub struct AboutWindow {
+    graphics_backend: String,
+}
+impl AboutWindow {
+    pub fn new(graphics_backend: String) -> Self {
+        AboutWindow { graphics_backend }
+    }
+
+    pub fn do_ui(&self, egui_ctx: &egui::Context, is_open: &mut bool) {
+        egui::Window::new("About Flits Editor")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .open(is_open)
+            .show(egui_ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.label(egui::RichText::new("Flits Editor").size(32.0));
+                    ui.label("Preview build");
+                    ui.hyperlink_to("Source code on GitHub", REPOSITORY_URL);
+                });
+                ui.separator();
+                egui::Grid::new("about_grid").show(ui, |ui| {
+                    ui.label("Version:");
+                    ui.label(env!("CARGO_PKG_VERSION"));
+                    ui.end_row();
+
+                    ui.label("Build date:");
+                    ui.label(env!("FLITS_BUILD_DATE"));
+                    ui.end_row();
+
+                    ui.label("Git hash:");
+                    ui.label(env!("FLITS_GIT_HASH"));
+                    ui.end_row();
+
+                    ui.label("Platform:");
+                    ui.label(format!(
+                        "{} ({})",
+                        std::env::consts::OS,
+                        std::env::consts::ARCH
+                    ));
+                    ui.end_row();
+
+                    ui.label("Graphics:");
+                    ui.label(&self.graphics_backend);
+                    ui.end_row();
+                });
+                ui.separator();
+                ui.label("Flits Editor is made possible by:");
+                egui::ScrollArea::vertical()
+                    .max_height(150.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("about_credits_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for (name, license) in THIRD_PARTY_CREDITS {
+                                    ui.label(*name);
+                                    ui.label(*license);
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                ui.label("The full license texts are in third-party-licenses.html.");
+            });
+    }
+}
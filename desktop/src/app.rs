@@ -75,10 +75,12 @@ impl PlayerController for FlitsPlayerController {
         // this sets the quality to High which turns on anti-aliasing
         // this is the same default as in Ruffle
         renderer.set_quality(StageQuality::default());
+        let adapter_info = self.descriptors.adapter.get_info();
         self.player = Some(Mutex::new(FlitsPlayer::new(
             Box::new(renderer),
             arguments.event_loop.clone(),
             arguments.cli_params.clone(),
+            format!("{} ({})", adapter_info.backend.to_str(), adapter_info.name),
         )));
     }
 
@@ -129,7 +131,8 @@ impl ApplicationHandler<FlitsEvent> for App {
                     // changing this from the default 2 doesn't seem to have an effect but change it anyway to be sure
                     desired_maximum_frame_latency: 1,
                     height_offset_unscaled: 0,
-                    send_tab_to_player: false,
+                    // tab is used for selecting the next placed symbol
+                    send_tab_to_player: true,
                 },
                 FlitsGui {},
                 false,
@@ -158,6 +161,16 @@ impl ApplicationHandler<FlitsEvent> for App {
         _window_id: winit::window::WindowId,
         event: winit::event::WindowEvent,
     ) {
+        // closing the window saves the editor settings like exiting from the menu does
+        if matches!(event, winit::event::WindowEvent::CloseRequested) {
+            self.event_loop
+                .send_event(FlitsEvent::ExitRequested)
+                .unwrap_or_else(|err| {
+                    eprintln!("Unable to send exit event: {}", err);
+                    event_loop.exit();
+                });
+            return;
+        }
         if let Some(main_window) = &mut self.main_window {
             if main_window.window_event(event_loop, event.clone()) {
                 return;
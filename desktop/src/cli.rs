@@ -1,14 +1,67 @@
 use std::path::PathBuf;
 
+use anyhow::anyhow;
+use flits_core::{ExportOptions, Movie};
+
+const DEBUG_OVERLAY_FLAG: &str = "--debug-overlay";
+
 #[derive(Clone)]
 pub enum CliParams {
     NoProject,
     OpenProject(PathBuf),
+    // export without opening the editor: flits-editor --export <project> [--prune-unused]
+    Export {
+        path: PathBuf,
+        options: ExportOptions,
+    },
 }
 pub fn parse_command_line_arguments() -> CliParams {
-    let args: Vec<String> = std::env::args().collect();
+    // flags that work with every mode are handled separately
+    let args: Vec<String> = std::env::args()
+        .filter(|arg| arg != DEBUG_OVERLAY_FLAG)
+        .collect();
+    if args.len() > 2 && args[1] == "--export" {
+        return CliParams::Export {
+            path: PathBuf::from(args[2].clone()),
+            options: ExportOptions {
+                prune_unused: args[3..].iter().any(|arg| arg == "--prune-unused"),
+                ..Default::default()
+            },
+        };
+    }
     if args.len() > 1 {
         return CliParams::OpenProject(PathBuf::from(args[1].clone()));
     }
     CliParams::NoProject
 }
+// shows the fps and render timings on top of the editor
+pub fn has_debug_overlay_flag() -> bool {
+    std::env::args().any(|arg| arg == DEBUG_OVERLAY_FLAG)
+}
+
+pub fn export_project(path: PathBuf, options: ExportOptions) -> Result<(), anyhow::Error> {
+    let (project_file_path, directory) = if path.is_dir() {
+        (path.join("movie.json"), path)
+    } else {
+        let directory = path
+            .parent()
+            .ok_or_else(|| anyhow!("Project file is not in a directory"))?
+            .to_path_buf();
+        (path, directory)
+    };
+    let movie = Movie::load(project_file_path).map_err(|err| anyhow!(err.to_string()))?;
+    let swf_path = directory.join("output.swf");
+    let export_result = movie
+        .export_with_options(directory, swf_path, &options)
+        .map_err(|err| anyhow!(err.to_string()))?;
+    for name in export_result.pruned_symbols {
+        println!("Pruned unused symbol: {}", name);
+    }
+    for warning in export_result.warnings {
+        println!("Warning: {}", warning);
+    }
+    for warning in export_result.compatibility_warnings {
+        println!("Warning: {}", warning);
+    }
+    Ok(())
+}
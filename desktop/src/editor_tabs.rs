@@ -0,0 +1,109 @@
+use flits_editor_lib::{Editor, FlitsEvent, NeedsRedraw};
+use rfd::MessageDialogResult;
+use winit::event_loop::EventLoopProxy;
+
+pub enum TabBarAction {
+    None,
+    Switched,
+    Close(usize),
+}
+
+// all the projects that are open, only the active one is shown
+pub struct EditorTabs {
+    editors: Vec<Editor>,
+    active: usize,
+}
+impl EditorTabs {
+    pub fn new(editor: Editor) -> Self {
+        EditorTabs {
+            editors: vec![editor],
+            active: 0,
+        }
+    }
+
+    pub fn add(&mut self, editor: Editor) {
+        self.editors.push(editor);
+        self.active = self.editors.len() - 1;
+    }
+
+    pub fn active(&self) -> &Editor {
+        &self.editors[self.active]
+    }
+    pub fn active_mut(&mut self) -> &mut Editor {
+        &mut self.editors[self.active]
+    }
+    pub fn active_index(&self) -> usize {
+        self.active
+    }
+    pub fn editors_mut(&mut self) -> impl Iterator<Item = &mut Editor> {
+        self.editors.iter_mut()
+    }
+
+    pub fn do_ui(
+        &mut self,
+        egui_ctx: &egui::Context,
+        event_loop: &EventLoopProxy<FlitsEvent>,
+    ) -> (NeedsRedraw, TabBarAction) {
+        // the tab bar is at the bottom so it doesn't change the height of the menu above the stage
+        let mut action = TabBarAction::None;
+        egui::TopBottomPanel::bottom("tab_bar").show(egui_ctx, |ui| {
+            ui.horizontal(|ui| {
+                for (i, editor) in self.editors.iter().enumerate() {
+                    let name = format!(
+                        "{}{}",
+                        editor.project_name(),
+                        if editor.unsaved_changes() { "*" } else { "" }
+                    );
+                    if ui.selectable_label(i == self.active, name).clicked() && i != self.active {
+                        self.active = i;
+                        action = TabBarAction::Switched;
+                    }
+                    if ui.small_button("x").on_hover_text("Close").clicked() {
+                        action = TabBarAction::Close(i);
+                    }
+                    ui.separator();
+                }
+            });
+        });
+        let needs_redraw = self.active_mut().do_ui(egui_ctx, event_loop);
+        match action {
+            TabBarAction::None => (needs_redraw, action),
+            _ => (NeedsRedraw::Yes, action),
+        }
+    }
+
+    // returns false if the user cancelled closing the tab
+    pub fn close(&mut self, index: usize) -> bool {
+        let editor = &mut self.editors[index];
+        if editor.unsaved_changes() {
+            match rfd::MessageDialog::new()
+                .set_title("Flits Editor")
+                .set_buttons(rfd::MessageButtons::YesNoCancel)
+                .set_description(format!(
+                    "Do you want to save the changes to {} before closing it?",
+                    editor.project_name()
+                ))
+                .show()
+            {
+                MessageDialogResult::Yes => {
+                    // keep the tab open so the error is visible and the changes aren't lost
+                    if !editor.save() {
+                        return false;
+                    }
+                }
+                MessageDialogResult::No => {}
+                _ => return false,
+            }
+        }
+        self.editors[index].save_editor_settings();
+        self.editors.remove(index);
+        if self.active > index || self.active >= self.editors.len() {
+            self.active = self.active.saturating_sub(1);
+        }
+        true
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.editors.is_empty()
+    }
+}
@@ -7,8 +7,10 @@
 // See https://docs.microsoft.com/en-us/cpp/build/reference/subsystem?view=msvc-160 for details.
 #![windows_subsystem = "windows"]
 
+mod about;
 mod app;
 mod cli;
+mod editor_tabs;
 mod player;
 mod welcome;
 
@@ -16,7 +18,7 @@ use std::panic::PanicHookInfo;
 
 use anyhow::{Context, Error};
 use app::App;
-use cli::parse_command_line_arguments;
+use cli::{export_project, parse_command_line_arguments, CliParams};
 use flits_editor_lib::FlitsEvent;
 use winit::event_loop::EventLoop;
 
@@ -70,6 +72,9 @@ fn shutdown() {
 
 fn start_app() -> Result<(), Error> {
     let cli_params = parse_command_line_arguments();
+    if let CliParams::Export { path, options } = cli_params {
+        return export_project(path, options);
+    }
     let event_loop: EventLoop<FlitsEvent> = EventLoop::with_user_event().build()?;
     let mut app = App::new(event_loop.create_proxy(), cli_params);
     event_loop.run_app(&mut app).context("Event loop failure")
@@ -10,33 +10,44 @@ use winit::{
     event_loop::{ActiveEventLoop, EventLoopProxy},
 };
 
-use crate::{cli::CliParams, welcome::WelcomeScreen};
+use crate::{
+    about::AboutWindow,
+    cli::{has_debug_overlay_flag, CliParams},
+    editor_tabs::{EditorTabs, TabBarAction},
+    welcome::WelcomeScreen,
+};
 
 enum FlitsState {
     Welcome(WelcomeScreen),
-    Editor(Editor),
+    Editor(EditorTabs),
 }
 
 pub struct FlitsPlayer {
     renderer: Box<dyn RenderBackend>,
     event_loop: EventLoopProxy<FlitsEvent>,
     state: FlitsState,
+    about_window: AboutWindow,
     is_about_visible: bool,
+    debug_overlay: bool,
 }
 impl FlitsPlayer {
     pub fn new(
         renderer: Box<dyn RenderBackend>,
         event_loop: EventLoopProxy<FlitsEvent>,
         cli_params: CliParams,
+        graphics_backend: String,
     ) -> Self {
         let mut player = FlitsPlayer {
             renderer,
             event_loop: event_loop,
             state: FlitsState::Welcome(WelcomeScreen::new()),
+            about_window: AboutWindow::new(graphics_backend),
             is_about_visible: false,
+            debug_overlay: has_debug_overlay_flag(),
         };
         match cli_params {
-            CliParams::NoProject => {
+            // exporting from the command line is handled before the editor starts
+            CliParams::NoProject | CliParams::Export { .. } => {
                 // call set_state  to force title update
                 player.set_state(FlitsState::Welcome(WelcomeScreen::new()));
             }
@@ -50,32 +61,25 @@ impl FlitsPlayer {
             FlitsState::Welcome(welcome_screen) => {
                 welcome_screen.do_ui(egui_ctx, self.event_loop.clone())
             }
-            FlitsState::Editor(editor) => {
-                needs_redraw = editor.do_ui(egui_ctx, &self.event_loop);
+            FlitsState::Editor(editor_tabs) => {
+                let (editor_needs_redraw, action) = editor_tabs.do_ui(egui_ctx, &self.event_loop);
+                needs_redraw = editor_needs_redraw;
+                match action {
+                    TabBarAction::None => {}
+                    TabBarAction::Switched => self.update_title(),
+                    TabBarAction::Close(index) => self.close_tab(index),
+                }
             }
         }
 
         if self.is_about_visible {
-            self.about_window(egui_ctx);
+            self.about_window
+                .do_ui(egui_ctx, &mut self.is_about_visible);
         }
 
         needs_redraw
     }
 
-    fn about_window(&mut self, egui_ctx: &egui::Context) {
-        egui::Window::new("About Flits Editor")
-            .collapsible(false)
-            .resizable(false)
-            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
-            .open(&mut self.is_about_visible)
-            .show(egui_ctx, |ui| {
-                ui.vertical_centered(|ui| {
-                    ui.label(egui::RichText::new("Flits Editor").size(32.0));
-                    ui.label("Preview build");
-                })
-            });
-    }
-
     pub fn window_event(&mut self, _event_loop: &ActiveEventLoop, _event: WindowEvent) {}
 
     pub fn user_event(&mut self, event_loop: &ActiveEventLoop, event: FlitsEvent) -> NeedsRedraw {
@@ -97,7 +101,12 @@ impl FlitsPlayer {
                 }
                 let json_path = new_project_data.path.join("movie.json");
                 let movie = Movie::from_properties(new_project_data.movie_properties);
-                movie.save(&json_path);
+                if let Err(err) = movie.save(&json_path) {
+                    rfd::MessageDialog::new()
+                        .set_description(format!("Unable to create the project: {}", err))
+                        .show();
+                    return NeedsRedraw::Yes;
+                }
                 self.open_editor(json_path);
                 NeedsRedraw::Yes
             }
@@ -113,10 +122,18 @@ impl FlitsPlayer {
                 NeedsRedraw::Yes
             }
             FlitsEvent::CloseFile => {
-                self.set_state(FlitsState::Welcome(WelcomeScreen::new()));
+                if let FlitsState::Editor(editor_tabs) = &self.state {
+                    let index = editor_tabs.active_index();
+                    self.close_tab(index);
+                }
                 NeedsRedraw::Yes
             }
             FlitsEvent::ExitRequested => {
+                if let FlitsState::Editor(editor_tabs) = &mut self.state {
+                    for editor in editor_tabs.editors_mut() {
+                        editor.save_editor_settings();
+                    }
+                }
                 // TODO: the old code calls shutdown()
                 event_loop.exit();
                 NeedsRedraw::No
@@ -125,20 +142,54 @@ impl FlitsPlayer {
                 self.is_about_visible = true;
                 NeedsRedraw::Yes
             }
-            FlitsEvent::CommandOutput(line) => {
-                if let FlitsState::Editor(editor) = &mut self.state {
-                    editor.receive_command_output(line)
-                } else {
-                    NeedsRedraw::No
+            // the output only goes to the editor that started the run
+            FlitsEvent::CommandOutput(run_id, line) => {
+                let FlitsState::Editor(editor_tabs) = &mut self.state else {
+                    return NeedsRedraw::No;
+                };
+                match editor_tabs
+                    .editors_mut()
+                    .find(|editor| editor.is_running(run_id))
+                {
+                    Some(editor) => editor.receive_command_output(run_id, line),
+                    None => NeedsRedraw::No,
                 }
             }
-            FlitsEvent::RuffleClosed => {
-                if let FlitsState::Editor(editor) = &mut self.state {
-                    editor.on_ruffle_closed();
-                    NeedsRedraw::Yes
-                } else {
-                    NeedsRedraw::No
+            // only the editor that started the run does something with this
+            FlitsEvent::RuffleClosed(run_id, exit_code) => {
+                let mut needs_redraw = NeedsRedraw::No;
+                if let FlitsState::Editor(editor_tabs) = &mut self.state {
+                    for editor in editor_tabs.editors_mut() {
+                        if let NeedsRedraw::Yes = editor.on_ruffle_closed(run_id, exit_code) {
+                            needs_redraw = NeedsRedraw::Yes;
+                        }
+                    }
                 }
+                needs_redraw
+            }
+            // only the editor that started the export does something with this
+            FlitsEvent::ExportProgress(export_id, progress) => {
+                let mut needs_redraw = NeedsRedraw::No;
+                if let FlitsState::Editor(editor_tabs) = &mut self.state {
+                    for editor in editor_tabs.editors_mut() {
+                        if let NeedsRedraw::Yes = editor.on_export_progress(export_id, progress) {
+                            needs_redraw = NeedsRedraw::Yes;
+                        }
+                    }
+                }
+                needs_redraw
+            }
+            // every editor checks if the asset is part of its project
+            FlitsEvent::AssetChanged(path) => {
+                let mut needs_redraw = NeedsRedraw::No;
+                if let FlitsState::Editor(editor_tabs) = &mut self.state {
+                    for editor in editor_tabs.editors_mut() {
+                        if let NeedsRedraw::Yes = editor.on_asset_changed(&path) {
+                            needs_redraw = NeedsRedraw::Yes;
+                        }
+                    }
+                }
+                needs_redraw
             }
             _ => NeedsRedraw::No,
         }
@@ -151,7 +202,20 @@ impl FlitsPlayer {
             self.event_loop.clone(),
         );
         match result {
-            Ok(editor) => self.set_state(FlitsState::Editor(editor)),
+            Ok(mut editor) => {
+                if self.debug_overlay {
+                    editor.show_performance_overlay();
+                }
+                match &mut self.state {
+                    FlitsState::Editor(editor_tabs) => {
+                        editor_tabs.add(editor);
+                        self.update_title();
+                    }
+                    FlitsState::Welcome(_) => {
+                        self.set_state(FlitsState::Editor(EditorTabs::new(editor)))
+                    }
+                }
+            }
             Err(error) => {
                 rfd::MessageDialog::new()
                     .set_level(rfd::MessageLevel::Error)
@@ -162,6 +226,28 @@ impl FlitsPlayer {
         }
     }
 
+    fn close_tab(&mut self, index: usize) {
+        let FlitsState::Editor(editor_tabs) = &mut self.state else {
+            return;
+        };
+        if !editor_tabs.close(index) {
+            return;
+        }
+        if editor_tabs.is_empty() {
+            self.set_state(FlitsState::Welcome(WelcomeScreen::new()));
+        } else {
+            self.update_title();
+        }
+    }
+
+    fn update_title(&self) {
+        self.event_loop
+            .send_event(FlitsEvent::UpdateTitle)
+            .unwrap_or_else(|err| {
+                eprintln!("Unable to send command output event: {}", err);
+            });
+    }
+
     fn set_state(&mut self, state: FlitsState) {
         self.state = state;
         self.event_loop
@@ -179,7 +265,8 @@ impl FlitsPlayer {
     pub fn title(&self) -> String {
         match &self.state {
             FlitsState::Welcome(_) => "Flits Editor".into(),
-            FlitsState::Editor(editor) => {
+            FlitsState::Editor(editor_tabs) => {
+                let editor = editor_tabs.active();
                 format!(
                     "{}{} - Flits Editor",
                     editor.project_name(),
@@ -200,7 +287,7 @@ impl Player for FlitsPlayer {
     fn render(&mut self) {
         match &mut self.state {
             FlitsState::Welcome(welcome_screen) => welcome_screen.render(&mut self.renderer),
-            FlitsState::Editor(editor) => editor.render(&mut self.renderer),
+            FlitsState::Editor(editor_tabs) => editor_tabs.active_mut().render(&mut self.renderer),
         }
     }
 
@@ -219,10 +306,10 @@ impl Player for FlitsPlayer {
     }
 
     fn handle_mouse_move(&mut self, mouse_x: f64, mouse_y: f64) {
-        let FlitsState::Editor(editor) = &mut self.state else {
+        let FlitsState::Editor(editor_tabs) = &mut self.state else {
             return;
         };
-        editor.handle_mouse_move(mouse_x, mouse_y);
+        editor_tabs.active_mut().handle_mouse_move(mouse_x, mouse_y);
     }
 
     fn handle_mouse_input(
@@ -232,9 +319,32 @@ impl Player for FlitsPlayer {
         button: winit::event::MouseButton,
         state: winit::event::ElementState,
     ) {
-        let FlitsState::Editor(editor) = &mut self.state else {
+        let FlitsState::Editor(editor_tabs) = &mut self.state else {
+            return;
+        };
+        editor_tabs
+            .active_mut()
+            .handle_mouse_input(mouse_x, mouse_y, button, state);
+    }
+
+    fn handle_mouse_wheel(
+        &mut self,
+        mouse_x: f64,
+        mouse_y: f64,
+        delta: winit::event::MouseScrollDelta,
+    ) {
+        let FlitsState::Editor(editor_tabs) = &mut self.state else {
+            return;
+        };
+        editor_tabs
+            .active_mut()
+            .handle_mouse_wheel(mouse_x, mouse_y, delta);
+    }
+
+    fn handle_key_input(&mut self, event: winit::event::KeyEvent) {
+        let FlitsState::Editor(editor_tabs) = &mut self.state else {
             return;
         };
-        editor.handle_mouse_input(mouse_x, mouse_y, button, state);
+        editor_tabs.active_mut().handle_key_input(event);
     }
 }
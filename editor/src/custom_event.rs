@@ -12,9 +12,14 @@ pub enum FlitsEvent {
     UpdateTitle,
     UpdateHeightOffset,
 
-    /// output received from running Ruffle process
-    CommandOutput(String),
-    RuffleClosed,
+    /// id of the run and output received from the running Ruffle process
+    CommandOutput(u64, String),
+    /// id of the run and the exit code of the Ruffle process
+    RuffleClosed(u64, Option<i32>),
+    /// id of the export and its progress on a background thread, from 0 to 1
+    ExportProgress(u64, f32),
+    /// a png or ttf file in the assets directory of a project changed on disk
+    AssetChanged(PathBuf),
 }
 
 #[derive(Debug, Default, Clone)]
@@ -1,19 +1,28 @@
-use undo::Edit;
+use std::time::{Duration, Instant};
+
+use undo::{Edit, Merged};
 
 use flits_core::{
-    BitmapProperties, FlitsFont, Movie, MovieClip, MovieClipProperties, MovieProperties,
-    PlaceSymbol, PlacedSymbolIndex, Symbol, SymbolIndex, SymbolIndexOrRoot,
+    BitmapProperties, FlitsFont, FlitsSound, FrameScript, Movie, MovieClip, MovieClipProperties,
+    MovieProperties, PlaceSymbol, PlacedSymbolIndex, PreloaderType, SoundAction,
+    StaticTextProperties, Symbol, SymbolIndex, SymbolIndexOrRoot,
 };
 
 pub enum MovieEdit {
     EditMovieProperties(MoviePropertiesEdit),
+    EditRootScripts(RootScriptsEdit),
 
     AddMovieClip(AddMovieClipEdit),
+    AddStaticText(AddStaticTextEdit),
+    AddSymbol(AddSymbolEdit),
     RemoveSymbol(RemoveSymbolEdit),
 
     EditBitmapProperties(BitmapPropertiesEdit),
     EditMovieClipProperties(MovieClipPropertiesEdit),
     EditFontProperties(FontPropertiesEdit),
+    EditSoundProperties(SoundPropertiesEdit),
+    EditStaticTextProperties(StaticTextPropertiesEdit),
+    RenameSymbols(RenameSymbolsEdit),
 
     AddPlacedSymbol(AddPlacedSymbolEdit),
 
@@ -26,10 +35,40 @@ impl MovieEdit {
         editing_symbol_index: SymbolIndexOrRoot,
         edit: PlacedSymbolEdit,
     ) -> Self {
-        Self::Multi(MultiEdit {
+        Self::Multi(MultiEdit::new(
             editing_symbol_index,
-            edits: vec![MultiEditEdit::EditPlacedSymbol(edit)],
-        })
+            vec![MultiEditEdit::EditPlacedSymbol(edit)],
+        ))
+    }
+    // the editing scope whose undo history this edit is part of
+    pub fn scope(&self) -> SymbolIndexOrRoot {
+        match self {
+            MovieEdit::AddPlacedSymbol(edit) => edit.editing_symbol_index,
+            MovieEdit::Multi(edit) => edit.editing_symbol_index,
+            // edits to the movie properties and the library are part of the root history
+            _ => None,
+        }
+    }
+    // dragging a value in the properties panel creates an edit every frame,
+    // those should be a single step in the undo history
+    pub fn can_merge(&self, other: &Self) -> bool {
+        let (MovieEdit::Multi(edit), MovieEdit::Multi(other)) = (self, other) else {
+            return false;
+        };
+        edit.editing_symbol_index == other.editing_symbol_index
+            && edit.edits.len() == other.edits.len()
+            && other.time.duration_since(edit.time) < MERGE_TIME
+            && edit
+                .edits
+                .iter()
+                .zip(other.edits.iter())
+                .all(|edits| match edits {
+                    (
+                        MultiEditEdit::EditPlacedSymbol(edit),
+                        MultiEditEdit::EditPlacedSymbol(other),
+                    ) => edit.placed_symbol_index == other.placed_symbol_index,
+                    _ => false,
+                })
     }
 }
 impl Edit for MovieEdit {
@@ -39,11 +78,17 @@ impl Edit for MovieEdit {
     fn edit(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
         match self {
             MovieEdit::EditMovieProperties(edit) => edit.edit(target),
+            MovieEdit::EditRootScripts(edit) => edit.edit(target),
             MovieEdit::AddMovieClip(edit) => edit.edit(target),
+            MovieEdit::AddStaticText(edit) => edit.edit(target),
+            MovieEdit::AddSymbol(edit) => edit.edit(target),
             MovieEdit::RemoveSymbol(edit) => edit.edit(target),
             MovieEdit::EditBitmapProperties(edit) => edit.edit(target),
             MovieEdit::EditMovieClipProperties(edit) => edit.edit(target),
             MovieEdit::EditFontProperties(edit) => edit.edit(target),
+            MovieEdit::EditSoundProperties(edit) => edit.edit(target),
+            MovieEdit::EditStaticTextProperties(edit) => edit.edit(target),
+            MovieEdit::RenameSymbols(edit) => edit.edit(target),
             MovieEdit::AddPlacedSymbol(edit) => edit.edit(target),
             MovieEdit::Multi(edit) => edit.edit(target),
         }
@@ -52,15 +97,34 @@ impl Edit for MovieEdit {
     fn undo(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
         match self {
             MovieEdit::EditMovieProperties(edit) => edit.undo(target),
+            MovieEdit::EditRootScripts(edit) => edit.undo(target),
             MovieEdit::AddMovieClip(edit) => edit.undo(target),
+            MovieEdit::AddStaticText(edit) => edit.undo(target),
+            MovieEdit::AddSymbol(edit) => edit.undo(target),
             MovieEdit::RemoveSymbol(edit) => edit.undo(target),
             MovieEdit::EditBitmapProperties(edit) => edit.undo(target),
             MovieEdit::EditMovieClipProperties(edit) => edit.undo(target),
             MovieEdit::EditFontProperties(edit) => edit.undo(target),
+            MovieEdit::EditSoundProperties(edit) => edit.undo(target),
+            MovieEdit::EditStaticTextProperties(edit) => edit.undo(target),
+            MovieEdit::RenameSymbols(edit) => edit.undo(target),
             MovieEdit::AddPlacedSymbol(edit) => edit.undo(target),
             MovieEdit::Multi(edit) => edit.undo(target),
         }
     }
+
+    fn merge(&mut self, other: Self) -> Merged<Self> {
+        if !self.can_merge(&other) {
+            return Merged::No(other);
+        }
+        match (self, other) {
+            (MovieEdit::Multi(edit), MovieEdit::Multi(other)) => {
+                edit.merge(other);
+                Merged::Yes
+            }
+            _ => unreachable!("can_merge only allows merging multi edits"),
+        }
+    }
 }
 pub enum MoviePropertiesOutput {
     Stage(SymbolIndexOrRoot),
@@ -79,13 +143,9 @@ pub struct AddMovieClipEdit {
 }
 impl AddMovieClipEdit {
     fn edit(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
-        target.symbols.push(Symbol::MovieClip(MovieClip {
-            properties: MovieClipProperties {
-                name: self.name.clone(),
-                class_name: "".to_string(),
-            },
-            place_symbols: vec![],
-        }));
+        target
+            .symbols
+            .push(Symbol::MovieClip(MovieClip::new(self.name.clone())));
         MoviePropertiesOutput::Stage(Some(target.symbols.len() - 1))
     }
     fn undo(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
@@ -93,14 +153,57 @@ impl AddMovieClipEdit {
         MoviePropertiesOutput::Stage(None)
     }
 }
+pub struct AddStaticTextEdit {
+    pub properties: StaticTextProperties,
+}
+impl AddStaticTextEdit {
+    fn edit(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
+        target
+            .symbols
+            .push(Symbol::StaticText(self.properties.clone()));
+        MoviePropertiesOutput::Properties(Some(target.symbols.len() - 1))
+    }
+    fn undo(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
+        target.symbols.pop();
+        MoviePropertiesOutput::Properties(None)
+    }
+}
+// for symbols that are created from a file, like fonts
+pub struct AddSymbolEdit {
+    pub symbol: Symbol,
+}
+impl AddSymbolEdit {
+    fn edit(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
+        target.symbols.push(self.symbol.clone_without_cache());
+        MoviePropertiesOutput::Properties(Some(target.symbols.len() - 1))
+    }
+    fn undo(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
+        target.symbols.pop();
+        MoviePropertiesOutput::Properties(None)
+    }
+}
 pub struct RemoveSymbolEdit {
     pub symbol_index: SymbolIndex,
     pub symbol: Symbol, // for undoing
     pub remove_place_symbol_edits: Vec<RemovePlacedSymbolEdit>,
+    // text spans that used the removed font, (container, placed symbol index, span index)
+    pub fallback_spans: Vec<(SymbolIndexOrRoot, PlacedSymbolIndex, usize)>,
+    // sound actions that played the removed sound, (movieclip, sound action index, sound action)
+    pub removed_sound_actions: Vec<(SymbolIndex, usize, SoundAction)>,
+    // static texts that used the removed font
+    pub static_texts_without_font: Vec<SymbolIndex>,
+    // the removed movieclip was the graphic of the preloader
+    pub removed_preloader_graphic: bool,
 }
 impl RemoveSymbolEdit {
     fn edit(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
         self.remove_place_symbol_edits = vec![];
+        self.fallback_spans = vec![];
+        self.removed_sound_actions = vec![];
+        self.remove_sound_actions(target);
+        self.static_texts_without_font = vec![];
+        self.remove_static_text_fonts(target);
+        self.remove_preloader_graphic(target);
         // remove the placed symbols that place this symbol
         self.remove_placed_symbols(target, None);
         for i in 0..target.symbols.len() {
@@ -129,13 +232,103 @@ impl RemoveSymbolEdit {
                     placed_symbol_index: i,
                     placed_symbol: placed_symbols[i].clone(),
                 });
+                continue;
             } else if placed_symbols[i].symbol_index > self.symbol_index {
                 // decrease the symbol index because removing the movieclip causes the index of the other moveclips to change
                 placed_symbols[i].symbol_index -= 1;
             }
+            let font_symbol_index = placed_symbols[i].symbol_index;
+            if let Some(text) = &mut placed_symbols[i].text {
+                for (span_index, span) in text.spans.iter_mut().enumerate() {
+                    if span.font_symbol_index == self.symbol_index {
+                        // use the font of the text field instead of the removed font
+                        span.font_symbol_index = font_symbol_index;
+                        self.fallback_spans.push((symbol_index, i, span_index));
+                    } else if span.font_symbol_index > self.symbol_index {
+                        span.font_symbol_index -= 1;
+                    }
+                }
+            }
+        }
+    }
+    fn remove_sound_actions(&mut self, target: &mut Movie) {
+        for (movieclip_index, symbol) in target.symbols.iter_mut().enumerate() {
+            let Symbol::MovieClip(movieclip) = symbol else {
+                continue;
+            };
+            let sound_actions = &mut movieclip.properties.sound_actions;
+            for i in (0..sound_actions.len()).rev() {
+                if sound_actions[i].sound_index == self.symbol_index {
+                    self.removed_sound_actions
+                        .push((movieclip_index, i, sound_actions.remove(i)));
+                } else if sound_actions[i].sound_index > self.symbol_index {
+                    sound_actions[i].sound_index -= 1;
+                }
+            }
+        }
+    }
+    fn remove_preloader_graphic(&mut self, target: &mut Movie) {
+        self.removed_preloader_graphic = false;
+        let PreloaderType::CustomGraphic { movieclip_index } = &mut target.properties.preloader
+        else {
+            return;
+        };
+        if *movieclip_index == self.symbol_index {
+            // the loading bar is the closest to a preloader without the graphic
+            target.properties.preloader = PreloaderType::StartAfterLoading;
+            self.removed_preloader_graphic = true;
+        } else if *movieclip_index > self.symbol_index {
+            *movieclip_index -= 1;
+        }
+    }
+    fn remove_static_text_fonts(&mut self, target: &mut Movie) {
+        for (static_text_index, symbol) in target.symbols.iter_mut().enumerate() {
+            let Symbol::StaticText(static_text) = symbol else {
+                continue;
+            };
+            match static_text.font_index {
+                Some(font_index) if font_index == self.symbol_index => {
+                    static_text.font_index = None;
+                    self.static_texts_without_font.push(static_text_index);
+                }
+                Some(font_index) if font_index > self.symbol_index => {
+                    static_text.font_index = Some(font_index - 1);
+                }
+                _ => {}
+            }
         }
     }
     fn undo(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
+        if self.removed_preloader_graphic {
+            target.properties.preloader = PreloaderType::CustomGraphic {
+                movieclip_index: self.symbol_index,
+            };
+        } else if let PreloaderType::CustomGraphic { movieclip_index } =
+            &mut target.properties.preloader
+        {
+            if *movieclip_index >= self.symbol_index {
+                *movieclip_index += 1;
+            }
+        }
+        for symbol in &mut target.symbols {
+            match symbol {
+                Symbol::MovieClip(movieclip) => {
+                    for sound_action in &mut movieclip.properties.sound_actions {
+                        if sound_action.sound_index >= self.symbol_index {
+                            sound_action.sound_index += 1;
+                        }
+                    }
+                }
+                Symbol::StaticText(static_text) => {
+                    if let Some(font_index) = &mut static_text.font_index {
+                        if *font_index >= self.symbol_index {
+                            *font_index += 1;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
         self.increase_placed_symbols(target.get_placed_symbols_mut(None));
         for i in 0..target.symbols.len() {
             match target.symbols[i] {
@@ -151,10 +344,36 @@ impl RemoveSymbolEdit {
         for i in 0..self.remove_place_symbol_edits.len() {
             self.remove_place_symbol_edits[i].undo(target);
         }
+        // insert in the opposite order they were removed in, so they end up at their original index
+        for (movieclip_index, sound_action_index, sound_action) in
+            self.removed_sound_actions.iter().rev()
+        {
+            if let Symbol::MovieClip(movieclip) = &mut target.symbols[*movieclip_index] {
+                movieclip
+                    .properties
+                    .sound_actions
+                    .insert(*sound_action_index, sound_action.clone());
+            }
+        }
+        for static_text_index in &self.static_texts_without_font {
+            if let Symbol::StaticText(static_text) = &mut target.symbols[*static_text_index] {
+                static_text.font_index = Some(self.symbol_index);
+            }
+        }
+        // the placed symbols are back at their original index, so the spans can be restored
+        for (container, placed_symbol_index, span_index) in &self.fallback_spans {
+            if let Some(text) =
+                &mut target.get_placed_symbols_mut(*container)[*placed_symbol_index].text
+            {
+                text.spans[*span_index].font_symbol_index = self.symbol_index;
+            }
+        }
         match &self.symbol {
             Symbol::Bitmap(_) => MoviePropertiesOutput::Properties(Some(self.symbol_index)),
             Symbol::MovieClip(_) => MoviePropertiesOutput::Stage(Some(self.symbol_index)),
             Symbol::Font(_) => MoviePropertiesOutput::Properties(Some(self.symbol_index)),
+            Symbol::Sound(_) => MoviePropertiesOutput::Properties(Some(self.symbol_index)),
+            Symbol::StaticText(_) => MoviePropertiesOutput::Properties(Some(self.symbol_index)),
         }
     }
     fn increase_placed_symbols(&self, placed_symbols: &mut Vec<PlaceSymbol>) {
@@ -163,6 +382,13 @@ impl RemoveSymbolEdit {
                 // increase the symbol index to make room for the reinserted symbol
                 placed_symbols[i].symbol_index += 1;
             }
+            if let Some(text) = &mut placed_symbols[i].text {
+                for span in &mut text.spans {
+                    if span.font_symbol_index >= self.symbol_index {
+                        span.font_symbol_index += 1;
+                    }
+                }
+            }
         }
     }
 }
@@ -182,6 +408,21 @@ impl MoviePropertiesEdit {
     }
 }
 
+pub struct RootScriptsEdit {
+    pub before: Vec<FrameScript>,
+    pub after: Vec<FrameScript>,
+}
+impl RootScriptsEdit {
+    fn edit(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
+        target.root_scripts = self.after.clone();
+        MoviePropertiesOutput::Properties(None)
+    }
+    fn undo(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
+        target.root_scripts = self.before.clone();
+        MoviePropertiesOutput::Properties(None)
+    }
+}
+
 pub struct BitmapPropertiesEdit {
     pub editing_symbol_index: SymbolIndex,
 
@@ -257,6 +498,85 @@ impl FontPropertiesEdit {
     }
 }
 
+pub struct SoundPropertiesEdit {
+    pub editing_symbol_index: SymbolIndex,
+
+    pub before: FlitsSound,
+    pub after: FlitsSound,
+}
+impl SoundPropertiesEdit {
+    fn edit(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
+        target.symbols[self.editing_symbol_index] = Symbol::Sound(self.after.clone());
+
+        MoviePropertiesOutput::Properties(Some(self.editing_symbol_index))
+    }
+    fn undo(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
+        target.symbols[self.editing_symbol_index] = Symbol::Sound(self.before.clone());
+
+        MoviePropertiesOutput::Properties(Some(self.editing_symbol_index))
+    }
+}
+
+pub struct StaticTextPropertiesEdit {
+    pub editing_symbol_index: SymbolIndex,
+
+    pub before: StaticTextProperties,
+    pub after: StaticTextProperties,
+}
+impl StaticTextPropertiesEdit {
+    fn edit(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
+        target.symbols[self.editing_symbol_index] = Symbol::StaticText(self.after.clone());
+
+        MoviePropertiesOutput::Properties(Some(self.editing_symbol_index))
+    }
+    fn undo(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
+        target.symbols[self.editing_symbol_index] = Symbol::StaticText(self.before.clone());
+
+        MoviePropertiesOutput::Properties(Some(self.editing_symbol_index))
+    }
+}
+
+pub struct SymbolRename {
+    pub symbol_index: SymbolIndex,
+    pub before: String,
+    pub after: String,
+}
+// renames multiple symbols as a single step in the undo history
+pub struct RenameSymbolsEdit {
+    pub renames: Vec<SymbolRename>,
+}
+impl RenameSymbolsEdit {
+    fn edit(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
+        for rename in &self.renames {
+            Self::set_name(
+                &mut target.symbols[rename.symbol_index],
+                rename.after.clone(),
+            );
+        }
+        // show the movie properties so the properties panel doesn't show an old name
+        MoviePropertiesOutput::Properties(None)
+    }
+    fn undo(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
+        for rename in &self.renames {
+            Self::set_name(
+                &mut target.symbols[rename.symbol_index],
+                rename.before.clone(),
+            );
+        }
+        MoviePropertiesOutput::Properties(None)
+    }
+    fn set_name(symbol: &mut Symbol, name: String) {
+        match symbol {
+            Symbol::Bitmap(bitmap) => bitmap.properties.name = name,
+            Symbol::MovieClip(movieclip) => movieclip.properties.name = name,
+            // the name of a font is its path, renaming it would break the font
+            Symbol::Font(_) => panic!("Renaming a font"),
+            Symbol::Sound(sound) => sound.name = name,
+            Symbol::StaticText(static_text) => static_text.name = name,
+        }
+    }
+}
+
 pub struct AddPlacedSymbolEdit {
     pub editing_symbol_index: SymbolIndexOrRoot,
     pub placed_symbol: PlaceSymbol,
@@ -283,12 +603,26 @@ impl AddPlacedSymbolEdit {
 
         MoviePropertiesOutput::remove_placed_symbol(self.editing_symbol_index)
     }
+
+    fn multi_edit(&mut self, target: &mut Movie) -> MultiEditOutput {
+        self.edit(target);
+        MultiEditOutput::PlacedSymbolProperties(
+            self.placed_symbol_index
+                .expect("AddPlacedSymbolEdit without placed_symbol_index after editing"),
+        )
+    }
+
+    fn multi_undo(&mut self, target: &mut Movie) -> MultiEditOutput {
+        self.undo(target);
+        MultiEditOutput::RemovedPlacedSymbol()
+    }
 }
 
 // this contains only edits that make sense for multi edit
 pub enum MultiEditEdit {
     EditPlacedSymbol(PlacedSymbolEdit),
     RemovePlacedSymbol(RemovePlacedSymbolEdit),
+    AddPlacedSymbol(AddPlacedSymbolEdit),
 }
 // the surrounding MultiEdit has the editing symbol index
 enum MultiEditOutput {
@@ -296,12 +630,34 @@ enum MultiEditOutput {
     // needs to be a seperate item because removed symbol should not be selected
     RemovedPlacedSymbol(),
 }
+// edits that happen within this time of the previous edit of the same placed symbols get merged
+const MERGE_TIME: Duration = Duration::from_millis(500);
 // edits multiple placed symbols at once
 pub struct MultiEdit {
     pub editing_symbol_index: SymbolIndexOrRoot,
     pub edits: Vec<MultiEditEdit>,
+    time: Instant, // for merging
 }
 impl MultiEdit {
+    pub fn new(editing_symbol_index: SymbolIndexOrRoot, edits: Vec<MultiEditEdit>) -> Self {
+        MultiEdit {
+            editing_symbol_index,
+            edits,
+            time: Instant::now(),
+        }
+    }
+    // assumes can_merge has been checked
+    fn merge(&mut self, other: MultiEdit) {
+        for edits in self.edits.iter_mut().zip(other.edits) {
+            if let (MultiEditEdit::EditPlacedSymbol(edit), MultiEditEdit::EditPlacedSymbol(other)) =
+                edits
+            {
+                edit.end = other.end;
+            }
+        }
+        // keep merging as long as the edits keep coming
+        self.time = other.time;
+    }
     fn edit(&mut self, target: &mut Movie) -> MoviePropertiesOutput {
         let placed_symbol_indexes = self
             .edits
@@ -309,6 +665,7 @@ impl MultiEdit {
             .map(|edit| match edit {
                 MultiEditEdit::EditPlacedSymbol(edit) => edit.edit(target),
                 MultiEditEdit::RemovePlacedSymbol(edit) => edit.edit(target),
+                MultiEditEdit::AddPlacedSymbol(edit) => edit.multi_edit(target),
             })
             .filter_map(|output| match output {
                 MultiEditOutput::PlacedSymbolProperties(placed_symbol_index) => {
@@ -327,6 +684,7 @@ impl MultiEdit {
             .map(|edit| match edit {
                 MultiEditEdit::EditPlacedSymbol(edit) => edit.undo(target),
                 MultiEditEdit::RemovePlacedSymbol(edit) => edit.undo(target),
+                MultiEditEdit::AddPlacedSymbol(edit) => edit.multi_undo(target),
             })
             .filter_map(|output| match output {
                 MultiEditOutput::PlacedSymbolProperties(placed_symbol_index) => {
@@ -384,3 +742,135 @@ impl RemovePlacedSymbolEdit {
         MultiEditOutput::PlacedSymbolProperties(self.placed_symbol_index)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use flits_core::{EditorTransform, Movie};
+    use undo::Record;
+
+    use super::*;
+
+    fn width_edit(movie: &Movie, width: f64) -> MovieEdit {
+        let mut after = movie.properties.clone();
+        after.width = width;
+        MovieEdit::EditMovieProperties(MoviePropertiesEdit {
+            before: movie.properties.clone(),
+            after,
+        })
+    }
+
+    #[test]
+    fn new_history_is_saved() {
+        let history: Record<MovieEdit> = Record::new();
+        assert!(history.is_saved());
+    }
+
+    #[test]
+    fn undo_past_saved_then_redo_back_to_saved() {
+        let mut movie = Movie::default();
+        let mut history: Record<MovieEdit> = Record::new();
+        history.edit(&mut movie, width_edit(&movie, 100.0));
+        history.edit(&mut movie, width_edit(&movie, 200.0));
+        history.set_saved(true);
+        assert!(history.is_saved());
+
+        history.undo(&mut movie);
+        assert!(!history.is_saved());
+        history.undo(&mut movie);
+        assert!(!history.is_saved());
+        assert_eq!(movie.properties.width, 640.0);
+
+        history.redo(&mut movie);
+        assert!(!history.is_saved());
+        history.redo(&mut movie);
+        assert!(history.is_saved());
+        assert_eq!(movie.properties.width, 200.0);
+    }
+
+    #[test]
+    fn edit_after_undoing_past_saved_is_unsaved() {
+        let mut movie = Movie::default();
+        let mut history: Record<MovieEdit> = Record::new();
+        history.edit(&mut movie, width_edit(&movie, 100.0));
+        history.set_saved(true);
+
+        history.undo(&mut movie);
+        history.edit(&mut movie, width_edit(&movie, 300.0));
+        assert!(!history.is_saved());
+
+        // the saved state was discarded, so undoing can't get back to it
+        history.undo(&mut movie);
+        assert!(!history.is_saved());
+    }
+
+    fn add_placed_symbol_edit(x: f64) -> MultiEditEdit {
+        MultiEditEdit::AddPlacedSymbol(AddPlacedSymbolEdit {
+            editing_symbol_index: None,
+            placed_symbol: PlaceSymbol::new(0)
+                .with_transform(EditorTransform::default().add_offset(x, 0.0)),
+            placed_symbol_index: None,
+        })
+    }
+
+    #[test]
+    fn multi_add_placed_symbols_is_a_single_undo_step() {
+        let mut movie = Movie::default();
+        let mut history: Record<MovieEdit> = Record::new();
+        history.edit(
+            &mut movie,
+            MovieEdit::Multi(MultiEdit::new(
+                None,
+                vec![add_placed_symbol_edit(0.0), add_placed_symbol_edit(100.0)],
+            )),
+        );
+        assert_eq!(movie.root.len(), 2);
+        assert_eq!(movie.root[1].transform.x, 100.0);
+
+        history.undo(&mut movie);
+        assert!(movie.root.is_empty());
+
+        history.redo(&mut movie);
+        assert_eq!(movie.root.len(), 2);
+    }
+
+    fn remove_symbol_edit(movie: &Movie, symbol_index: SymbolIndex) -> MovieEdit {
+        MovieEdit::RemoveSymbol(RemoveSymbolEdit {
+            symbol_index,
+            symbol: movie.symbols[symbol_index].clone_without_cache(),
+            remove_place_symbol_edits: vec![],
+            fallback_spans: vec![],
+            removed_sound_actions: vec![],
+            static_texts_without_font: vec![],
+            removed_preloader_graphic: false,
+        })
+    }
+
+    #[test]
+    fn removing_symbols_keeps_the_preloader_graphic() {
+        let mut movie = Movie::default();
+        movie
+            .symbols
+            .push(Symbol::MovieClip(MovieClip::new("first")));
+        movie
+            .symbols
+            .push(Symbol::MovieClip(MovieClip::new("graphic")));
+        movie.properties.preloader = PreloaderType::CustomGraphic { movieclip_index: 1 };
+        let mut history: Record<MovieEdit> = Record::new();
+
+        history.edit(&mut movie, remove_symbol_edit(&movie, 0));
+        assert_eq!(
+            movie.properties.preloader,
+            PreloaderType::CustomGraphic { movieclip_index: 0 }
+        );
+
+        history.edit(&mut movie, remove_symbol_edit(&movie, 0));
+        assert_eq!(movie.properties.preloader, PreloaderType::StartAfterLoading);
+
+        history.undo(&mut movie);
+        history.undo(&mut movie);
+        assert_eq!(
+            movie.properties.preloader,
+            PreloaderType::CustomGraphic { movieclip_index: 1 }
+        );
+    }
+}
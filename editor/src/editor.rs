@@ -1,45 +1,89 @@
-use std::{any::Any, path::PathBuf};
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
 
-use flits_core::{run::run_movie, Movie, PlacedSymbolIndex, Symbol, SymbolIndexOrRoot};
+use flits_core::{
+    run::run_movie, BitmapCacheStatus, ExportOptions, ExportResult, Movie, PlacedSymbolIndex,
+    ProjectIssue, Symbol, SymbolIndex, SymbolIndexOrRoot,
+};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use ruffle_render::backend::{RenderBackend, ViewportDimensions};
 use tracing::instrument;
-use undo::Record;
 use winit::{
-    event::{ElementState, MouseButton},
+    event::{ElementState, KeyEvent, MouseButton, MouseScrollDelta},
     event_loop::EventLoopProxy,
+    keyboard::{Key, NamedKey},
 };
 
 use crate::{
     edit::{MovieEdit, MoviePropertiesOutput, MultiEdit, MultiEditEdit, RemovePlacedSymbolEdit},
     editor::{
+        add_font_window::{AddFontWindow, AddFontWindowResult},
+        batch_rename_window::{BatchRenameWindow, BatchRenameWindowResult},
         breadcrumb_bar::BreadcrumbBar,
+        dependency_tree::DependencyTree,
+        editor_settings::EditorSettings,
         error_window::{ErrorWindow, ErrorWindowTrait},
+        export_options_window::{ExportOptionsWindow, ExportOptionsWindowResult},
+        export_window::{AfterExport, ExportWindow},
         library::Library,
         menu_bar::MenuBar,
         new_symbol_window::{NewSymbolWindow, NewSymbolWindowResult},
+        performance_overlay::PerformanceOverlay,
+        project_issues_window::{ProjectIssuesWindow, ProjectIssuesWindowResult},
         properties_panel::{MoviePropertiesPanel, PropertiesPanel},
-        run_ui::RunUi,
-        stage::Stage,
+        run_ui::{RunUi, RunUiAction},
+        scripts_window::{ScriptsWindow, ScriptsWindowResult},
+        shortcuts_window::{ShortcutsWindow, ShortcutsWindowResult},
+        stage::{camera::Camera, Stage},
+        warning_window::{WarningWindow, WarningWindowTrait},
     },
+    history::MovieHistory,
     message::EditorMessage,
     message_bus::MessageBus,
     FlitsEvent,
 };
 
+mod add_font_window;
+mod batch_rename_window;
 mod breadcrumb_bar;
+mod character_picker_window;
+mod dependency_tree;
+mod editor_settings;
 mod error_window;
+mod export_options_window;
+mod export_window;
 mod library;
 mod menu_bar;
 mod new_symbol_window;
+mod performance_overlay;
+mod project_issues_window;
 mod properties_panel;
 mod run_ui;
+mod scripts_window;
+mod shortcuts_window;
 pub(crate) mod stage;
+mod warning_window;
 
 pub const MENU_HEIGHT: u32 = 44;
 const LIBRARY_WIDTH: u32 = 150;
+// the height doesn't depend on the content so the panel doesn't change size when the selection changes
+const PROPERTIES_PANEL_HEIGHT: f32 = 120.0;
+const PROPERTIES_PANEL_MIN_HEIGHT: f32 = 60.0;
+// only shows the heading
+const PROPERTIES_PANEL_COLLAPSED_HEIGHT: f32 = 32.0;
 pub const EDIT_EPSILON: f64 = 0.00001;
 const EMPTY_CLIP_WIDTH: f64 = 16.0;
 const EMPTY_CLIP_HEIGHT: f64 = 16.0;
+// paint programs can write a file multiple times when saving, only reload when the writes stop
+const ASSET_CHANGE_DEBOUNCE: Duration = Duration::from_millis(200);
+
+// shared by all editors, so every run in the application has a different id
+static NEXT_RUN_ID: AtomicU64 = AtomicU64::new(0);
 
 type Renderer = Box<dyn RenderBackend>;
 struct BitmapHandleWrapper(ruffle_render::bitmap::BitmapHandle);
@@ -54,6 +98,7 @@ pub enum NeedsRedraw {
     No,
 }
 
+#[derive(Clone, Copy)]
 pub struct StageSize {
     pub width: u32,
     pub height: u32,
@@ -94,20 +139,43 @@ pub struct Editor {
     viewport_dimensions: ViewportDimensions,
     event_loop: EventLoopProxy<FlitsEvent>,
     needs_redraw: bool,
+    // stops watching when dropped
+    _asset_watcher: Option<RecommendedWatcher>,
 
     selection: Selection,
-    history: Record<MovieEdit>,
+    history: MovieHistory,
     modifiers: egui::Modifiers,
 
     run_ui: Option<RunUi>,
     menu_bar: MenuBar,
     library: Library,
+    library_width: f32, // in egui points
+    // the width the library starts with, from the editor settings
+    library_default_width: f32,
+    dependency_tree: DependencyTree,
     breadcrumb_bar: BreadcrumbBar,
     stage: Stage,
     properties_panel: PropertiesPanel,
+    properties_panel_height: f32,
+    is_properties_panel_collapsed: bool,
     new_symbol_window: Option<NewSymbolWindow>,
+    add_font_window: Option<AddFontWindow>,
+    export_options_window: Option<ExportOptionsWindow>,
+    batch_rename_window: Option<BatchRenameWindow>,
+    scripts_window: Option<ScriptsWindow>,
+    shortcuts_window: Option<ShortcutsWindow>,
+    project_issues_window: Option<ProjectIssuesWindow>,
+    export_window: Option<ExportWindow>,
+    // to show which symbols changed since the last export
+    last_export_hashes: HashMap<SymbolIndex, u64>,
+    // where the camera was when you last left the scene or a movieclip
+    camera_by_scope: HashMap<SymbolIndexOrRoot, Camera>,
+    // the scenes and movieclips you were editing before the current one, escape goes back to the last one
+    parent_scopes: Vec<SymbolIndexOrRoot>,
+    performance_overlay: Option<PerformanceOverlay>,
 
     error: Option<ErrorWindow>,
+    warnings: Option<WarningWindow>,
 }
 impl Editor {
     pub fn new(
@@ -127,8 +195,27 @@ impl Editor {
             PathBuf::from(project_file_path.parent().unwrap())
         };
 
-        let movie = Movie::load(project_file_path.clone())?;
+        let (movie, issues) = Movie::load_with_issues(project_file_path.clone())?;
+        let mut history = MovieHistory::new();
+        // the changed paths are only kept when the project is saved
+        if issues
+            .iter()
+            .any(|issue| matches!(issue, ProjectIssue::MovedBitmap { .. }))
+        {
+            history.set_changed_without_edit();
+        }
         let movie_properties = movie.properties.clone();
+        let project_issues_window = ProjectIssuesWindow::new(issues);
+        // the editor still works without live reloading, so only warn about it
+        let asset_watcher = Self::watch_assets(&directory, event_loop.clone())
+            .inspect_err(|err| eprintln!("Unable to watch the assets directory: {}", err))
+            .ok();
+        let mut stage = Stage::new(&movie_properties, directory.clone());
+        let editor_settings = EditorSettings::load(&directory);
+        // continue where the camera was when the project was closed
+        if let Some(camera) = editor_settings.camera {
+            stage.set_camera(camera);
+        }
         Ok(Editor {
             movie,
             project_file_path,
@@ -137,32 +224,102 @@ impl Editor {
             viewport_dimensions,
             event_loop,
             needs_redraw: false,
+            _asset_watcher: asset_watcher,
 
             selection: Selection::default(),
-            history: Record::new(),
+            history,
             modifiers: egui::Modifiers::NONE,
 
             run_ui: None,
             menu_bar: MenuBar::default(),
             library: Library::default(),
+            library_width: 0.0, // set after the library is shown the first time
+            library_default_width: editor_settings
+                .library_width
+                .unwrap_or(LIBRARY_WIDTH as f32),
+            dependency_tree: DependencyTree::default(),
             breadcrumb_bar: BreadcrumbBar::default(),
-            stage: Stage::new(&movie_properties, directory),
+            stage,
             properties_panel: PropertiesPanel::MovieProperties(MoviePropertiesPanel {
                 before_edit: movie_properties,
             }),
+            properties_panel_height: PROPERTIES_PANEL_HEIGHT,
+            is_properties_panel_collapsed: false,
             new_symbol_window: None,
+            add_font_window: None,
+            export_options_window: None,
+            batch_rename_window: None,
+            scripts_window: None,
+            shortcuts_window: None,
+            project_issues_window,
+            export_window: None,
+            last_export_hashes: HashMap::new(),
+            camera_by_scope: HashMap::new(),
+            parent_scopes: Vec::new(),
+            performance_overlay: None,
 
             error: None,
+            warnings: None,
         })
     }
 
+    fn watch_assets(
+        directory: &Path,
+        event_loop: EventLoopProxy<FlitsEvent>,
+    ) -> Result<RecommendedWatcher, notify::Error> {
+        let (path_sender, path_receiver) = std::sync::mpsc::channel::<PathBuf>();
+        // the thread stops when the watcher is dropped, because that closes the channel
+        std::thread::spawn(move || {
+            while let Ok(path) = path_receiver.recv() {
+                let mut changed_paths = HashSet::from([path]);
+                while let Ok(path) = path_receiver.recv_timeout(ASSET_CHANGE_DEBOUNCE) {
+                    changed_paths.insert(path);
+                }
+                for path in changed_paths {
+                    event_loop
+                        .send_event(FlitsEvent::AssetChanged(path))
+                        .unwrap_or_else(|err| {
+                            eprintln!("Unable to send asset changed event: {}", err);
+                        });
+                }
+            }
+        });
+        let mut watcher =
+            notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+                let Ok(event) = result else {
+                    return;
+                };
+                if !event.kind.is_create() && !event.kind.is_modify() {
+                    return;
+                }
+                for path in event.paths {
+                    let is_asset = path.extension().is_some_and(|extension| {
+                        extension == "png" || extension == "ttf" || extension == "otf"
+                    });
+                    if is_asset {
+                        _ = path_sender.send(path);
+                    }
+                }
+            })?;
+        // assets can be in subdirectories of the assets directory
+        watcher.watch(&directory.join("assets"), RecursiveMode::Recursive)?;
+        Ok(watcher)
+    }
+
     pub fn do_ui(
         &mut self,
         egui_ctx: &egui::Context,
         event_loop: &EventLoopProxy<FlitsEvent>,
     ) -> NeedsRedraw {
         if let Some(run_ui) = &mut self.run_ui {
-            run_ui.do_ui(egui_ctx);
+            match run_ui.do_ui(egui_ctx) {
+                RunUiAction::None => {}
+                RunUiAction::Restart => {
+                    run_ui.stop();
+                    self.run();
+                }
+                RunUiAction::Close => self.run_ui = None,
+            }
         }
         // don't show the editor ui when you have selected a different tab in the run ui
         if !self.is_editor_visible() {
@@ -180,33 +337,81 @@ impl Editor {
         };
 
         egui::TopBottomPanel::top("menu_bar").show(egui_ctx, |ui| {
-            self.menu_bar.do_ui(ui, &context);
+            self.menu_bar
+                .do_ui(ui, &context, self.export_window.is_some());
         });
 
-        egui::SidePanel::right("library")
-            .resizable(false) // resizing causes glitches
+        let library_response = egui::SidePanel::right("library")
+            .resizable(true)
             .min_width(LIBRARY_WIDTH as f32)
+            .default_width(self.library_default_width)
             .show(egui_ctx, |ui| {
+                egui::TopBottomPanel::bottom("dependency_tree").show_inside(ui, |ui| {
+                    self.dependency_tree.do_ui(ui, &context);
+                });
                 self.library.do_ui(ui, &context);
             });
+        // the stage needs the actual width, otherwise it is off while resizing
+        let library_width = library_response.response.rect.width();
+        if library_width != self.library_width {
+            self.library_width = library_width;
+            self.stage.set_library_width(
+                (library_width as f64 * self.viewport_dimensions.scale_factor) as u32,
+            );
+        }
 
         egui::TopBottomPanel::top("breadcrumb_bar").show(egui_ctx, |ui| {
             self.breadcrumb_bar.do_ui(ui, &context);
         });
 
-        egui::TopBottomPanel::bottom("properties").show(egui_ctx, |ui| {
-            let mut mutable_context = MutableContext {
-                movie: &mut self.movie,
-                selection: &self.selection,
-                modifiers: self.modifiers,
-                message_bus: &message_bus,
-                viewport_dimensions: self.viewport_dimensions,
-            };
-            self.properties_panel.do_ui(ui, &mut mutable_context);
+        // seperate ids so the expanded panel remembers its height while collapsed
+        let properties_panel = if self.is_properties_panel_collapsed {
+            egui::TopBottomPanel::bottom("properties_collapsed")
+                .resizable(false)
+                .exact_height(PROPERTIES_PANEL_COLLAPSED_HEIGHT)
+        } else {
+            egui::TopBottomPanel::bottom("properties")
+                .resizable(true)
+                .min_height(PROPERTIES_PANEL_MIN_HEIGHT)
+                .default_height(self.properties_panel_height)
+        };
+        let properties_response = properties_panel.show(egui_ctx, |ui| {
+            ui.horizontal_top(|ui| {
+                let caret = if self.is_properties_panel_collapsed {
+                    "⏶"
+                } else {
+                    "⏷"
+                };
+                if ui.small_button(caret).clicked() {
+                    self.is_properties_panel_collapsed = !self.is_properties_panel_collapsed;
+                }
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        let mut mutable_context = MutableContext {
+                            movie: &mut self.movie,
+                            selection: &self.selection,
+                            modifiers: self.modifiers,
+                            message_bus: &message_bus,
+                            viewport_dimensions: self.viewport_dimensions,
+                        };
+                        self.properties_panel
+                            .do_ui(ui, &mut mutable_context, &self.directory);
+                    });
+            });
         });
+        let properties_panel_height = properties_response.response.rect.height();
+        if !self.is_properties_panel_collapsed {
+            self.properties_panel_height = properties_panel_height;
+        }
+        self.stage.set_properties_panel_height(
+            (properties_panel_height as f64 * self.viewport_dimensions.scale_factor) as u32,
+        );
+        // what is left after the panels is the stage
+        self.stage.set_screen_rect(egui_ctx.available_rect());
 
         if let Some(new_symbol_window) = &mut self.new_symbol_window {
-            match new_symbol_window.do_ui(egui_ctx) {
+            match new_symbol_window.do_ui(egui_ctx, &self.movie) {
                 NewSymbolWindowResult::Confirm(movie_edit) => {
                     self.handle_message(EditorMessage::Edit(movie_edit));
                     self.new_symbol_window = None;
@@ -217,9 +422,90 @@ impl Editor {
                 NewSymbolWindowResult::NoAction => {}
             }
         }
+        if let Some(add_font_window) = &mut self.add_font_window {
+            match add_font_window.do_ui(egui_ctx, &self.directory) {
+                AddFontWindowResult::Confirm(movie_edit) => {
+                    self.handle_message(EditorMessage::Edit(movie_edit));
+                    self.add_font_window = None;
+                }
+                AddFontWindowResult::Cancel => {
+                    self.add_font_window = None;
+                }
+                AddFontWindowResult::NoAction => {}
+            }
+        }
+        if let Some(export_options_window) = &mut self.export_options_window {
+            match export_options_window.do_ui(egui_ctx) {
+                ExportOptionsWindowResult::Export(options) => {
+                    self.export_options_window = None;
+                    self.start_export(options, AfterExport::Nothing);
+                }
+                ExportOptionsWindowResult::Cancel => {
+                    self.export_options_window = None;
+                }
+                ExportOptionsWindowResult::NoAction => {}
+            }
+        }
+        if let Some(project_issues_window) = &mut self.project_issues_window {
+            match project_issues_window.do_ui(egui_ctx) {
+                ProjectIssuesWindowResult::Continue => {
+                    self.project_issues_window = None;
+                }
+                ProjectIssuesWindowResult::GoTo(symbol_index) => {
+                    self.handle_message(EditorMessage::ChangeSelectedSymbol(symbol_index));
+                    self.project_issues_window = None;
+                }
+                ProjectIssuesWindowResult::NoAction => {}
+            }
+        }
+        if let Some(batch_rename_window) = &mut self.batch_rename_window {
+            match batch_rename_window.do_ui(egui_ctx, &self.movie) {
+                BatchRenameWindowResult::Confirm(movie_edit) => {
+                    self.handle_message(EditorMessage::Edit(movie_edit));
+                    self.batch_rename_window = None;
+                }
+                BatchRenameWindowResult::Cancel => {
+                    self.batch_rename_window = None;
+                }
+                BatchRenameWindowResult::NoAction => {}
+            }
+        }
+        if let Some(scripts_window) = &mut self.scripts_window {
+            match scripts_window.do_ui(egui_ctx, &self.movie) {
+                ScriptsWindowResult::Edit(movie_edit) => {
+                    self.handle_message(EditorMessage::Edit(movie_edit));
+                }
+                ScriptsWindowResult::Close => {
+                    self.scripts_window = None;
+                }
+                ScriptsWindowResult::NoAction => {}
+            }
+        }
+        if let Some(shortcuts_window) = &mut self.shortcuts_window {
+            match shortcuts_window.do_ui(egui_ctx) {
+                ShortcutsWindowResult::Close => {
+                    self.shortcuts_window = None;
+                }
+                ShortcutsWindowResult::NoAction => {}
+            }
+        }
 
+        if let Some(export_window) = &self.export_window {
+            export_window.do_ui(egui_ctx);
+        }
+        if let Some(performance_overlay) = &self.performance_overlay {
+            performance_overlay.do_ui(egui_ctx);
+        }
+        self.warnings.do_ui(egui_ctx);
         self.error.do_ui(egui_ctx);
 
+        // the stage isn't part of the egui ui, so only change the cursor when egui doesn't use it
+        if !egui_ctx.wants_pointer_input() {
+            if let Some(cursor_icon) = self.stage.cursor_icon() {
+                egui_ctx.set_cursor_icon(cursor_icon);
+            }
+        }
+
         self.needs_redraw = false;
         self.handle_messages(message_bus);
 
@@ -246,46 +532,36 @@ impl Editor {
 
         match message {
             EditorMessage::Save => {
-                self.movie.save(&self.project_file_path);
-                self.history.set_saved(true);
-                self.update_title();
+                self.save();
             }
             EditorMessage::Export => {
-                // we don't care about the result here, export_swf sets self.error
-                _ = self.export_swf();
+                self.start_export(ExportOptions::default(), AfterExport::Nothing);
+            }
+            EditorMessage::OpenExportOptionsWindow => {
+                self.export_options_window = Some(ExportOptionsWindow::default());
+            }
+            EditorMessage::ExportArchive => {
+                self.start_export(ExportOptions::default(), AfterExport::CreateArchive);
             }
             EditorMessage::Run => {
                 // only run the movie if the export is successful
-                if self.export_swf().is_ok() {
-                    self.run_ui = Some(RunUi::new());
-                    let result = run_movie(
-                        &self.directory.join("output.swf"),
-                        self.event_loop.clone(),
-                        |line, event_loop| {
-                            // TODO: debounce events
-                            event_loop
-                                .send_event(FlitsEvent::CommandOutput(line))
-                                .unwrap_or_else(|err| {
-                                    eprintln!("Unable to send command output event: {}", err);
-                                });
-                        },
-                        |event_loop| {
-                            event_loop
-                                .send_event(FlitsEvent::RuffleClosed)
-                                .unwrap_or_else(|err| {
-                                    eprintln!("Unable to send command output event: {}", err);
-                                });
-                        },
-                    );
-                    self.error = match &result {
-                        Ok(_) => None,
-                        Err(err) => ErrorWindow::new(err.to_string()),
-                    };
-                }
+                self.start_export(ExportOptions::default(), AfterExport::Run);
             }
             EditorMessage::OpenNewSymbolWindow => {
                 self.new_symbol_window = Some(NewSymbolWindow::default());
             }
+            EditorMessage::OpenAddFontWindow => {
+                self.add_font_window = Some(AddFontWindow::default());
+            }
+            EditorMessage::OpenScriptsWindow => {
+                self.scripts_window = Some(ScriptsWindow::new(&self.movie));
+            }
+            EditorMessage::OpenShortcutsWindow => {
+                self.shortcuts_window = Some(ShortcutsWindow::default());
+            }
+            EditorMessage::OpenBatchRenameWindow => {
+                self.batch_rename_window = Some(BatchRenameWindow::default());
+            }
             EditorMessage::ChangeSelectedSymbol(symbol_index) => 'change_selected_symbol: {
                 if symbol_index == self.selection.stage_symbol_index {
                     break 'change_selected_symbol;
@@ -295,17 +571,35 @@ impl Editor {
                     Symbol::MovieClip(_movie_clip) => true,
                     _ => false,
                 }) {
+                    self.camera_by_scope.insert(
+                        self.selection.stage_symbol_index,
+                        self.stage.camera().clone(),
+                    );
+                    // going back to a scope you came from forgets the scopes after it
+                    match self
+                        .parent_scopes
+                        .iter()
+                        .position(|scope| *scope == symbol_index)
+                    {
+                        Some(position) => self.parent_scopes.truncate(position),
+                        None if symbol_index.is_none() => self.parent_scopes.clear(),
+                        None => self.parent_scopes.push(self.selection.stage_symbol_index),
+                    }
                     self.selection.stage_symbol_index = symbol_index;
 
-                    let message_bus = MessageBus::new();
-                    self.stage.reset_camera(Context {
-                        movie: &self.movie,
-                        selection: &self.selection,
-                        modifiers: self.modifiers,
-                        message_bus: &message_bus,
-                        viewport_dimensions: self.viewport_dimensions,
-                    });
-                    self.handle_messages(message_bus);
+                    if let Some(camera) = self.camera_by_scope.remove(&symbol_index) {
+                        self.stage.set_camera(camera);
+                    } else {
+                        let message_bus = MessageBus::new();
+                        self.stage.reset_camera(Context {
+                            movie: &self.movie,
+                            selection: &self.selection,
+                            modifiers: self.modifiers,
+                            message_bus: &message_bus,
+                            viewport_dimensions: self.viewport_dimensions,
+                        });
+                        self.handle_messages(message_bus);
+                    }
 
                     self.handle_message(EditorMessage::ChangeSelectedPlacedSymbols(Vec::new()));
                 }
@@ -316,6 +610,18 @@ impl Editor {
                 self.selection.placed_symbols = Vec::new();
                 self.properties_panel.update(&self.movie, &self.selection);
             }
+            EditorMessage::OpenSymbolFile(symbol_index) => {
+                let path = match &self.movie.symbols[symbol_index] {
+                    Symbol::Bitmap(bitmap) => &bitmap.properties.path,
+                    Symbol::Font(font) => &font.path,
+                    Symbol::Sound(sound) => &sound.path,
+                    Symbol::MovieClip(_) | Symbol::StaticText(_) => return,
+                };
+                // open the file in the default program of the os
+                if let Err(err) = open::that(self.directory.join(path)) {
+                    self.error = ErrorWindow::new(format!("Unable to open {}: {}", path, err));
+                }
+            }
             EditorMessage::ChangeSelectedPlacedSymbols(items) => {
                 self.selection.placed_symbols = items;
                 self.selection.properties_symbol_index = self.selection.stage_symbol_index;
@@ -355,27 +661,45 @@ impl Editor {
                             .clone(),
                     }));
                 }
-                self.handle_message(EditorMessage::Edit(MovieEdit::Multi(MultiEdit {
-                    editing_symbol_index: self.selection.stage_symbol_index,
+                self.handle_message(EditorMessage::Edit(MovieEdit::Multi(MultiEdit::new(
+                    self.selection.stage_symbol_index,
                     edits,
-                })));
+                ))));
             }
             EditorMessage::ReloadAssets => {
-                self.movie.reload_assets(&self.directory);
+                let issues = self.movie.reload_assets(&self.directory);
                 // reset text renderer to force it to reload everything
                 self.stage.reset_text_renderer();
+                // the changed paths of the bitmaps that were moved need to be saved
+                if issues
+                    .iter()
+                    .any(|issue| matches!(issue, ProjectIssue::MovedBitmap { .. }))
+                {
+                    self.history.set_changed_without_edit();
+                    self.update_title();
+                }
+                if !issues.is_empty() {
+                    self.project_issues_window = ProjectIssuesWindow::new(issues);
+                }
             }
             EditorMessage::Edit(edit) => {
+                let symbol_count = self.movie.symbols.len();
                 let result = self.history.edit(&mut self.movie, edit);
-                self.update_after_edit(Some(result));
+                self.update_after_edit(symbol_count, Some(result));
             }
             EditorMessage::Undo => {
-                let result = self.history.undo(&mut self.movie);
-                self.update_after_edit(result);
+                let symbol_count = self.movie.symbols.len();
+                let result = self
+                    .history
+                    .undo(&mut self.movie, self.selection.stage_symbol_index);
+                self.update_after_edit(symbol_count, result);
             }
             EditorMessage::Redo => {
-                let result = self.history.redo(&mut self.movie);
-                self.update_after_edit(result);
+                let symbol_count = self.movie.symbols.len();
+                let result = self
+                    .history
+                    .redo(&mut self.movie, self.selection.stage_symbol_index);
+                self.update_after_edit(symbol_count, result);
             }
             EditorMessage::Stage(stage_message) => {
                 let message_bus = MessageBus::new();
@@ -400,7 +724,21 @@ impl Editor {
             }
         }
     }
-    fn update_after_edit(&mut self, result: Option<MoviePropertiesOutput>) {
+    // symbol_count is the number of symbols before the edit
+    fn update_after_edit(&mut self, symbol_count: usize, result: Option<MoviePropertiesOutput>) {
+        if self.movie.symbols.len() != symbol_count {
+            // the fonts of the text renderer are indexed by symbol, so they have to be loaded again
+            self.stage.reset_text_renderer();
+            // the selected symbols can be at a different index now
+            self.library.clear_selection();
+            // and so can the clips the cameras were saved for, root is the only one that can't move
+            self.camera_by_scope.retain(|scope, _| scope.is_none());
+            self.parent_scopes.clear();
+            // and the symbols the export hashes are for
+            self.last_export_hashes.clear();
+        } else {
+            self.stage.remove_unused_edit_texts(&self.movie);
+        }
         if let Some(result) = result {
             match result {
                 MoviePropertiesOutput::Stage(editing_clip) => {
@@ -444,7 +782,31 @@ impl Editor {
             renderer,
         };
 
-        self.stage.render(&mut context);
+        let render_start = Instant::now();
+        let stats = self.stage.render(&mut context);
+        if let Some(performance_overlay) = &mut self.performance_overlay {
+            let bitmap_count = self
+                .movie
+                .symbols
+                .iter()
+                .filter(|symbol| {
+                    matches!(
+                        symbol,
+                        Symbol::Bitmap(flits_core::Bitmap {
+                            cache: BitmapCacheStatus::Cached(flits_core::CachedBitmap {
+                                bitmap_handle: Some(_),
+                                ..
+                            }),
+                            ..
+                        })
+                    )
+                })
+                .count();
+            performance_overlay.record_frame(render_start, stats, bitmap_count);
+        }
+    }
+    pub fn show_performance_overlay(&mut self) {
+        self.performance_overlay = Some(PerformanceOverlay::default());
     }
 
     pub fn handle_mouse_move(&mut self, mouse_x: f64, mouse_y: f64) {
@@ -463,6 +825,22 @@ impl Editor {
             .handle_mouse_move(&mut mutable_context, mouse_x, mouse_y);
         self.handle_messages(message_bus);
     }
+    pub fn handle_mouse_wheel(&mut self, mouse_x: f64, mouse_y: f64, delta: MouseScrollDelta) {
+        if !self.is_editor_visible() {
+            return;
+        }
+        let message_bus = MessageBus::new();
+        let mut mutable_context = MutableContext {
+            movie: &mut self.movie,
+            selection: &mut self.selection,
+            modifiers: self.modifiers,
+            message_bus: &message_bus,
+            viewport_dimensions: self.viewport_dimensions,
+        };
+        self.stage
+            .handle_mouse_wheel(&mut mutable_context, mouse_x, mouse_y, delta);
+        self.handle_messages(message_bus);
+    }
     pub fn handle_mouse_input(
         &mut self,
         mouse_x: f64,
@@ -486,6 +864,63 @@ impl Editor {
         self.handle_messages(message_bus);
     }
 
+    pub fn handle_key_input(&mut self, event: KeyEvent) {
+        if !self.is_editor_visible() || event.state != ElementState::Pressed {
+            return;
+        }
+        match event.logical_key {
+            Key::Named(NamedKey::Escape) => {
+                // go back to the movieclip you were editing before this one,
+                // or deselect everything when you are already in the root
+                if self.selection.stage_symbol_index.is_some() {
+                    let parent_scope = self.parent_scopes.last().copied().flatten();
+                    self.handle_message(EditorMessage::ChangeSelectedSymbol(parent_scope));
+                } else {
+                    self.handle_message(EditorMessage::ChangeSelectedPlacedSymbols(Vec::new()));
+                }
+            }
+            Key::Named(NamedKey::Tab) => {
+                if self.modifiers.shift {
+                    self.select_previous();
+                } else {
+                    self.select_next();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn select_next(&mut self) {
+        let placed_symbol_count = self
+            .movie
+            .get_placed_symbols(self.selection.stage_symbol_index)
+            .len();
+        if placed_symbol_count == 0 {
+            return;
+        }
+        let next = match self.selection.placed_symbols.last() {
+            Some(placed_symbol_index) => (placed_symbol_index + 1) % placed_symbol_count,
+            None => 0,
+        };
+        self.handle_message(EditorMessage::ChangeSelectedPlacedSymbols(vec![next]));
+    }
+    fn select_previous(&mut self) {
+        let placed_symbol_count = self
+            .movie
+            .get_placed_symbols(self.selection.stage_symbol_index)
+            .len();
+        if placed_symbol_count == 0 {
+            return;
+        }
+        let previous = match self.selection.placed_symbols.last() {
+            Some(placed_symbol_index) => {
+                (placed_symbol_index + placed_symbol_count - 1) % placed_symbol_count
+            }
+            None => placed_symbol_count - 1,
+        };
+        self.handle_message(EditorMessage::ChangeSelectedPlacedSymbols(vec![previous]));
+    }
+
     fn handle_messages(&mut self, message_bus: MessageBus<EditorMessage>) {
         for message in message_bus.into_vec() {
             self.handle_message(message);
@@ -499,27 +934,184 @@ impl Editor {
         true
     }
 
-    fn export_swf(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        let directory = self.directory.clone();
-        let swf_path = directory.clone().join("output.swf");
-        let result = self.movie.export(directory, swf_path);
-        self.error = match &result {
+    fn start_export(&mut self, options: ExportOptions, after_export: AfterExport) {
+        // the menu items are disabled while exporting, but shortcuts can still get here
+        if self.export_window.is_some() {
+            return;
+        }
+        match ExportWindow::start(
+            &self.movie,
+            self.directory.clone(),
+            options,
+            after_export,
+            &self.last_export_hashes,
+            self.event_loop.clone(),
+        ) {
+            Ok(export_window) => self.export_window = Some(export_window),
+            Err(err) => self.error = ErrorWindow::new(err.to_string()),
+        }
+    }
+    pub fn on_export_progress(&mut self, export_id: u64, progress: f32) -> NeedsRedraw {
+        let Some(export_window) = &mut self.export_window else {
+            return NeedsRedraw::No;
+        };
+        // the event is from an export in another editor
+        if export_window.export_id() != export_id {
+            return NeedsRedraw::No;
+        }
+        let Some(result) = export_window.update_progress(progress) else {
+            return NeedsRedraw::Yes;
+        };
+        let export_window = self.export_window.take().unwrap();
+        let symbol_changes = export_window.symbol_changes.unwrap_or_default();
+        self.show_export_result(&result, symbol_changes.changed_symbols);
+        if result.is_ok() {
+            self.last_export_hashes = symbol_changes.symbol_hashes;
+            match export_window.after_export {
+                AfterExport::Nothing => {}
+                AfterExport::Run => self.run(),
+                AfterExport::CreateArchive => {
+                    if let Err(err) = self.movie.export_archive(
+                        &self.directory,
+                        &self.directory.join("output.swf"),
+                        &self.directory.join("output.zip"),
+                    ) {
+                        self.error = ErrorWindow::new(format!("Unable to create archive: {}", err));
+                    }
+                }
+            }
+        }
+        NeedsRedraw::Yes
+    }
+    fn run(&mut self) {
+        // every run gets an id because all editors receive the events of all runs
+        let run_id = NEXT_RUN_ID.fetch_add(1, Ordering::Relaxed);
+        let result = run_movie(
+            &self.directory.join("output.swf"),
+            (self.event_loop.clone(), run_id),
+            |line, (event_loop, run_id)| {
+                // TODO: debounce events
+                event_loop
+                    .send_event(FlitsEvent::CommandOutput(run_id, line))
+                    .unwrap_or_else(|err| {
+                        eprintln!("Unable to send command output event: {}", err);
+                    });
+            },
+            |exit_code, (event_loop, run_id)| {
+                event_loop
+                    .send_event(FlitsEvent::RuffleClosed(run_id, exit_code))
+                    .unwrap_or_else(|err| {
+                        eprintln!("Unable to send Ruffle closed event: {}", err);
+                    });
+            },
+        );
+        match result {
+            Ok(running_movie) => {
+                self.error = None;
+                match &mut self.run_ui {
+                    Some(run_ui) => run_ui.restart(run_id, running_movie),
+                    None => self.run_ui = Some(RunUi::new(run_id, running_movie)),
+                }
+            }
+            Err(err) => self.error = ErrorWindow::new(err.to_string()),
+        }
+    }
+    fn show_export_result(
+        &mut self,
+        result: &Result<ExportResult, String>,
+        changed_symbols: Vec<String>,
+    ) {
+        self.error = match result {
             Ok(_) => None,
-            Err(err) => ErrorWindow::new(err.to_string()),
+            Err(err) => ErrorWindow::new(err.clone()),
+        };
+        self.warnings = match result {
+            Ok(export_result) => WarningWindow::new(
+                export_result
+                    .warnings
+                    .iter()
+                    .cloned()
+                    .chain(
+                        export_result
+                            .compatibility_warnings
+                            .iter()
+                            .map(|warning| warning.to_string()),
+                    )
+                    .collect(),
+                export_result.pruned_symbols.clone(),
+                changed_symbols,
+            ),
+            Err(_) => None,
         };
-        result
     }
-    pub fn receive_command_output(&mut self, line: String) -> NeedsRedraw {
+    // returns false if saving failed, the error is shown in the error window
+    pub fn save(&mut self) -> bool {
+        if let Err(err) = self.movie.save(&self.project_file_path) {
+            self.error = ErrorWindow::new(format!(
+                "Unable to save {}: {}",
+                self.project_file_path.display(),
+                err
+            ));
+            return false;
+        }
+        self.history.set_saved();
+        self.update_title();
+        self.save_editor_settings();
+        true
+    }
+    // failing to save the settings isn't worth bothering the user with
+    pub fn save_editor_settings(&self) {
+        let settings = EditorSettings {
+            camera: self.scene_camera().cloned(),
+            library_width: (self.library_width > 0.0).then_some(self.library_width),
+        };
+        if let Err(err) = settings.save(&self.directory) {
+            eprintln!("Unable to save the editor settings: {}", err);
+        }
+    }
+    fn scene_camera(&self) -> Option<&Camera> {
+        match self.selection.stage_symbol_index {
+            None => Some(self.stage.camera()),
+            // the scene camera is stored while a movieclip is being edited
+            Some(_) => self.camera_by_scope.get(&None),
+        }
+    }
+    pub fn is_running(&self, run_id: u64) -> bool {
+        self.run_ui
+            .as_ref()
+            .is_some_and(|run_ui| run_ui.run_id() == run_id)
+    }
+    pub fn receive_command_output(&mut self, run_id: u64, line: String) -> NeedsRedraw {
         if let Some(run_ui) = &mut self.run_ui {
-            run_ui.add_line(line);
-            if run_ui.needs_redraw_after_new_line() {
+            if run_ui.on_command_output(run_id, line) && run_ui.needs_redraw_after_new_line() {
                 return NeedsRedraw::Yes;
             }
         }
         NeedsRedraw::No
     }
-    pub fn on_ruffle_closed(&mut self) {
-        self.run_ui = None;
+    pub fn on_asset_changed(&mut self, path: &Path) -> NeedsRedraw {
+        // the watcher reports absolute paths, but the project directory can be relative
+        let assets_directory = self.directory.join("assets").canonicalize();
+        let changed_directory = path.parent().map(|parent| parent.canonicalize());
+        let is_project_asset = match (assets_directory, changed_directory) {
+            (Ok(assets_directory), Some(Ok(changed_directory))) => {
+                changed_directory.starts_with(assets_directory)
+            }
+            _ => false,
+        };
+        if !is_project_asset {
+            return NeedsRedraw::No;
+        }
+        self.handle_message(EditorMessage::ReloadAssets);
+        NeedsRedraw::Yes
+    }
+    pub fn on_ruffle_closed(&mut self, run_id: u64, exit_code: Option<i32>) -> NeedsRedraw {
+        if let Some(run_ui) = &mut self.run_ui {
+            if run_ui.on_ruffle_closed(run_id, exit_code) {
+                return NeedsRedraw::Yes;
+            }
+        }
+        NeedsRedraw::No
     }
     pub fn project_name(&self) -> &str {
         self.directory
@@ -0,0 +1,110 @@
+use std::path::{Path, PathBuf};
+
+use egui::Vec2;
+use flits_core::{FlitsFont, FontCharacters, Symbol};
+
+use crate::{
+    edit::{AddSymbolEdit, MovieEdit},
+    editor::properties_panel::import_asset_file,
+};
+
+const DEFAULT_CHARACTERS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+pub struct AddFontWindow {
+    path: Option<PathBuf>,
+    characters: String,
+    error: Option<String>,
+}
+impl Default for AddFontWindow {
+    fn default() -> Self {
+        AddFontWindow {
+            path: None,
+            characters: DEFAULT_CHARACTERS.into(),
+            error: None,
+        }
+    }
+}
+impl AddFontWindow {
+    pub fn do_ui(&mut self, egui_ctx: &egui::Context, directory: &Path) -> AddFontWindowResult {
+        let mut result = AddFontWindowResult::NoAction;
+        let mut is_window_open = true;
+        egui::Window::new("Add font")
+            .resizable(false)
+            .collapsible(false)
+            .open(&mut is_window_open)
+            .show(egui_ctx, |ui| {
+                egui::Grid::new("add_font_grid").show(ui, |ui| {
+                    ui.label("File:");
+                    ui.horizontal(|ui| {
+                        ui.label(match &self.path {
+                            Some(path) => path.to_string_lossy().to_string(),
+                            None => "No file chosen".into(),
+                        });
+                        if ui.button("Browse...").clicked() {
+                            if let Some(chosen_path) = rfd::FileDialog::new()
+                                .add_filter("Fonts", &["ttf", "otf"])
+                                .set_directory(directory.join("assets"))
+                                .set_title("Choose a font")
+                                .pick_file()
+                            {
+                                self.path = Some(chosen_path);
+                            }
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("Characters:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.characters)
+                            .min_size(Vec2::new(200.0, 0.0)),
+                    );
+                    ui.end_row();
+
+                    if ui
+                        .add_enabled(self.path.is_some(), egui::Button::new("Add"))
+                        .clicked()
+                    {
+                        if let Some(movie_edit) = self.create_edit(directory) {
+                            result = AddFontWindowResult::Confirm(movie_edit);
+                        }
+                    }
+                    ui.end_row();
+                });
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.style().visuals.error_fg_color, error);
+                }
+            });
+        if !is_window_open {
+            result = AddFontWindowResult::Cancel;
+        }
+        result
+    }
+
+    fn create_edit(&mut self, directory: &Path) -> Option<MovieEdit> {
+        let chosen_path = self.path.as_ref()?;
+        // fonts outside of the assets directory are copied into it
+        let path = match import_asset_file(directory, chosen_path) {
+            Ok(path) => path,
+            Err(err) => {
+                self.error = Some(format!("Unable to copy {}: {}", chosen_path.display(), err));
+                return None;
+            }
+        };
+        Some(MovieEdit::AddSymbol(AddSymbolEdit {
+            symbol: Symbol::Font(FlitsFont {
+                path,
+                characters: FontCharacters {
+                    ascii: false,
+                    additional_characters: self.characters.clone(),
+                    only_used: false,
+                },
+            }),
+        }))
+    }
+}
+
+pub enum AddFontWindowResult {
+    NoAction,
+    Confirm(MovieEdit),
+    Cancel,
+}
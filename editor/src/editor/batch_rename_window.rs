@@ -0,0 +1,121 @@
+use egui::Vec2;
+use flits_core::{Movie, Symbol};
+use regex::Regex;
+
+use crate::edit::{MovieEdit, RenameSymbolsEdit, SymbolRename};
+
+#[derive(Default)]
+pub struct BatchRenameWindow {
+    find: String,
+    replace: String,
+    use_regex: bool,
+}
+impl BatchRenameWindow {
+    pub fn do_ui(&mut self, egui_ctx: &egui::Context, movie: &Movie) -> BatchRenameWindowResult {
+        let mut result = BatchRenameWindowResult::NoAction;
+        let mut is_window_open = true;
+        let renames = self.renames(movie);
+        egui::Window::new("Batch rename")
+            .collapsible(false)
+            .open(&mut is_window_open)
+            .show(egui_ctx, |ui| {
+                egui::Grid::new("batch_rename_grid").show(ui, |ui| {
+                    ui.label("Find:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.find).min_size(Vec2::new(200.0, 0.0)),
+                    );
+                    ui.end_row();
+
+                    ui.label("Replace:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.replace)
+                            .min_size(Vec2::new(200.0, 0.0)),
+                    );
+                    ui.end_row();
+
+                    ui.label("");
+                    ui.checkbox(&mut self.use_regex, "Regular expression");
+                    ui.end_row();
+                });
+
+                ui.separator();
+                match &renames {
+                    Ok(renames) if renames.is_empty() => {
+                        ui.label("No symbols will be renamed.");
+                    }
+                    Ok(renames) => {
+                        egui::ScrollArea::vertical()
+                            .max_height(200.0)
+                            .show(ui, |ui| {
+                                for rename in renames {
+                                    ui.label(format!("{} → {}", rename.before, rename.after));
+                                }
+                            });
+                    }
+                    Err(error) => {
+                        ui.colored_label(ui.style().visuals.error_fg_color, error);
+                    }
+                }
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    let can_apply = matches!(&renames, Ok(renames) if !renames.is_empty());
+                    if ui
+                        .add_enabled(can_apply, egui::Button::new("Apply"))
+                        .clicked()
+                    {
+                        if let Ok(renames) = self.renames(movie) {
+                            result = BatchRenameWindowResult::Confirm(MovieEdit::RenameSymbols(
+                                RenameSymbolsEdit { renames },
+                            ));
+                        }
+                    }
+                    if ui.button("Cancel").clicked() {
+                        result = BatchRenameWindowResult::Cancel;
+                    }
+                });
+            });
+        if !is_window_open {
+            result = BatchRenameWindowResult::Cancel;
+        }
+        result
+    }
+
+    // the symbols whose name changes, also used for the preview
+    fn renames(&self, movie: &Movie) -> Result<Vec<SymbolRename>, String> {
+        if self.find.is_empty() {
+            return Ok(vec![]);
+        }
+        let regex = if self.use_regex {
+            Some(Regex::new(&self.find).map_err(|err| err.to_string())?)
+        } else {
+            None
+        };
+        let mut renames = vec![];
+        for (symbol_index, symbol) in movie.symbols.iter().enumerate() {
+            // the name of a font is its path
+            if let Symbol::Font(_) = symbol {
+                continue;
+            }
+            let before = symbol.name();
+            let after = match &regex {
+                Some(regex) => regex.replace_all(&before, &self.replace).into_owned(),
+                None => before.replace(&self.find, &self.replace),
+            };
+            if after != before {
+                renames.push(SymbolRename {
+                    symbol_index,
+                    before,
+                    after,
+                });
+            }
+        }
+        Ok(renames)
+    }
+}
+
+pub enum BatchRenameWindowResult {
+    NoAction,
+    Confirm(MovieEdit),
+    Cancel,
+}
@@ -1,4 +1,7 @@
-use crate::{editor::Context, message::EditorMessage};
+use crate::{
+    editor::{library::symbol_icon, Context},
+    message::EditorMessage,
+};
 
 #[derive(Default)]
 pub struct BreadcrumbBar {}
@@ -10,7 +13,9 @@ impl BreadcrumbBar {
                     ctx.message_bus
                         .publish(EditorMessage::ChangeSelectedSymbol(None));
                 }
-                let _ = ui.selectable_label(true, ctx.movie.symbols[editing_clip].name());
+                let symbol = &ctx.movie.symbols[editing_clip];
+                symbol_icon(ui, symbol);
+                let _ = ui.selectable_label(true, symbol.name());
             } else {
                 let _ = ui.selectable_label(true, "Scene");
             }
@@ -0,0 +1,134 @@
+use egui::Vec2;
+
+// the unicode blocks the characters are grouped by, characters outside of these are listed under "Other"
+const UNICODE_BLOCKS: &[(&str, u32, u32)] = &[
+    ("Basic Latin", 0x0000, 0x007F),
+    ("Latin-1 Supplement", 0x0080, 0x00FF),
+    ("Latin Extended-A", 0x0100, 0x017F),
+    ("Latin Extended-B", 0x0180, 0x024F),
+    ("IPA Extensions", 0x0250, 0x02AF),
+    ("Greek and Coptic", 0x0370, 0x03FF),
+    ("Cyrillic", 0x0400, 0x04FF),
+    ("Hebrew", 0x0590, 0x05FF),
+    ("Arabic", 0x0600, 0x06FF),
+    ("Latin Extended Additional", 0x1E00, 0x1EFF),
+    ("General Punctuation", 0x2000, 0x206F),
+    ("Currency Symbols", 0x20A0, 0x20CF),
+    ("Letterlike Symbols", 0x2100, 0x214F),
+    ("Number Forms", 0x2150, 0x218F),
+    ("Arrows", 0x2190, 0x21FF),
+    ("Mathematical Operators", 0x2200, 0x22FF),
+    ("Box Drawing", 0x2500, 0x257F),
+    ("Block Elements", 0x2580, 0x259F),
+    ("Geometric Shapes", 0x25A0, 0x25FF),
+    ("Miscellaneous Symbols", 0x2600, 0x26FF),
+    ("Dingbats", 0x2700, 0x27BF),
+    ("Hiragana", 0x3040, 0x309F),
+    ("Katakana", 0x30A0, 0x30FF),
+    ("CJK Unified Ideographs", 0x4E00, 0x9FFF),
+];
+
+#[derive(Default)]
+pub struct CharacterPickerWindow {
+    search: String,
+}
+impl CharacterPickerWindow {
+    // characters are the characters of the font, other characters can't be shown in the text field anyway
+    pub fn do_ui(
+        &mut self,
+        egui_ctx: &egui::Context,
+        characters: &str,
+    ) -> CharacterPickerWindowResult {
+        let mut result = CharacterPickerWindowResult::NoAction;
+        let mut is_window_open = true;
+        egui::Window::new("Insert character")
+            .collapsible(false)
+            .open(&mut is_window_open)
+            .show(egui_ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Search:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.search).hint_text("Block or U+00E9"),
+                    );
+                });
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        let blocks = self.blocks(characters);
+                        if blocks.is_empty() {
+                            ui.label("No characters found.");
+                        }
+                        for (block_name, block_characters) in blocks {
+                            ui.label(block_name);
+                            ui.horizontal_wrapped(|ui| {
+                                for character in block_characters {
+                                    if ui
+                                        .add_sized(
+                                            Vec2::new(24.0, 24.0),
+                                            egui::Button::new(character.to_string()),
+                                        )
+                                        .on_hover_text(format!("U+{:04X}", character as u32))
+                                        .clicked()
+                                    {
+                                        result = CharacterPickerWindowResult::Insert(character);
+                                    }
+                                }
+                            });
+                        }
+                    });
+            });
+        if !is_window_open {
+            result = CharacterPickerWindowResult::Close;
+        }
+        result
+    }
+
+    // the characters grouped by block, filtered by the search query
+    fn blocks(&self, characters: &str) -> Vec<(&'static str, Vec<char>)> {
+        let search = self.search.trim().to_lowercase();
+        let mut blocks: Vec<(&'static str, Vec<char>)> = vec![];
+        for character in characters.chars() {
+            if character.is_control() || character == ' ' {
+                continue;
+            }
+            let block_name = Self::block_name(character);
+            let code_point = format!("u+{:04x}", character as u32);
+            if !search.is_empty()
+                && !block_name.to_lowercase().contains(&search)
+                && !code_point.contains(&search)
+                && !character.to_lowercase().eq(search.chars())
+            {
+                continue;
+            }
+            match blocks.iter_mut().find(|(name, _)| *name == block_name) {
+                Some((_, block_characters)) => {
+                    if !block_characters.contains(&character) {
+                        block_characters.push(character);
+                    }
+                }
+                None => blocks.push((block_name, vec![character])),
+            }
+        }
+        for (_, block_characters) in &mut blocks {
+            block_characters.sort();
+        }
+        blocks.sort_by_key(|(_, block_characters)| block_characters[0]);
+        blocks
+    }
+
+    fn block_name(character: char) -> &'static str {
+        let code_point = character as u32;
+        UNICODE_BLOCKS
+            .iter()
+            .find(|(_, start, end)| (*start..=*end).contains(&code_point))
+            .map(|(name, _, _)| *name)
+            .unwrap_or("Other")
+    }
+}
+
+pub enum CharacterPickerWindowResult {
+    NoAction,
+    Insert(char),
+    Close,
+}
@@ -0,0 +1,78 @@
+use flits_core::{PlaceSymbol, Symbol, SymbolIndex, SymbolIndexOrRoot};
+
+use crate::{editor::Context, message::EditorMessage};
+
+// shows which symbols are placed where, starting from the root
+#[derive(Default)]
+pub struct DependencyTree {}
+impl DependencyTree {
+    pub fn do_ui(&mut self, ui: &mut egui::Ui, ctx: &Context) {
+        egui::CollapsingHeader::new("Dependency tree").show(ui, |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    let mut path = vec![];
+                    Self::placed_symbols_ui(ui, ctx, None, &ctx.movie.root, &mut path);
+                });
+        });
+    }
+
+    fn placed_symbols_ui(
+        ui: &mut egui::Ui,
+        ctx: &Context,
+        container: SymbolIndexOrRoot,
+        placed_symbols: &Vec<PlaceSymbol>,
+        // the movieclips we are inside of, to stop at movieclips that place themselves
+        path: &mut Vec<SymbolIndex>,
+    ) {
+        for (placed_symbol_index, place_symbol) in placed_symbols.iter().enumerate() {
+            let symbol = &ctx.movie.symbols[place_symbol.symbol_index];
+            let mut text = symbol.name();
+            if !place_symbol.instance_name.is_empty() {
+                text = format!("{} ({})", text, place_symbol.instance_name);
+            }
+            let is_selected = ctx.selection.stage_symbol_index == container
+                && ctx.selection.placed_symbols.contains(&placed_symbol_index);
+
+            let label_ui = |ui: &mut egui::Ui| {
+                if ui.selectable_label(is_selected, text).clicked() {
+                    ctx.message_bus
+                        .publish(EditorMessage::ChangeSelectedSymbol(container));
+                    ctx.message_bus
+                        .publish(EditorMessage::ChangeSelectedPlacedSymbols(vec![
+                            placed_symbol_index,
+                        ]));
+                }
+            };
+            match symbol {
+                Symbol::MovieClip(movieclip)
+                    if !movieclip.place_symbols.is_empty()
+                        && !path.contains(&place_symbol.symbol_index) =>
+                {
+                    let id = ui.make_persistent_id((container, placed_symbol_index));
+                    egui::collapsing_header::CollapsingState::load_with_default_open(
+                        ui.ctx(),
+                        id,
+                        false,
+                    )
+                    .show_header(ui, label_ui)
+                    .body(|ui| {
+                        path.push(place_symbol.symbol_index);
+                        Self::placed_symbols_ui(
+                            ui,
+                            ctx,
+                            Some(place_symbol.symbol_index),
+                            &movieclip.place_symbols,
+                            path,
+                        );
+                        path.pop();
+                    });
+                }
+                _ => {
+                    label_ui(ui);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::editor::stage::camera::Camera;
+
+const EDITOR_SETTINGS_FILE_NAME: &str = "editor_settings.json";
+
+// state of the editor that is kept between sessions, stored next to the project file
+// it isn't part of the movie, so changing it doesn't count as an unsaved change
+#[derive(Serialize, Deserialize, Default)]
+pub struct EditorSettings {
+    // the camera of the scene, not of the movieclip that was being edited
+    #[serde(default)]
+    pub camera: Option<Camera>,
+    // in egui points, none when the library was never shown
+    #[serde(default)]
+    pub library_width: Option<f32>,
+}
+impl EditorSettings {
+    pub fn load(directory: &Path) -> EditorSettings {
+        let path = directory.join(EDITOR_SETTINGS_FILE_NAME);
+        let Ok(file) = std::fs::File::open(&path) else {
+            // projects that were never opened in the editor don't have settings yet
+            return EditorSettings::default();
+        };
+        serde_json::from_reader(file).unwrap_or_else(|err| {
+            eprintln!("Warning: ignoring invalid {}: {}", path.display(), err);
+            EditorSettings::default()
+        })
+    }
+    pub fn save(&self, directory: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let file = std::fs::File::options()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(directory.join(EDITOR_SETTINGS_FILE_NAME))?;
+        serde_json::to_writer(file, self)?;
+        Ok(())
+    }
+}
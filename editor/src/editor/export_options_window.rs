@@ -0,0 +1,37 @@
+use flits_core::ExportOptions;
+
+// asks for the options before exporting, the normal export uses the defaults
+#[derive(Default)]
+pub struct ExportOptionsWindow {
+    prune_unused: bool,
+}
+impl ExportOptionsWindow {
+    pub fn do_ui(&mut self, egui_ctx: &egui::Context) -> ExportOptionsWindowResult {
+        let mut result = ExportOptionsWindowResult::NoAction;
+        let mut is_window_open = true;
+        egui::Window::new("Export with options")
+            .resizable(false)
+            .collapsible(false)
+            .open(&mut is_window_open)
+            .show(egui_ctx, |ui| {
+                ui.checkbox(&mut self.prune_unused, "Leave out unused symbols")
+                    .on_hover_text("Symbols that aren't placed anywhere");
+                if ui.button("Export").clicked() {
+                    result = ExportOptionsWindowResult::Export(ExportOptions {
+                        prune_unused: self.prune_unused,
+                        ..Default::default()
+                    });
+                }
+            });
+        if !is_window_open {
+            result = ExportOptionsWindowResult::Cancel;
+        }
+        result
+    }
+}
+
+pub enum ExportOptionsWindowResult {
+    NoAction,
+    Export(ExportOptions),
+    Cancel,
+}
@@ -0,0 +1,145 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::Receiver,
+    },
+};
+
+use flits_core::{ExportOptions, ExportResult, Movie, Symbol, SymbolIndex};
+use winit::event_loop::EventLoopProxy;
+
+use crate::FlitsEvent;
+
+// shared by all editors, the progress events of an export go to every editor
+static NEXT_EXPORT_ID: AtomicU64 = AtomicU64::new(0);
+
+// what to do after the export succeeded
+pub enum AfterExport {
+    Nothing,
+    Run,
+    // zip the swf with the assets into output.zip
+    CreateArchive,
+}
+
+#[derive(Default)]
+pub struct SymbolChanges {
+    // the content hashes of the symbols that are being exported
+    pub symbol_hashes: HashMap<SymbolIndex, u64>,
+    // names of the symbols that changed since the last export, empty for the first export
+    pub changed_symbols: Vec<String>,
+}
+
+// shows the progress of an export that runs on a background thread
+pub struct ExportWindow {
+    export_id: u64,
+    progress: f32,
+    result_receiver: Receiver<Result<ExportResult, String>>,
+    pub after_export: AfterExport,
+    changes_receiver: Receiver<SymbolChanges>,
+    pub symbol_changes: Option<SymbolChanges>,
+}
+impl ExportWindow {
+    pub fn start(
+        movie: &Movie,
+        directory: PathBuf,
+        mut options: ExportOptions,
+        after_export: AfterExport,
+        last_export_hashes: &HashMap<SymbolIndex, u64>,
+        event_loop: EventLoopProxy<FlitsEvent>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        // hashing reads all the asset files, so it happens next to the export
+        // like the export, the thread gets a copy of the symbols without the bitmap cache
+        let symbols = serde_json::to_value(&movie.symbols)?;
+        let hash_directory = directory.clone();
+        let last_export_hashes = last_export_hashes.clone();
+        let changes_thread = std::thread::spawn(move || {
+            let symbols: Vec<Symbol> = serde_json::from_value(symbols).unwrap_or_default();
+            Self::symbol_changes(&symbols, &hash_directory, &last_export_hashes)
+        });
+        let (changes_sender, changes_receiver) = std::sync::mpsc::channel();
+        let export_id = NEXT_EXPORT_ID.fetch_add(1, Ordering::Relaxed);
+        let (progress_sender, progress_receiver) = std::sync::mpsc::channel();
+        options.progress = Some(progress_sender);
+        let swf_path = directory.join("output.swf");
+        let result_receiver = movie.export_in_background(directory, swf_path, options)?;
+        // the events wake up the event loop so the progress bar gets redrawn
+        std::thread::spawn(move || {
+            for progress in progress_receiver {
+                _ = event_loop.send_event(FlitsEvent::ExportProgress(export_id, progress));
+            }
+            // sent before the last event, so the changes are there when that event is handled
+            _ = changes_sender.send(changes_thread.join().unwrap_or_default());
+            // the progress channel is closed after the result is sent, so the result is ready now
+            event_loop
+                .send_event(FlitsEvent::ExportProgress(export_id, 1.0))
+                .unwrap_or_else(|err| {
+                    eprintln!("Unable to send export progress event: {}", err);
+                });
+        });
+        Ok(ExportWindow {
+            export_id,
+            progress: 0.0,
+            result_receiver,
+            after_export,
+            changes_receiver,
+            symbol_changes: None,
+        })
+    }
+    fn symbol_changes(
+        symbols: &[Symbol],
+        directory: &Path,
+        last_export_hashes: &HashMap<SymbolIndex, u64>,
+    ) -> SymbolChanges {
+        let symbol_hashes: HashMap<SymbolIndex, u64> = symbols
+            .iter()
+            .enumerate()
+            .map(|(symbol_index, symbol)| (symbol_index, symbol.content_hash(directory)))
+            .collect();
+        let changed_symbols = if last_export_hashes.is_empty() {
+            vec![]
+        } else {
+            let mut changed_symbol_indices: Vec<SymbolIndex> = symbol_hashes
+                .iter()
+                .filter(|(symbol_index, hash)| last_export_hashes.get(symbol_index) != Some(hash))
+                .map(|(symbol_index, _)| *symbol_index)
+                .collect();
+            changed_symbol_indices.sort();
+            changed_symbol_indices
+                .into_iter()
+                .map(|symbol_index| symbols[symbol_index].name())
+                .collect()
+        };
+        SymbolChanges {
+            symbol_hashes,
+            changed_symbols,
+        }
+    }
+
+    pub fn export_id(&self) -> u64 {
+        self.export_id
+    }
+    // returns the result when the export is done and the symbol changes are known
+    pub fn update_progress(&mut self, progress: f32) -> Option<Result<ExportResult, String>> {
+        self.progress = progress;
+        if self.symbol_changes.is_none() {
+            self.symbol_changes = Some(self.changes_receiver.try_recv().ok()?);
+        }
+        self.result_receiver.try_recv().ok()
+    }
+
+    pub fn do_ui(&self, egui_ctx: &egui::Context) {
+        egui::Window::new("Exporting")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(egui_ctx, |ui| {
+                ui.add(
+                    egui::ProgressBar::new(self.progress)
+                        .desired_width(300.0)
+                        .show_percentage(),
+                );
+            });
+    }
+}
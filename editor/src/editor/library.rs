@@ -1,16 +1,29 @@
+use flits_core::{Symbol, SymbolIndex};
+
 use crate::{
     editor::{stage::StageMessage, Context},
     message::EditorMessage,
 };
 
 #[derive(Default)]
-pub struct Library {}
+pub struct Library {
+    // symbols selected with ctrl+click, these are dragged to the stage together
+    selected_symbols: Vec<SymbolIndex>,
+}
 impl Library {
+    pub fn clear_selection(&mut self) {
+        self.selected_symbols = vec![];
+    }
     pub fn do_ui(&mut self, ui: &mut egui::Ui, ctx: &Context) {
         ui.heading("Library");
-        if ui.button("Add MovieClip...").clicked() {
-            ctx.message_bus.publish(EditorMessage::OpenNewSymbolWindow);
-        }
+        ui.horizontal(|ui| {
+            if ui.button("Add MovieClip...").clicked() {
+                ctx.message_bus.publish(EditorMessage::OpenNewSymbolWindow);
+            }
+            if ui.button("Add Font...").clicked() {
+                ctx.message_bus.publish(EditorMessage::OpenAddFontWindow);
+            }
+        });
         egui::ScrollArea::vertical()
             .auto_shrink([false, false])
             .show(ui, |ui| {
@@ -19,29 +32,83 @@ impl Library {
                     let checked = ctx
                         .selection
                         .properties_symbol_index
-                        .map_or(false, |symbol_index| symbol_index == i);
+                        .map_or(false, |symbol_index| symbol_index == i)
+                        || self.selected_symbols.contains(&i);
                     let mut text = egui::RichText::new(symbol.name());
                     if symbol.is_invalid() {
                         text = text.color(ui.style().visuals.error_fg_color);
                     }
-                    let response = ui.selectable_label(checked, text);
+                    let response = ui
+                        .horizontal(|ui| {
+                            symbol_icon(ui, symbol);
+                            ui.selectable_label(checked, text)
+                        })
+                        .inner;
                     let response = response.interact(egui::Sense::drag());
 
-                    if response.clicked() {
+                    if response.double_clicked() {
+                        ctx.message_bus.publish(match symbol {
+                            Symbol::MovieClip(_) | Symbol::StaticText(_) => {
+                                EditorMessage::ChangeSelectedSymbol(Some(i))
+                            }
+                            Symbol::Bitmap(_) | Symbol::Font(_) | Symbol::Sound(_) => {
+                                EditorMessage::OpenSymbolFile(i)
+                            }
+                        });
+                    } else if response.clicked() && ctx.modifiers.command {
+                        if let Some(position) =
+                            self.selected_symbols.iter().position(|index| *index == i)
+                        {
+                            self.selected_symbols.remove(position);
+                        } else {
+                            self.selected_symbols.push(i);
+                        }
+                    } else if response.clicked() {
+                        self.selected_symbols = vec![i];
                         ctx.message_bus
                             .publish(EditorMessage::ChangeSelectedSymbol(Some(i)));
                         /*needs_redraw = NeedsRedraw::Yes;*/
                     } else if response.drag_stopped() {
-                        // TODO: handle drag that doesn't end on stage
-                        ctx.message_bus.publish(EditorMessage::Stage(
-                            StageMessage::ReleaseSymbolDragDrop(
-                                response.interact_pointer_pos().unwrap(),
-                                i,
-                            ),
-                        ));
+                        // there is no position when the mouse is released outside the window
+                        if let Some(pos) = response.interact_pointer_pos() {
+                            // dragging one of the selected symbols drags all of them
+                            let symbol_indices = if self.selected_symbols.contains(&i) {
+                                self.selected_symbols.clone()
+                            } else {
+                                vec![i]
+                            };
+                            ctx.message_bus.publish(EditorMessage::Stage(
+                                StageMessage::ReleaseSymbolDragDrop(pos, symbol_indices),
+                            ));
+                        }
                         //needs_redraw = NeedsRedraw::Yes;
                     }
                 }
             });
     }
 }
+
+const SYMBOL_ICON_SIZE: f32 = 12.0;
+
+// a colored square so the type of a symbol can be seen at a glance
+pub(super) fn symbol_icon(ui: &mut egui::Ui, symbol: &Symbol) {
+    let color = match symbol {
+        Symbol::MovieClip(_) => egui::Color32::from_rgb(66, 133, 244),
+        Symbol::Bitmap(_) => egui::Color32::from_rgb(52, 168, 83),
+        Symbol::Font(_) => egui::Color32::from_rgb(245, 140, 30),
+        Symbol::StaticText(_) => egui::Color32::from_rgb(160, 90, 220),
+        Symbol::Sound(_) => egui::Color32::from_rgb(220, 60, 60),
+    };
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(SYMBOL_ICON_SIZE, SYMBOL_ICON_SIZE),
+        egui::Sense::hover(),
+    );
+    ui.painter().rect_filled(rect, 2.0, color);
+    response.on_hover_text(match symbol {
+        Symbol::MovieClip(_) => "MovieClip",
+        Symbol::Bitmap(_) => "Bitmap",
+        Symbol::Font(_) => "Font",
+        Symbol::StaticText(_) => "Static text",
+        Symbol::Sound(_) => "Sound",
+    });
+}
@@ -1,19 +1,18 @@
-use egui::Widget;
-
 use crate::{editor::stage::StageMessage, editor::Context, message::EditorMessage, FlitsEvent};
 
-struct Menu<'a> {
-    name: &'a str,
-    items: &'a [MenuItem<'a>],
+pub(super) struct Menu<'a> {
+    pub(super) name: &'a str,
+    pub(super) items: &'a [MenuItem<'a>],
 }
 
-struct MenuItem<'a> {
-    name: &'a str,
-    keyboard_shortcut: Option<egui::KeyboardShortcut>,
+pub(super) struct MenuItem<'a> {
+    pub(super) name: &'a str,
+    pub(super) keyboard_shortcut: Option<egui::KeyboardShortcut>,
     message: fn() -> EditorMessage,
 }
 
-const MENUS: &[Menu] = &[
+// the shortcuts window reads this too, so it always matches the menu
+pub(super) const MENUS: &[Menu] = &[
     Menu {
         name: "File",
         items: &[
@@ -42,6 +41,16 @@ const MENUS: &[Menu] = &[
                 )),
                 message: || EditorMessage::Export,
             },
+            MenuItem {
+                name: "Export with options...",
+                keyboard_shortcut: None,
+                message: || EditorMessage::OpenExportOptionsWindow,
+            },
+            MenuItem {
+                name: "Export as archive",
+                keyboard_shortcut: None,
+                message: || EditorMessage::ExportArchive,
+            },
             MenuItem {
                 name: "Close",
                 keyboard_shortcut: Some(egui::KeyboardShortcut::new(
@@ -95,6 +104,16 @@ const MENUS: &[Menu] = &[
                 )),
                 message: || EditorMessage::SelectAll,
             },
+            MenuItem {
+                name: "Batch rename...",
+                keyboard_shortcut: None,
+                message: || EditorMessage::OpenBatchRenameWindow,
+            },
+            MenuItem {
+                name: "Root scripts...",
+                keyboard_shortcut: None,
+                message: || EditorMessage::OpenScriptsWindow,
+            },
             MenuItem {
                 name: "Reload assets",
                 keyboard_shortcut: Some(egui::KeyboardShortcut::new(
@@ -147,18 +166,36 @@ const MENUS: &[Menu] = &[
     },
     Menu {
         name: "Help",
-        items: &[MenuItem {
-            name: "About...",
-            keyboard_shortcut: None,
-            message: || EditorMessage::Event(FlitsEvent::About),
-        }],
+        items: &[
+            MenuItem {
+                name: "Keyboard shortcuts...",
+                keyboard_shortcut: None,
+                message: || EditorMessage::OpenShortcutsWindow,
+            },
+            MenuItem {
+                name: "About...",
+                keyboard_shortcut: None,
+                message: || EditorMessage::Event(FlitsEvent::About),
+            },
+        ],
     },
 ];
 
 #[derive(Default)]
 pub struct MenuBar {}
 impl MenuBar {
-    pub fn do_ui(&mut self, ui: &mut egui::Ui, ctx: &Context) {
+    pub fn do_ui(&mut self, ui: &mut egui::Ui, ctx: &Context, is_exporting: bool) {
+        // items that export can't be used until the current export is done
+        let is_enabled = |item: &MenuItem| {
+            !is_exporting
+                || !matches!(
+                    (item.message)(),
+                    EditorMessage::Export
+                        | EditorMessage::OpenExportOptionsWindow
+                        | EditorMessage::ExportArchive
+                        | EditorMessage::Run
+                )
+        };
         // this isn't just text field, also buttons and such
         let is_something_focused = ui.ctx().memory(|memory| memory.focused().is_some());
         for menu in MENUS {
@@ -167,6 +204,7 @@ impl MenuBar {
                     // only activate keyboard shortcuts without modifiers when nothing is focused
                     let can_activate = keyboard_shortcut.modifiers.any() || !is_something_focused;
                     if can_activate
+                        && is_enabled(item)
                         && ui
                             .ctx()
                             .input_mut(|input| input.consume_shortcut(&keyboard_shortcut))
@@ -194,7 +232,7 @@ impl MenuBar {
                             button =
                                 button.shortcut_text(ui.ctx().format_shortcut(&keyboard_shortcut));
                         }
-                        if button.ui(ui).clicked() {
+                        if ui.add_enabled(is_enabled(item), button).clicked() {
                             ctx.message_bus.publish((item.message)());
                             ui.close_menu();
                         }
@@ -1,18 +1,25 @@
 use egui::Vec2;
+use flits_core::{EditorColor, Movie, StaticTextProperties, Symbol};
 
-use crate::edit::{AddMovieClipEdit, MovieEdit};
+use crate::edit::{AddMovieClipEdit, AddStaticTextEdit, MovieEdit};
 
 #[derive(Default)]
 pub struct NewSymbolWindow {
     name: String,
+    symbol_type: NewSymbolType,
     has_requestion_focus: bool,
 }
+#[derive(Default, PartialEq, Clone, Copy)]
+enum NewSymbolType {
+    #[default]
+    MovieClip,
+    StaticText,
+}
 impl NewSymbolWindow {
-    pub fn do_ui(&mut self, egui_ctx: &egui::Context) -> NewSymbolWindowResult {
+    pub fn do_ui(&mut self, egui_ctx: &egui::Context, movie: &Movie) -> NewSymbolWindowResult {
         let mut result = NewSymbolWindowResult::NoAction;
         let mut is_window_open = true;
-        // title says new movieclip because there are no other options yet
-        egui::Window::new("New movieclip")
+        egui::Window::new("New symbol")
             .resizable(false)
             .collapsible(false)
             .open(&mut is_window_open)
@@ -35,16 +42,27 @@ impl NewSymbolWindow {
                     }
                     ui.end_row();
 
+                    ui.label("Type:");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(
+                            &mut self.symbol_type,
+                            NewSymbolType::MovieClip,
+                            "Movieclip",
+                        );
+                        ui.radio_value(
+                            &mut self.symbol_type,
+                            NewSymbolType::StaticText,
+                            "Static text",
+                        );
+                    });
+                    ui.end_row();
+
                     if ui
                         .add_enabled(!self.name.is_empty(), egui::Button::new("Create"))
                         .clicked()
                         || (user_confirmed_form && !self.name.is_empty())
                     {
-                        result = NewSymbolWindowResult::Confirm(MovieEdit::AddMovieClip(
-                            AddMovieClipEdit {
-                                name: self.name.clone(),
-                            },
-                        ));
+                        result = NewSymbolWindowResult::Confirm(self.create_edit(movie));
                     }
                     ui.end_row();
                 });
@@ -54,6 +72,29 @@ impl NewSymbolWindow {
         }
         result
     }
+
+    fn create_edit(&self, movie: &Movie) -> MovieEdit {
+        match self.symbol_type {
+            NewSymbolType::MovieClip => MovieEdit::AddMovieClip(AddMovieClipEdit {
+                name: self.name.clone(),
+            }),
+            NewSymbolType::StaticText => MovieEdit::AddStaticText(AddStaticTextEdit {
+                properties: StaticTextProperties {
+                    name: self.name.clone(),
+                    text: self.name.clone(),
+                    // use the first font, it can be changed in the properties
+                    font_index: movie
+                        .symbols
+                        .iter()
+                        .position(|symbol| matches!(symbol, Symbol::Font(_))),
+                    size: 12.0,
+                    color: EditorColor::BLACK,
+                    x: 0.0,
+                    y: 0.0,
+                },
+            }),
+        }
+    }
 }
 
 pub enum NewSymbolWindowResult {
@@ -0,0 +1,86 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use crate::editor::{stage::RenderStats, MENU_HEIGHT};
+
+// the fps is averaged over this many frames
+const SAMPLE_COUNT: usize = 60;
+
+// shows how long rendering takes, enabled with --debug-overlay
+#[derive(Default)]
+pub struct PerformanceOverlay {
+    frame_starts: VecDeque<Instant>,
+    render_time: Duration,
+    stats: RenderStats,
+    bitmap_count: usize,
+}
+impl PerformanceOverlay {
+    pub fn record_frame(&mut self, frame_start: Instant, stats: RenderStats, bitmap_count: usize) {
+        if self.frame_starts.len() == SAMPLE_COUNT {
+            self.frame_starts.pop_front();
+        }
+        self.frame_starts.push_back(frame_start);
+        self.render_time = frame_start.elapsed();
+        self.stats = stats;
+        self.bitmap_count = bitmap_count;
+    }
+
+    fn fps(&self) -> f64 {
+        let (Some(first), Some(last)) = (self.frame_starts.front(), self.frame_starts.back())
+        else {
+            return 0.0;
+        };
+        let elapsed = last.duration_since(*first).as_secs_f64();
+        if elapsed == 0.0 {
+            return 0.0;
+        }
+        (self.frame_starts.len() - 1) as f64 / elapsed
+    }
+
+    pub fn do_ui(&self, egui_ctx: &egui::Context) {
+        egui::Area::new(egui::Id::new("performance_overlay"))
+            .anchor(
+                egui::Align2::LEFT_TOP,
+                egui::Vec2::new(8.0, MENU_HEIGHT as f32 + 8.0),
+            )
+            .interactable(false)
+            .show(egui_ctx, |ui| {
+                egui::Frame::new()
+                    .fill(egui::Color32::from_black_alpha(160))
+                    .inner_margin(6.0)
+                    .corner_radius(4.0)
+                    .show(ui, |ui| {
+                        let label = |ui: &mut egui::Ui, text: String| {
+                            ui.label(
+                                egui::RichText::new(text)
+                                    .monospace()
+                                    .color(egui::Color32::WHITE),
+                            );
+                        };
+                        label(ui, format!("FPS: {:.1}", self.fps()));
+                        label(
+                            ui,
+                            format!("Render: {:.2} ms", self.render_time.as_secs_f64() * 1000.0),
+                        );
+                        label(
+                            ui,
+                            format!(
+                                "GPU submit: {:.2} ms",
+                                self.stats.submit_time.as_secs_f64() * 1000.0
+                            ),
+                        );
+                        label(ui, format!("Draw commands: {}", self.stats.command_count));
+                        label(ui, format!("Bitmaps: {}", self.bitmap_count));
+                        label(
+                            ui,
+                            format!(
+                                "Text arena: {:.1} KiB",
+                                self.stats.text_arena_size_bytes as f64 / 1024.0
+                            ),
+                        );
+                    });
+            });
+    }
+}
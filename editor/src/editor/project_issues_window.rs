@@ -0,0 +1,46 @@
+use flits_core::{ProjectIssue, SymbolIndexOrRoot};
+
+pub struct ProjectIssuesWindow {
+    issues: Vec<ProjectIssue>,
+}
+impl ProjectIssuesWindow {
+    pub fn new(issues: Vec<ProjectIssue>) -> Option<Self> {
+        if issues.is_empty() {
+            return None;
+        }
+        Some(Self { issues })
+    }
+
+    pub fn do_ui(&mut self, egui_ctx: &egui::Context) -> ProjectIssuesWindowResult {
+        let mut result = ProjectIssuesWindowResult::NoAction;
+        // modal so the user sees the issues before editing anything
+        egui::Modal::new(egui::Id::new("ProjectIssues"))
+            .backdrop_color(egui::Color32::from_black_alpha(230))
+            .show(egui_ctx, |ui| {
+                ui.heading("This project has issues");
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        let warn_color = ui.style().visuals.warn_fg_color;
+                        for issue in &self.issues {
+                            ui.label(egui::RichText::new(issue.to_string()).color(warn_color));
+                        }
+                    });
+                ui.horizontal(|ui| {
+                    if ui.button("Continue").clicked() {
+                        result = ProjectIssuesWindowResult::Continue;
+                    }
+                    if ui.button("Go to first issue").clicked() {
+                        result = ProjectIssuesWindowResult::GoTo(self.issues[0].symbol_index());
+                    }
+                });
+            });
+        result
+    }
+}
+
+pub enum ProjectIssuesWindowResult {
+    NoAction,
+    Continue,
+    GoTo(SymbolIndexOrRoot),
+}
@@ -1,14 +1,26 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
 use egui::Vec2;
 
 use flits_core::{
-    Animation, Bitmap, BitmapCacheStatus, BitmapProperties, EditorColor, FlitsFont, Movie,
-    MovieClip, MovieClipProperties, MovieProperties, PlaceSymbol, PlacedSymbolIndex, PreloaderType,
-    Symbol, SymbolIndex, SymbolIndexOrRoot, TextAlign, TextProperties,
+    path_with_forward_slashes, Animation, Bitmap, BitmapCacheStatus, BitmapLoadError,
+    BitmapProperties, EditorColor, EditorTransform, FlitsFont, FlitsSound, Movie, MovieClip,
+    MovieClipProperties, MovieProperties, PixelSnappingMode, PlaceSymbol, PlacedSymbolIndex,
+    PreloaderType, SoundAction, SoundActionType, StaticTextProperties, Symbol, SymbolIndex,
+    SymbolIndexOrRoot, TextAlign, TextProperties, TextSpan, SWF_VERSION,
 };
 
 use crate::{
-    edit::FontPropertiesEdit,
-    editor::{MutableContext, Selection},
+    edit::{FontPropertiesEdit, SoundPropertiesEdit, StaticTextPropertiesEdit},
+    editor::{
+        character_picker_window::{CharacterPickerWindow, CharacterPickerWindowResult},
+        stage::Stage,
+        MutableContext, Selection,
+    },
     message::EditorMessage,
 };
 
@@ -27,10 +39,10 @@ pub enum PropertiesPanel {
     MultiSelectionProperties(MultiSelectionPropertiesPanel),
 }
 impl PropertiesPanel {
-    pub fn do_ui(&mut self, ui: &mut egui::Ui, ctx: &mut MutableContext) {
+    pub fn do_ui(&mut self, ui: &mut egui::Ui, ctx: &mut MutableContext, directory: &Path) {
         let edit = match self {
             PropertiesPanel::MovieProperties(panel) => panel.do_ui(ctx.movie, ui),
-            PropertiesPanel::SymbolProperties(panel) => panel.do_ui(ctx.movie, ui),
+            PropertiesPanel::SymbolProperties(panel) => panel.do_ui(ctx.movie, ui, directory),
             PropertiesPanel::PlacedSymbolProperties(panel) => {
                 if ctx.selection.placed_symbols.len() != 1 {
                     panic!(
@@ -58,6 +70,8 @@ impl PropertiesPanel {
                 if let Some(editing_clip) = selection.properties_symbol_index {
                     *self = PropertiesPanel::SymbolProperties(SymbolPropertiesPanel {
                         symbol_index: editing_clip,
+                        browse_error: None,
+                        font_load_error: None,
                         before_edit: match &movie.symbols[editing_clip] {
                             Symbol::Bitmap(bitmap) => {
                                 SymbolProperties::Bitmap(bitmap.properties.clone())
@@ -66,6 +80,10 @@ impl PropertiesPanel {
                                 SymbolProperties::MovieClip(movieclip.properties.clone())
                             }
                             Symbol::Font(font) => SymbolProperties::Font(font.clone()),
+                            Symbol::Sound(sound) => SymbolProperties::Sound(sound.clone()),
+                            Symbol::StaticText(static_text) => {
+                                SymbolProperties::StaticText(static_text.clone())
+                            }
                         },
                     });
                 } else {
@@ -78,8 +96,34 @@ impl PropertiesPanel {
                 let placed_symbol_index = selection.placed_symbols[0];
                 let place_symbol = &movie.get_placed_symbols(selection.properties_symbol_index)
                     [placed_symbol_index];
+                // keep the aspect ratio lock when the panel gets recreated after an edit
+                let aspect_ratio_lock = match self {
+                    PropertiesPanel::PlacedSymbolProperties(panel)
+                        if panel.placed_symbol_index == placed_symbol_index =>
+                    {
+                        panel.aspect_ratio_lock
+                    }
+                    _ => None,
+                };
+                let scale_from_corner = match self {
+                    PropertiesPanel::PlacedSymbolProperties(panel) => panel.scale_from_corner,
+                    _ => false,
+                };
+                // inserting a character is an edit, so the picker has to survive that as well
+                let character_picker = match self {
+                    PropertiesPanel::PlacedSymbolProperties(panel)
+                        if panel.placed_symbol_index == placed_symbol_index =>
+                    {
+                        panel.character_picker.take()
+                    }
+                    _ => None,
+                };
                 *self = PropertiesPanel::PlacedSymbolProperties(PlacedSymbolPropertiesPanel {
+                    placed_symbol_index,
                     before_edit: place_symbol.clone(),
+                    aspect_ratio_lock,
+                    scale_from_corner,
+                    character_picker,
                 });
             }
             _ => {
@@ -97,17 +141,34 @@ impl MoviePropertiesPanel {
         let mut edit: Option<MovieEdit> = None;
 
         ui.heading("Movie properties");
+        let mut properties_edited = false;
+        // the movieclips that can be used as the preloader graphic
+        let movieclips: Vec<(SymbolIndex, String)> = movie
+            .symbols
+            .iter()
+            .enumerate()
+            .filter_map(|(symbol_index, symbol)| match symbol {
+                Symbol::MovieClip(movieclip) => {
+                    Some((symbol_index, movieclip.properties.name.clone()))
+                }
+                _ => None,
+            })
+            .collect();
         egui::Grid::new("movie_properties_grid").show(ui, |ui| {
-            let mut properties_edited = false;
-
             ui.label("Width:");
-            let response = ui.add(egui::DragValue::new(&mut movie.properties.width));
+            let response = ui.add(
+                egui::DragValue::new(&mut movie.properties.width)
+                    .range(MovieProperties::SIZE_RANGE),
+            );
             if response.lost_focus() || response.drag_stopped() {
                 properties_edited = true;
             }
 
             ui.label("Framerate:");
-            let response = ui.add(egui::DragValue::new(&mut movie.properties.frame_rate));
+            let response = ui.add(
+                egui::DragValue::new(&mut movie.properties.frame_rate)
+                    .range(MovieProperties::FRAME_RATE_RANGE),
+            );
             if response.lost_focus() || response.drag_stopped() {
                 properties_edited = true;
             }
@@ -131,6 +192,23 @@ impl MoviePropertiesPanel {
                         PreloaderType::WithPlayButton,
                         PreloaderType::to_string(&PreloaderType::WithPlayButton),
                     );
+                    let is_custom_graphic = matches!(
+                        movie.properties.preloader,
+                        PreloaderType::CustomGraphic { .. }
+                    );
+                    // there has to be a movieclip to show
+                    if let Some((first_movieclip_index, _)) = movieclips.first() {
+                        if ui
+                            .selectable_label(is_custom_graphic, "Custom graphic")
+                            .clicked()
+                            && !is_custom_graphic
+                        {
+                            movie.properties.preloader = PreloaderType::CustomGraphic {
+                                movieclip_index: *first_movieclip_index,
+                            };
+                            properties_edited = true;
+                        }
+                    }
                 });
             if response.response.changed() {
                 properties_edited = true;
@@ -138,7 +216,10 @@ impl MoviePropertiesPanel {
             ui.end_row();
 
             ui.label("Height:");
-            let response = ui.add(egui::DragValue::new(&mut movie.properties.height));
+            let response = ui.add(
+                egui::DragValue::new(&mut movie.properties.height)
+                    .range(MovieProperties::SIZE_RANGE),
+            );
             if response.lost_focus() || response.drag_stopped() {
                 properties_edited = true;
             }
@@ -155,19 +236,105 @@ impl MoviePropertiesPanel {
             if bg_color_puc.edited {
                 properties_edited = true;
             }
+
+            ui.label("SWF version:");
+            let response = ui.add(
+                egui::DragValue::new(&mut movie.properties.swf_version)
+                    .speed(0.05)
+                    .range(1..=SWF_VERSION),
+            );
+            if response.lost_focus() || response.drag_stopped() {
+                properties_edited = true;
+            }
             ui.end_row();
+        });
 
-            if properties_edited {
-                // only add edit when the properties actually changed
-                if self.before_edit != movie.properties {
-                    edit = Some(MovieEdit::EditMovieProperties(MoviePropertiesEdit {
-                        before: self.before_edit.clone(),
-                        after: movie.properties.clone(),
-                    }));
+        egui::CollapsingHeader::new("Preloader style...").show(ui, |ui| {
+            egui::Grid::new("preloader_style_grid").show(ui, |ui| {
+                let style = &mut movie.properties.preloader_style;
+                let before_style = &self.before_edit.preloader_style;
+                let mut puc = PropertyUiContext::new();
+                if let PreloaderType::CustomGraphic { movieclip_index } =
+                    &mut movie.properties.preloader
+                {
+                    let mut graphic_edited = false;
+                    let selected_name = movieclips
+                        .iter()
+                        .find(|(symbol_index, _)| symbol_index == movieclip_index)
+                        .map(|(_, name)| name.clone())
+                        .unwrap_or_default();
+                    puc.combobox(ui, "Graphic:", selected_name, |ui| {
+                        for (symbol_index, name) in &movieclips {
+                            if ui
+                                .selectable_value(movieclip_index, *symbol_index, name)
+                                .clicked()
+                            {
+                                graphic_edited = true;
+                            }
+                        }
+                    });
+                    if graphic_edited {
+                        puc.edited = true;
+                    }
+                    ui.end_row();
                 }
-            }
+                puc.color_value(
+                    ui,
+                    "Bar color:",
+                    &mut style.bar_color,
+                    &before_style.bar_color,
+                    egui::color_picker::Alpha::OnlyBlend,
+                );
+                puc.color_value(
+                    ui,
+                    "Background color:",
+                    &mut style.background_color,
+                    &before_style.background_color,
+                    egui::color_picker::Alpha::OnlyBlend,
+                );
+                ui.end_row();
+
+                ui.label("Bar width:");
+                let response = ui.add(
+                    egui::DragValue::new(&mut style.bar_width_fraction)
+                        .speed(0.01)
+                        .range(0.0..=1.0)
+                        .custom_formatter(|value, _| format!("{:.0}%", value * 100.0))
+                        .custom_parser(|text| {
+                            text.trim_end_matches('%')
+                                .trim()
+                                .parse::<f64>()
+                                .ok()
+                                .map(|percentage| percentage / 100.0)
+                        }),
+                );
+                if response.lost_focus() || response.drag_stopped() {
+                    puc.edited = true;
+                }
+                ui.label("Bar height:");
+                let response =
+                    ui.add(egui::DragValue::new(&mut style.bar_height).range(0.0..=f32::MAX));
+                if response.lost_focus() || response.drag_stopped() {
+                    puc.edited = true;
+                }
+                ui.end_row();
+
+                if puc.edited {
+                    properties_edited = true;
+                }
+            });
         });
 
+        if properties_edited {
+            // only add edit when the properties actually changed
+            if self.before_edit != movie.properties {
+                edit = Some(MovieEdit::EditMovieProperties(MoviePropertiesEdit {
+                    before: self.before_edit.clone(),
+                    after: movie.properties.clone(),
+                }));
+            }
+        }
+
         edit
     }
 }
@@ -175,16 +342,52 @@ impl MoviePropertiesPanel {
 pub struct SymbolPropertiesPanel {
     pub symbol_index: SymbolIndex,
     pub before_edit: SymbolProperties,
+    // copying a file chosen with "Browse..." failed
+    browse_error: Option<String>,
+    // reading the font for the glyph preview failed, it is only read again when the file changes
+    font_load_error: Option<FontLoadError>,
+}
+struct FontLoadError {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    message: String,
 }
 pub enum SymbolProperties {
     Bitmap(BitmapProperties),
     MovieClip(MovieClipProperties),
     Font(FlitsFont),
+    Sound(FlitsSound),
+    StaticText(StaticTextProperties),
 }
 impl SymbolPropertiesPanel {
-    pub fn do_ui(&mut self, movie: &mut Movie, ui: &mut egui::Ui) -> Option<MovieEdit> {
+    pub fn do_ui(
+        &mut self,
+        movie: &mut Movie,
+        ui: &mut egui::Ui,
+        directory: &Path,
+    ) -> Option<MovieEdit> {
         let mut edit1: Option<MovieEdit> = None;
 
+        let used_characters = match &movie.symbols[self.symbol_index] {
+            Symbol::Font(_) => movie.collect_used_characters(self.symbol_index),
+            _ => HashSet::new(),
+        };
+        // the sounds movieclips can start and stop
+        let sounds: Vec<(SymbolIndex, String)> = movie
+            .symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, symbol)| matches!(symbol, Symbol::Sound(_)))
+            .map(|(sound_index, symbol)| (sound_index, symbol.name()))
+            .collect();
+        // the fonts static text can use
+        let fonts: Vec<(SymbolIndex, String)> = movie
+            .symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, symbol)| matches!(symbol, Symbol::Font(_)))
+            .map(|(font_index, symbol)| (font_index, symbol.name()))
+            .collect();
         let symbol = &mut movie.symbols[self.symbol_index];
         ui.horizontal(|ui| {
             ui.heading(format!("{} properties", symbol.type_name()));
@@ -197,15 +400,21 @@ impl SymbolPropertiesPanel {
                             symbol: symbol.clone_without_cache(),
                             // TODO: actually set this
                             remove_place_symbol_edits: vec![],
+                            fallback_spans: vec![],
+                            removed_sound_actions: vec![],
+                            static_texts_without_font: vec![],
+                            removed_preloader_graphic: false,
                         }));
                     }
                 },
             );
         });
         let edit2 = match symbol {
-            Symbol::Bitmap(bitmap) => self.bitmap_ui(bitmap, ui),
-            Symbol::MovieClip(movieclip) => self.movieclip_ui(movieclip, ui),
-            Symbol::Font(font) => self.font_ui(font, ui),
+            Symbol::Bitmap(bitmap) => self.bitmap_ui(bitmap, directory, ui),
+            Symbol::MovieClip(movieclip) => self.movieclip_ui(movieclip, &sounds, ui),
+            Symbol::Font(font) => self.font_ui(font, &used_characters, directory, ui),
+            Symbol::Sound(sound) => self.sound_ui(sound, ui),
+            Symbol::StaticText(static_text) => self.static_text_ui(static_text, &fonts, ui),
         };
         if edit1.is_some() {
             edit1
@@ -214,7 +423,12 @@ impl SymbolPropertiesPanel {
         }
     }
 
-    fn bitmap_ui(&self, bitmap: &mut Bitmap, ui: &mut egui::Ui) -> Option<MovieEdit> {
+    fn bitmap_ui(
+        &mut self,
+        bitmap: &mut Bitmap,
+        directory: &Path,
+        ui: &mut egui::Ui,
+    ) -> Option<MovieEdit> {
         let mut edit: Option<MovieEdit> = None;
         let mut edited = false;
         egui::Grid::new(format!("bitmap_{}_properties_grid", self.symbol_index)).show(ui, |ui| {
@@ -229,18 +443,46 @@ impl SymbolPropertiesPanel {
             ui.end_row();
 
             ui.label("Path:");
-            let mut path_text_edit = egui::TextEdit::singleline(&mut bitmap.properties.path)
-                .min_size(Vec2::new(200.0, 0.0));
-            if let BitmapCacheStatus::Invalid(_) = &bitmap.cache {
-                path_text_edit = path_text_edit.text_color(ui.style().visuals.error_fg_color);
-            }
-            let response = ui.add(path_text_edit);
-            if response.lost_focus() {
-                edited = true;
-            }
+            ui.horizontal(|ui| {
+                let mut path_text = egui::RichText::new(&bitmap.properties.path);
+                if let BitmapCacheStatus::Invalid(_) = &bitmap.cache {
+                    path_text = path_text.color(ui.style().visuals.error_fg_color);
+                }
+                ui.label(path_text);
+                if ui.button("Browse...").clicked() {
+                    if let Some(chosen_path) = rfd::FileDialog::new()
+                        .add_filter("Images", &["png", "jpg", "jpeg", "gif"])
+                        .set_directory(directory.join("assets"))
+                        .set_title("Choose an image")
+                        .pick_file()
+                    {
+                        match import_asset_file(directory, &chosen_path) {
+                            Ok(path) => {
+                                bitmap.properties.path = path;
+                                self.browse_error = None;
+                                edited = true;
+                            }
+                            Err(err) => {
+                                self.browse_error = Some(format!(
+                                    "Unable to copy {}: {}",
+                                    chosen_path.display(),
+                                    err
+                                ))
+                            }
+                        }
+                    }
+                }
+            });
             ui.end_row();
         });
 
+        let response = ui
+            .checkbox(&mut bitmap.properties.do_not_export, "Do not export")
+            .on_hover_text("Leave this bitmap out of the exported swf");
+        if response.changed() {
+            edited = true;
+        }
+
         let mut has_animation = bitmap.properties.animation.is_some();
         let response = ui.checkbox(&mut has_animation, "Animated");
         if response.changed() {
@@ -290,9 +532,34 @@ impl SymbolPropertiesPanel {
             });
         }
 
-        if let BitmapCacheStatus::Invalid(error) = &bitmap.cache {
-            ui.colored_label(ui.style().visuals.error_fg_color, "Error:");
+        if let Some(error) = &self.browse_error {
             ui.colored_label(ui.style().visuals.error_fg_color, error);
+        } else if let BitmapCacheStatus::Invalid(error) = &bitmap.cache {
+            ui.colored_label(ui.style().visuals.error_fg_color, "Error:");
+            ui.colored_label(ui.style().visuals.error_fg_color, error.to_string());
+            // the browse button next to the path can fix most of these
+            ui.label(match error {
+                BitmapLoadError::FileNotFound(_) => {
+                    "Did you move your assets? Use 'Browse...' to choose the new location."
+                }
+                BitmapLoadError::UnsupportedFormat(_) => {
+                    "Convert the image to png or choose another image."
+                }
+                BitmapLoadError::DecodeError(_) => {
+                    "The file might be damaged, try saving it again in an image editor."
+                }
+                BitmapLoadError::PermissionDenied(_) => {
+                    "Make sure the file can be read by your user and try again."
+                }
+            });
+            let can_retry = matches!(
+                error,
+                BitmapLoadError::FileNotFound(_) | BitmapLoadError::PermissionDenied(_)
+            );
+            if can_retry && ui.button("Retry").clicked() {
+                // the stage creates the bitmap handle when it sees the cached image
+                bitmap.cache_image(directory);
+            }
         } else {
             // add an empty row so the amount of rows is always the same
             // otherwise the height of the panel will only be updated on the next redraw
@@ -314,7 +581,12 @@ impl SymbolPropertiesPanel {
         edit
     }
 
-    fn movieclip_ui(&self, movieclip: &mut MovieClip, ui: &mut egui::Ui) -> Option<MovieEdit> {
+    fn movieclip_ui(
+        &self,
+        movieclip: &mut MovieClip,
+        sounds: &[(SymbolIndex, String)],
+        ui: &mut egui::Ui,
+    ) -> Option<MovieEdit> {
         let mut edit: Option<MovieEdit> = None;
 
         egui::Grid::new(format!("movieclip_{}_properties_grid", self.symbol_index)).show(
@@ -342,6 +614,94 @@ impl SymbolPropertiesPanel {
                 }
                 ui.end_row();
 
+                ui.label("");
+                let response = ui
+                    .checkbox(&mut movieclip.properties.do_not_export, "Do not export")
+                    .on_hover_text("Leave this movieclip out of the exported swf");
+                if response.changed() {
+                    edited = true;
+                }
+                ui.end_row();
+
+                ui.label("Sounds:");
+                ui.add_enabled_ui(!sounds.is_empty(), |ui| {
+                    if ui
+                        .button("Add sound action")
+                        .on_disabled_hover_text("Add an mp3 or wav file to the assets directory")
+                        .clicked()
+                    {
+                        movieclip.properties.sound_actions.push(SoundAction {
+                            sound_index: sounds[0].0,
+                            action: SoundActionType::Start,
+                        });
+                        edited = true;
+                    }
+                });
+                ui.end_row();
+                let mut removed_sound_action_index = None;
+                for (sound_action_index, sound_action) in
+                    movieclip.properties.sound_actions.iter_mut().enumerate()
+                {
+                    ui.label("");
+                    ui.horizontal(|ui| {
+                        ui.push_id(sound_action_index, |ui| {
+                            egui::ComboBox::from_id_salt("sound_action_type")
+                                .selected_text(format!("{:?}", sound_action.action))
+                                .show_ui(ui, |ui| {
+                                    for action in [
+                                        SoundActionType::Start,
+                                        SoundActionType::StartIfNotPlaying,
+                                        SoundActionType::Stop,
+                                    ] {
+                                        let text = format!("{:?}", action);
+                                        if ui
+                                            .selectable_value(
+                                                &mut sound_action.action,
+                                                action,
+                                                text,
+                                            )
+                                            .changed()
+                                        {
+                                            edited = true;
+                                        }
+                                    }
+                                });
+                            let sound_name = sounds
+                                .iter()
+                                .find(|(sound_index, _)| *sound_index == sound_action.sound_index)
+                                .map(|(_, name)| name.as_str())
+                                .unwrap_or("Missing sound");
+                            egui::ComboBox::from_id_salt("sound_action_sound")
+                                .selected_text(sound_name)
+                                .show_ui(ui, |ui| {
+                                    for (sound_index, name) in sounds {
+                                        if ui
+                                            .selectable_value(
+                                                &mut sound_action.sound_index,
+                                                *sound_index,
+                                                name,
+                                            )
+                                            .changed()
+                                        {
+                                            edited = true;
+                                        }
+                                    }
+                                });
+                            if ui.button("Remove").clicked() {
+                                removed_sound_action_index = Some(sound_action_index);
+                            }
+                        });
+                    });
+                    ui.end_row();
+                }
+                if let Some(sound_action_index) = removed_sound_action_index {
+                    movieclip
+                        .properties
+                        .sound_actions
+                        .remove(sound_action_index);
+                    edited = true;
+                }
+
                 let SymbolProperties::MovieClip(before_edit) = &self.before_edit else {
                     panic!("before_edit is not a movieclip");
                 };
@@ -360,7 +720,126 @@ impl SymbolPropertiesPanel {
         edit
     }
 
-    fn font_ui(&self, font: &mut FlitsFont, ui: &mut egui::Ui) -> Option<MovieEdit> {
+    fn sound_ui(&self, sound: &mut FlitsSound, ui: &mut egui::Ui) -> Option<MovieEdit> {
+        let mut edit: Option<MovieEdit> = None;
+        let mut edited = false;
+        egui::Grid::new(format!("sound_{}_properties_grid", self.symbol_index)).show(ui, |ui| {
+            ui.label("Name:");
+            let response = ui
+                .add(egui::TextEdit::singleline(&mut sound.name).min_size(Vec2::new(200.0, 0.0)))
+                .on_hover_text("Play the sound from code with attachSound using this name");
+            if response.lost_focus() {
+                edited = true;
+            }
+            ui.end_row();
+
+            ui.label("Path:");
+            let response =
+                ui.add(egui::TextEdit::singleline(&mut sound.path).min_size(Vec2::new(200.0, 0.0)));
+            if response.lost_focus() {
+                edited = true;
+            }
+            ui.end_row();
+        });
+
+        let SymbolProperties::Sound(before_edit) = &self.before_edit else {
+            panic!("before_edit is not a sound");
+        };
+        if edited && before_edit != sound {
+            edit = Some(MovieEdit::EditSoundProperties(SoundPropertiesEdit {
+                editing_symbol_index: self.symbol_index,
+                before: before_edit.clone(),
+                after: sound.clone(),
+            }));
+        }
+
+        edit
+    }
+
+    fn static_text_ui(
+        &self,
+        static_text: &mut StaticTextProperties,
+        fonts: &[(SymbolIndex, String)],
+        ui: &mut egui::Ui,
+    ) -> Option<MovieEdit> {
+        let mut edit: Option<MovieEdit> = None;
+        let SymbolProperties::StaticText(before_edit) = &self.before_edit else {
+            panic!("before_edit is not static text");
+        };
+        let mut puc = PropertyUiContext::new();
+        egui::Grid::new(format!("static_text_{}_properties_grid", self.symbol_index)).show(
+            ui,
+            |ui| {
+                puc.text_value(ui, "Name:", &mut static_text.name);
+                ui.end_row();
+
+                puc.text_value_multiline(ui, "Text:", &mut static_text.text);
+                ui.end_row();
+
+                let font_name = fonts
+                    .iter()
+                    .find(|(font_index, _)| Some(*font_index) == static_text.font_index)
+                    .map(|(_, name)| name.clone())
+                    .unwrap_or("Missing font".into());
+                // the combobox doesn't report changes made inside it
+                let mut font_changed = false;
+                puc.combobox(ui, "Font:", font_name, |ui| {
+                    for (font_index, name) in fonts {
+                        if ui
+                            .selectable_value(&mut static_text.font_index, Some(*font_index), name)
+                            .changed()
+                        {
+                            font_changed = true;
+                        }
+                    }
+                });
+                if font_changed {
+                    puc.edited = true;
+                }
+                ui.end_row();
+
+                ui.label("");
+                ui.horizontal(|ui| {
+                    puc.drag_value(ui, "Size:", &mut static_text.size);
+                    puc.color_value(
+                        ui,
+                        "Color:",
+                        &mut static_text.color,
+                        &before_edit.color,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+                });
+                ui.end_row();
+
+                ui.label("");
+                ui.horizontal(|ui| {
+                    puc.drag_value(ui, "X:", &mut static_text.x);
+                    puc.drag_value(ui, "Y:", &mut static_text.y);
+                });
+                ui.end_row();
+            },
+        );
+
+        if puc.edited && before_edit != static_text {
+            edit = Some(MovieEdit::EditStaticTextProperties(
+                StaticTextPropertiesEdit {
+                    editing_symbol_index: self.symbol_index,
+                    before: before_edit.clone(),
+                    after: static_text.clone(),
+                },
+            ));
+        }
+
+        edit
+    }
+
+    fn font_ui(
+        &mut self,
+        font: &mut FlitsFont,
+        used_characters: &HashSet<char>,
+        directory: &Path,
+        ui: &mut egui::Ui,
+    ) -> Option<MovieEdit> {
         let mut edit: Option<MovieEdit> = None;
         let mut puc = PropertyUiContext::new();
         egui::Grid::new(format!("font_{}_properties_grid", self.symbol_index)).show(ui, |ui| {
@@ -376,8 +855,44 @@ impl SymbolPropertiesPanel {
                     &mut font.characters.additional_characters,
                 );
             });
+            ui.end_row();
+
+            ui.label("");
+            puc.bool_value(
+                ui,
+                "Only export characters used in text",
+                &mut font.characters.only_used,
+            );
+            ui.end_row();
         });
 
+        let font_characters: HashSet<char> = font.characters.characters().chars().collect();
+        let mut missing_characters: Vec<char> = used_characters
+            .difference(&font_characters)
+            .copied()
+            .collect();
+        missing_characters.sort();
+        // with only the used characters, the characters above are just for the editor
+        if !missing_characters.is_empty() && !font.characters.only_used {
+            ui.horizontal(|ui| {
+                ui.colored_label(
+                    ui.style().visuals.warn_fg_color,
+                    format!(
+                        "Used in text fields but not in the font: {}",
+                        missing_characters.iter().collect::<String>()
+                    ),
+                );
+                if ui.button("Auto-populate characters from text").clicked() {
+                    font.characters
+                        .additional_characters
+                        .extend(missing_characters.iter());
+                    puc.edited = true;
+                }
+            });
+        }
+
+        Self::glyph_preview_ui(font, directory, &mut self.font_load_error, ui);
+
         let SymbolProperties::Font(before_edit) = &self.before_edit else {
             panic!("before_edit is not a font");
         };
@@ -393,8 +908,82 @@ impl SymbolPropertiesPanel {
     }
 }
 
+impl SymbolPropertiesPanel {
+    // renders the characters of the font with the font itself, so you can see which glyphs are missing
+    fn glyph_preview_ui(
+        font: &FlitsFont,
+        directory: &Path,
+        font_load_error: &mut Option<FontLoadError>,
+        ui: &mut egui::Ui,
+    ) {
+        // egui fonts are identified by name, the path makes sure a new font is loaded when it changes
+        let font_name = format!("flits_font_{}", font.path);
+        let family = egui::FontFamily::Name(font_name.clone().into());
+        let is_loaded = ui.fonts(|fonts| fonts.families().contains(&family));
+        if !is_loaded {
+            let path = directory.join("assets").join(&font.path);
+            let modified = std::fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .ok();
+            if let Some(error) = font_load_error {
+                if error.path == path && error.modified == modified {
+                    ui.colored_label(ui.style().visuals.error_fg_color, &error.message);
+                    return;
+                }
+            }
+            match std::fs::read(&path) {
+                Ok(data) => {
+                    *font_load_error = None;
+                    ui.ctx().add_font(egui::epaint::text::FontInsert::new(
+                        &font_name,
+                        egui::FontData::from_owned(data),
+                        vec![egui::epaint::text::InsertFontFamily {
+                            family,
+                            priority: egui::epaint::text::FontPriority::Highest,
+                        }],
+                    ));
+                    // the font is available in the next frame
+                    ui.ctx().request_repaint();
+                }
+                Err(err) => {
+                    let message = format!("Unable to load font: {}", err);
+                    ui.colored_label(ui.style().visuals.error_fg_color, &message);
+                    *font_load_error = Some(FontLoadError {
+                        path,
+                        modified,
+                        message,
+                    });
+                }
+            }
+            return;
+        }
+
+        let font_id = egui::FontId::new(24.0, family);
+        ui.horizontal_wrapped(|ui| {
+            for character in font.characters.characters().chars() {
+                let has_glyph = ui.fonts(|fonts| fonts.has_glyph(&font_id, character));
+                let mut text = egui::RichText::new(character.to_string()).font(font_id.clone());
+                if !has_glyph {
+                    text = text.color(ui.style().visuals.error_fg_color);
+                }
+                egui::Frame::group(ui.style()).show(ui, |ui| {
+                    ui.set_min_size(Vec2::new(28.0, 28.0));
+                    ui.label(text)
+                        .on_hover_text(format!("U+{:04X}", character as u32));
+                });
+            }
+        });
+    }
+}
+
 pub struct PlacedSymbolPropertiesPanel {
+    pub placed_symbol_index: PlacedSymbolIndex,
     pub before_edit: PlaceSymbol,
+    // x_scale / y_scale at the time the lock was enabled
+    pub aspect_ratio_lock: Option<f64>,
+    // the position is the center, so by default scaling happens from the center
+    pub scale_from_corner: bool,
+    pub character_picker: Option<CharacterPickerWindow>,
 }
 impl PlacedSymbolPropertiesPanel {
     pub fn do_ui(
@@ -405,6 +994,31 @@ impl PlacedSymbolPropertiesPanel {
         placed_symbol_index: PlacedSymbolIndex,
     ) -> Option<MovieEdit> {
         ui.heading("Placed symbol properties");
+        let is_bitmap = matches!(
+            movie.symbols[movie.get_placed_symbols(editing_clip)[placed_symbol_index].symbol_index],
+            Symbol::Bitmap(_)
+        );
+        let local_bounds = Stage::local_bounds_of_placed_symbol(
+            movie,
+            &movie.get_placed_symbols(editing_clip)[placed_symbol_index],
+        );
+        let symbol_index = movie.get_placed_symbols(editing_clip)[placed_symbol_index].symbol_index;
+        let font_characters = match &movie.symbols[symbol_index] {
+            Symbol::Font(font) => font.characters.characters(),
+            _ => String::new(),
+        };
+        let exported_font_characters: HashSet<char> = movie
+            .exported_font_characters(symbol_index)
+            .chars()
+            .collect();
+        // the fonts text spans can use
+        let fonts: Vec<(SymbolIndex, String)> = movie
+            .symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, symbol)| matches!(symbol, Symbol::Font(_)))
+            .map(|(font_symbol_index, symbol)| (font_symbol_index, symbol.name()))
+            .collect();
         let placed_symbol = movie
             .get_placed_symbols_mut(editing_clip)
             .get_mut(placed_symbol_index)
@@ -413,6 +1027,10 @@ impl PlacedSymbolPropertiesPanel {
         let mut edit: Option<MovieEdit> = None;
         let mut transform_puc = PropertyUiContext::new();
         let mut puc = PropertyUiContext::new();
+        let transform_before = placed_symbol.transform.clone();
+        let x_scale_before = transform_before.x_scale;
+        let y_scale_before = transform_before.y_scale;
+        let mut scale_reset = false;
 
         egui::Grid::new(format!(
             "placed_symbol_{placed_symbol_index}_properties_grid"
@@ -427,11 +1045,102 @@ impl PlacedSymbolPropertiesPanel {
 
             transform_puc.drag_value(ui, "y", &mut placed_symbol.transform.y);
             transform_puc.drag_value(ui, "Y scale", &mut placed_symbol.transform.y_scale);
+            let mut locked = self.aspect_ratio_lock.is_some();
+            if ui
+                .toggle_value(&mut locked, if locked { "🔒" } else { "🔓" })
+                .on_hover_text("Lock aspect ratio")
+                .changed()
+            {
+                let transform = &placed_symbol.transform;
+                self.aspect_ratio_lock =
+                    if locked && transform.x_scale != 0.0 && transform.y_scale != 0.0 {
+                        Some(transform.x_scale / transform.y_scale)
+                    } else {
+                        None
+                    };
+            }
+            ui.end_row();
+
+            ui.label("");
+            if ui.button("Reset position").clicked() {
+                placed_symbol.transform.x = 0.0;
+                placed_symbol.transform.y = 0.0;
+                transform_puc.edited = true;
+            }
+            ui.label("");
+            if ui.button("Reset scale").clicked() {
+                placed_symbol.transform.x_scale = 1.0;
+                placed_symbol.transform.y_scale = 1.0;
+                transform_puc.edited = true;
+                scale_reset = true;
+            }
+            if ui.button("Reset transform").clicked() {
+                placed_symbol.transform = EditorTransform::default();
+                transform_puc.edited = true;
+                scale_reset = true;
+            }
+            ui.end_row();
+
+            ui.label("Scale mode:");
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.scale_from_corner, false, "From center");
+                ui.selectable_value(&mut self.scale_from_corner, true, "From corner");
+            });
             ui.end_row();
         });
 
+        if scale_reset {
+            // the lock keeps the reset scale instead of changing it back to the old aspect ratio
+            if self.aspect_ratio_lock.is_some() {
+                self.aspect_ratio_lock = Some(1.0);
+            }
+        } else if let Some(aspect_ratio) = self.aspect_ratio_lock {
+            let transform = &mut placed_symbol.transform;
+            if transform.x_scale != x_scale_before {
+                transform.y_scale = transform.x_scale / aspect_ratio;
+            } else if transform.y_scale != y_scale_before {
+                transform.x_scale = transform.y_scale * aspect_ratio;
+            }
+        }
+        // don't move it when the position was also changed, like when resetting the transform
+        let position_changed = placed_symbol.transform.x != transform_before.x
+            || placed_symbol.transform.y != transform_before.y;
+        if self.scale_from_corner && !position_changed {
+            if let Some(local_bounds) = local_bounds {
+                // move the symbol so the top left corner stays at the same position
+                let transform = &mut placed_symbol.transform;
+                transform.x += local_bounds.min_x * (x_scale_before - transform.x_scale);
+                transform.y += local_bounds.min_y * (y_scale_before - transform.y_scale);
+            }
+        }
+
+        if is_bitmap {
+            ui.horizontal(|ui| {
+                puc.bool_value(ui, "Smoothing", &mut placed_symbol.smoothing);
+                let pixel_snapping = &mut placed_symbol.pixel_snapping;
+                puc.combobox(
+                    ui,
+                    "Pixel snapping:",
+                    format!("{:?}", pixel_snapping),
+                    |ui| {
+                        ui.selectable_value(pixel_snapping, PixelSnappingMode::Never, "Never");
+                        ui.selectable_value(pixel_snapping, PixelSnappingMode::Always, "Always");
+                        ui.selectable_value(pixel_snapping, PixelSnappingMode::Auto, "Auto");
+                    },
+                );
+            });
+        }
+
         if let Some(text) = &mut placed_symbol.text {
-            self.text_ui(ui, &mut puc, text);
+            self.text_ui(
+                ui,
+                &mut puc,
+                text,
+                &font_characters,
+                &exported_font_characters,
+                symbol_index,
+                &fonts,
+            );
         }
 
         if transform_puc.edited {
@@ -474,7 +1183,16 @@ impl PlacedSymbolPropertiesPanel {
         edit
     }
 
-    fn text_ui(&self, ui: &mut egui::Ui, puc: &mut PropertyUiContext, text: &mut TextProperties) {
+    fn text_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        puc: &mut PropertyUiContext,
+        text: &mut TextProperties,
+        font_characters: &str,
+        exported_font_characters: &HashSet<char>,
+        font_symbol_index: SymbolIndex,
+        fonts: &[(SymbolIndex, String)],
+    ) {
         ui.heading("Text properties");
         ui.horizontal(|ui| {
             puc.drag_value(ui, "Width:", &mut text.width);
@@ -506,16 +1224,184 @@ impl PlacedSymbolPropertiesPanel {
             puc.bool_value(ui, "HTML", &mut text.is_html);
             puc.bool_value(ui, "Multiline", &mut text.is_multiline);
             puc.bool_value(ui, "Word wrap", &mut text.word_wrap);
+            puc.bool_value(ui, "Underline", &mut text.underline);
         });
         ui.end_row();
+        let text_edit_id = ui
+            .horizontal(|ui| {
+                let response = if text.is_multiline {
+                    puc.text_value_multiline(ui, "Text:", &mut text.text)
+                } else {
+                    puc.text_value(ui, "Text:", &mut text.text)
+                };
+                if ui.button("Insert character...").clicked() {
+                    self.character_picker = Some(CharacterPickerWindow::default());
+                }
+                ui.end_row();
+                response.id
+            })
+            .inner;
+
+        // spans replace the text and have their own fonts
+        if text.spans.is_empty() {
+            let mut is_in_tag = false;
+            let mut missing_characters: Vec<char> = text
+                .text
+                .chars()
+                .filter(|character| {
+                    // html tags aren't shown, so they don't need glyphs
+                    if text.is_html && *character == '<' {
+                        is_in_tag = true;
+                    } else if text.is_html && *character == '>' {
+                        is_in_tag = false;
+                        return false;
+                    }
+                    !is_in_tag
+                        && !character.is_control()
+                        && !exported_font_characters.contains(character)
+                })
+                .collect();
+            missing_characters.sort();
+            missing_characters.dedup();
+            if !missing_characters.is_empty() {
+                ui.colored_label(
+                    ui.style().visuals.warn_fg_color,
+                    format!(
+                        "Not in the exported font: {}",
+                        missing_characters.iter().collect::<String>()
+                    ),
+                );
+            }
+        }
+
+        self.spans_ui(ui, puc, text, font_symbol_index, fonts);
+
+        if let Some(character_picker) = &mut self.character_picker {
+            match character_picker.do_ui(ui.ctx(), font_characters) {
+                CharacterPickerWindowResult::NoAction => {}
+                CharacterPickerWindowResult::Insert(character) => {
+                    Self::insert_character(ui.ctx(), text_edit_id, &mut text.text, character);
+                    puc.edited = true;
+                }
+                CharacterPickerWindowResult::Close => self.character_picker = None,
+            }
+        }
+    }
+
+    fn spans_ui(
+        &self,
+        ui: &mut egui::Ui,
+        puc: &mut PropertyUiContext,
+        text: &mut TextProperties,
+        font_symbol_index: SymbolIndex,
+        fonts: &[(SymbolIndex, String)],
+    ) {
         ui.horizontal(|ui| {
-            if text.is_multiline {
-                puc.text_value_multiline(ui, "Text:", &mut text.text);
-            } else {
-                puc.text_value(ui, "Text:", &mut text.text);
+            ui.label("Spans:");
+            if ui.button("Add span").clicked() {
+                text.spans.push(TextSpan {
+                    text: String::new(),
+                    font_symbol_index,
+                    size: text.size,
+                    color: text.color.clone(),
+                    underline: text.underline,
+                });
+                puc.edited = true;
+            }
+            if !text.spans.is_empty() {
+                ui.weak("The spans are used instead of the text, size and color");
             }
-            ui.end_row();
         });
+        let spans_before_edit = &self.before_edit.text.as_ref().unwrap().spans;
+        let mut removed_span_index = None;
+        for (span_index, span) in text.spans.iter_mut().enumerate() {
+            ui.push_id(span_index, |ui| {
+                ui.horizontal(|ui| {
+                    puc.text_value(ui, "Text:", &mut span.text);
+                    ui.label("Font:");
+                    let font_name = fonts
+                        .iter()
+                        .find(|(symbol_index, _)| *symbol_index == span.font_symbol_index)
+                        .map(|(_, name)| name.as_str())
+                        .unwrap_or("Missing font");
+                    egui::ComboBox::from_id_salt("span_font")
+                        .selected_text(font_name)
+                        .show_ui(ui, |ui| {
+                            for (symbol_index, name) in fonts {
+                                if ui
+                                    .selectable_value(
+                                        &mut span.font_symbol_index,
+                                        *symbol_index,
+                                        name,
+                                    )
+                                    .changed()
+                                {
+                                    puc.edited = true;
+                                }
+                            }
+                        });
+                    puc.drag_value(ui, "Size:", &mut span.size);
+                    let color_before_edit = spans_before_edit
+                        .get(span_index)
+                        .map(|span_before_edit| span_before_edit.color.clone())
+                        .unwrap_or_else(|| span.color.clone());
+                    puc.color_value(
+                        ui,
+                        "Color:",
+                        &mut span.color,
+                        &color_before_edit,
+                        egui::color_picker::Alpha::Opaque,
+                    );
+                    puc.bool_value(ui, "Underline", &mut span.underline);
+                    if ui.button("Remove span").clicked() {
+                        removed_span_index = Some(span_index);
+                    }
+                });
+            });
+        }
+        if let Some(span_index) = removed_span_index {
+            text.spans.remove(span_index);
+            puc.edited = true;
+        }
+    }
+
+    // replaces the selection of the text field, or appends when the text field was never focused
+    fn insert_character(
+        egui_ctx: &egui::Context,
+        text_edit_id: egui::Id,
+        text: &mut String,
+        character: char,
+    ) {
+        let mut state = egui::TextEdit::load_state(egui_ctx, text_edit_id);
+        let char_count = text.chars().count();
+        let (start, end) = match state.as_ref().and_then(|state| state.cursor.char_range()) {
+            Some(range) => {
+                let start = range.primary.index.min(range.secondary.index);
+                let end = range.primary.index.max(range.secondary.index);
+                (start.min(char_count), end.min(char_count))
+            }
+            None => (char_count, char_count),
+        };
+        let byte_index = |char_index: usize| {
+            text.char_indices()
+                .nth(char_index)
+                .map(|(byte_index, _)| byte_index)
+                .unwrap_or(text.len())
+        };
+        let byte_range = byte_index(start)..byte_index(end);
+        text.replace_range(byte_range, &character.to_string());
+
+        // put the cursor after the inserted character so multiple characters can be inserted in a row
+        if let Some(state) = &mut state {
+            state
+                .cursor
+                .set_char_range(Some(egui::text::CCursorRange::one(
+                    egui::text::CCursor::new(start + 1),
+                )));
+        }
+        if let Some(state) = state {
+            egui::TextEdit::store_state(egui_ctx, text_edit_id, state);
+        }
     }
 }
 struct PropertyUiContext {
@@ -532,19 +1418,26 @@ impl PropertyUiContext {
             self.edited = true;
         }
     }
-    fn text_value(&mut self, ui: &mut egui::Ui, label: &str, value: &mut String) {
+    fn text_value(&mut self, ui: &mut egui::Ui, label: &str, value: &mut String) -> egui::Response {
         ui.label(label);
         let response = ui.add(egui::TextEdit::singleline(value).min_size(Vec2::new(200.0, 0.0)));
         if response.lost_focus() {
             self.edited = true;
         }
+        response
     }
-    fn text_value_multiline(&mut self, ui: &mut egui::Ui, label: &str, value: &mut String) {
+    fn text_value_multiline(
+        &mut self,
+        ui: &mut egui::Ui,
+        label: &str,
+        value: &mut String,
+    ) -> egui::Response {
         ui.label(label);
         let response = ui.add(egui::TextEdit::multiline(value).min_size(Vec2::new(200.0, 0.0)));
         if response.lost_focus() {
             self.edited = true;
         }
+        response
     }
     fn color_value(
         &mut self,
@@ -599,3 +1492,38 @@ impl MultiSelectionPropertiesPanel {
         None
     }
 }
+
+// returns the path relative to the assets directory, files outside of it are copied into it
+// the old file isn't removed because other symbols might still use it
+pub(super) fn import_asset_file(
+    directory: &Path,
+    chosen_path: &Path,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let asset_directory = directory.join("assets");
+    if let (Ok(canonical_asset_directory), Ok(canonical_chosen_path)) =
+        (asset_directory.canonicalize(), chosen_path.canonicalize())
+    {
+        if let Ok(relative_path) = canonical_chosen_path.strip_prefix(&canonical_asset_directory) {
+            return Ok(path_with_forward_slashes(relative_path));
+        }
+    }
+
+    let file_stem = chosen_path
+        .file_stem()
+        .ok_or("The file has no name")?
+        .to_string_lossy();
+    let extension = chosen_path
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+    // don't overwrite a different file with the same name
+    let mut file_name = format!("{}{}", file_stem, extension);
+    let mut number = 2;
+    while asset_directory.join(&file_name).exists() {
+        file_name = format!("{} ({}){}", file_stem, number, extension);
+        number += 1;
+    }
+    std::fs::create_dir_all(&asset_directory)?;
+    std::fs::copy(chosen_path, asset_directory.join(&file_name))?;
+    Ok(file_name)
+}
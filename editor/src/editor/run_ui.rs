@@ -1,26 +1,98 @@
+use std::collections::VecDeque;
+
 use ansi_parser::{AnsiParser, AnsiSequence};
 use egui::{Color32, FontFamily, RichText};
+use flits_core::run::RunningMovie;
+
+// older lines are removed so long running movies don't use more and more memory
+const MAX_LINES: usize = 1000;
 
 #[derive(PartialEq, Eq)]
 enum RunTab {
     Editor,
     Output,
 }
+#[derive(PartialEq, Eq)]
+enum RunState {
+    Running,
+    // closed normally or stopped by the user
+    Stopped,
+    // the exit code is missing when the process was killed by a signal
+    Crashed(Option<i32>),
+}
+
+pub(crate) enum RunUiAction {
+    None,
+    Restart,
+    Close,
+}
+
 pub(crate) struct RunUi {
     tab: RunTab,
-    lines: Vec<String>,
+    lines: VecDeque<String>,
+    // stop following new output while the user is reading older output
+    user_scrolled_up: bool,
+    state: RunState,
+    // events from a previous run are ignored after restarting
+    run_id: u64,
+    running_movie: RunningMovie,
 }
 impl RunUi {
-    pub fn new() -> Self {
+    pub fn new(run_id: u64, running_movie: RunningMovie) -> Self {
         RunUi {
             tab: RunTab::Editor,
-            lines: vec![],
+            lines: VecDeque::new(),
+            user_scrolled_up: false,
+            state: RunState::Running,
+            run_id,
+            running_movie,
         }
     }
-    pub fn do_ui(&mut self, egui_ctx: &egui::Context) {
+    // keeps the selected tab, but starts with empty output
+    pub fn restart(&mut self, run_id: u64, running_movie: RunningMovie) {
+        self.lines.clear();
+        self.user_scrolled_up = false;
+        self.state = RunState::Running;
+        self.run_id = run_id;
+        self.running_movie = running_movie;
+    }
+    pub fn stop(&mut self) {
+        if self.state != RunState::Running {
+            return;
+        }
+        if let Err(err) = self.running_movie.stop() {
+            eprintln!("Unable to stop Ruffle: {}", err);
+        }
+        self.state = RunState::Stopped;
+    }
+    pub fn do_ui(&mut self, egui_ctx: &egui::Context) -> RunUiAction {
+        let mut action = RunUiAction::None;
         egui::TopBottomPanel::top("run_ui_bar").show(egui_ctx, |ui| {
             ui.horizontal(|ui| {
-                ui.heading("Running...");
+                ui.heading(match self.state {
+                    RunState::Running => "Running...".to_string(),
+                    RunState::Stopped => "Stopped".to_string(),
+                    RunState::Crashed(Some(exit_code)) => {
+                        format!("Crashed (exit code {})", exit_code)
+                    }
+                    RunState::Crashed(None) => "Crashed".to_string(),
+                });
+                if self.state == RunState::Running {
+                    if ui.button("Stop").clicked() {
+                        self.stop();
+                    }
+                    if ui.button("Restart").clicked() {
+                        action = RunUiAction::Restart;
+                    }
+                } else {
+                    if ui.button("Run again").clicked() {
+                        action = RunUiAction::Restart;
+                    }
+                    if ui.button("Close").clicked() {
+                        action = RunUiAction::Close;
+                    }
+                }
+                ui.separator();
                 if ui
                     .selectable_label(self.tab == RunTab::Editor, "Editor")
                     .clicked()
@@ -38,6 +110,7 @@ impl RunUi {
         if self.tab == RunTab::Output {
             self.show_ouput_tab(egui_ctx);
         }
+        action
     }
     pub fn is_editor_visible(&self) -> bool {
         self.tab == RunTab::Editor
@@ -47,65 +120,99 @@ impl RunUi {
             let text_style = egui::TextStyle::Body;
             let row_height = ui.text_style_height(&text_style);
             let num_rows = self.lines.len();
-            egui::ScrollArea::vertical()
-                .auto_shrink(false)
-                .stick_to_bottom(true)
-                .scroll(true)
-                .show_rows(ui, row_height, num_rows, |ui, row_range| {
-                    ui.spacing_mut().item_spacing = egui::Vec2::ZERO;
-                    for row in row_range {
-                        // TODO: caching of parsing
-                        ui.horizontal(|ui| {
-                            let mut color = Color32::WHITE;
-                            for output in self.lines[row].ansi_parse() {
-                                match output {
-                                    ansi_parser::Output::TextBlock(text) => {
-                                        ui.label(
-                                            RichText::new(text)
-                                                .color(color)
-                                                .family(FontFamily::Monospace),
-                                        );
-                                    }
-                                    ansi_parser::Output::Escape(seq) => match seq {
-                                        AnsiSequence::SetGraphicsMode(mode) => {
-                                            //ui.label(format!("({:?})", mode));
-                                            // only codes that i've seen Ruffle use are implemented
-                                            match mode[0] {
-                                                0 => {
-                                                    // reset
-                                                    color = Color32::WHITE;
-                                                }
-                                                2 => {
-                                                    // dim
-                                                    color = Color32::GRAY;
-                                                }
-                                                31 => {
-                                                    // foreground red
-                                                    color = Color32::RED;
-                                                }
-                                                32 => {
-                                                    // foreground green
-                                                    color = Color32::GREEN;
-                                                }
-                                                33 => {
-                                                    // foreground yellow
-                                                    color = Color32::YELLOW;
-                                                }
-                                                _ => {}
+            let mut scroll_area = egui::ScrollArea::vertical().auto_shrink(false);
+            if !self.user_scrolled_up {
+                // this is clamped to the bottom
+                scroll_area = scroll_area.vertical_scroll_offset(num_rows as f32 * row_height);
+            }
+            let output = scroll_area.show_rows(ui, row_height, num_rows, |ui, row_range| {
+                ui.spacing_mut().item_spacing = egui::Vec2::ZERO;
+                for row in row_range {
+                    // TODO: caching of parsing
+                    ui.horizontal(|ui| {
+                        let mut color = Color32::WHITE;
+                        for output in self.lines[row].ansi_parse() {
+                            match output {
+                                ansi_parser::Output::TextBlock(text) => {
+                                    ui.label(
+                                        RichText::new(text)
+                                            .color(color)
+                                            .family(FontFamily::Monospace),
+                                    );
+                                }
+                                ansi_parser::Output::Escape(seq) => match seq {
+                                    AnsiSequence::SetGraphicsMode(mode) => {
+                                        //ui.label(format!("({:?})", mode));
+                                        // only codes that i've seen Ruffle use are implemented
+                                        match mode[0] {
+                                            0 => {
+                                                // reset
+                                                color = Color32::WHITE;
+                                            }
+                                            2 => {
+                                                // dim
+                                                color = Color32::GRAY;
+                                            }
+                                            31 => {
+                                                // foreground red
+                                                color = Color32::RED;
+                                            }
+                                            32 => {
+                                                // foreground green
+                                                color = Color32::GREEN;
+                                            }
+                                            33 => {
+                                                // foreground yellow
+                                                color = Color32::YELLOW;
                                             }
+                                            _ => {}
                                         }
-                                        _ => {}
-                                    },
-                                }
+                                    }
+                                    _ => {}
+                                },
                             }
-                        });
-                    }
-                });
+                        }
+                    });
+                }
+            });
+            let max_offset = (output.content_size.y - output.inner_rect.height()).max(0.0);
+            self.user_scrolled_up = output.state.offset.y < max_offset - row_height / 2.0;
         });
     }
+    pub fn run_id(&self) -> u64 {
+        self.run_id
+    }
     pub fn add_line(&mut self, line: String) {
-        // TODO: use circular buffer?
-        self.lines.push(line);
+        if self.lines.len() >= MAX_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+    // returns false if the output is from a previous run
+    pub fn on_command_output(&mut self, run_id: u64, line: String) -> bool {
+        if run_id != self.run_id {
+            return false;
+        }
+        self.add_line(line);
+        true
+    }
+    // returns false if the event is from a previous run
+    pub fn on_ruffle_closed(&mut self, run_id: u64, exit_code: Option<i32>) -> bool {
+        if run_id != self.run_id {
+            return false;
+        }
+        self.add_line(match exit_code {
+            Some(exit_code) => format!("Ruffle exited with code {}", exit_code),
+            None => "Ruffle was terminated".to_string(),
+        });
+        // stopping kills the process, that isn't a crash
+        if self.state == RunState::Running {
+            self.state = match exit_code {
+                Some(0) => RunState::Stopped,
+                exit_code => RunState::Crashed(exit_code),
+            };
+        }
+        true
     }
     pub fn needs_redraw_after_new_line(&self) -> bool {
         self.tab == RunTab::Output
@@ -0,0 +1,200 @@
+use egui::{text::LayoutJob, Color32, FontId, TextFormat, Vec2};
+use flits_core::{FrameScript, Movie};
+
+use crate::edit::{MovieEdit, RootScriptsEdit};
+
+const KEYWORDS: &[&str] = &[
+    "break",
+    "case",
+    "class",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "extends",
+    "false",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "new",
+    "null",
+    "private",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "true",
+    "typeof",
+    "undefined",
+    "var",
+    "void",
+    "while",
+    "with",
+];
+const KEYWORD_COLOR: Color32 = Color32::from_rgb(86, 156, 214);
+const STRING_COLOR: Color32 = Color32::from_rgb(206, 145, 120);
+const COMMENT_COLOR: Color32 = Color32::from_rgb(106, 153, 85);
+
+pub struct ScriptsWindow {
+    // the scripts as they are in the movie, to know when to create an edit
+    before_edit: Vec<FrameScript>,
+    scripts: Vec<FrameScript>,
+    selected_script_index: Option<usize>,
+}
+impl ScriptsWindow {
+    pub fn new(movie: &Movie) -> Self {
+        ScriptsWindow {
+            before_edit: movie.root_scripts.clone(),
+            scripts: movie.root_scripts.clone(),
+            selected_script_index: None,
+        }
+    }
+
+    pub fn do_ui(&mut self, egui_ctx: &egui::Context, movie: &Movie) -> ScriptsWindowResult {
+        // the scripts were changed outside of this window, for example by undoing
+        if movie.root_scripts != self.before_edit {
+            self.before_edit = movie.root_scripts.clone();
+            self.scripts = movie.root_scripts.clone();
+        }
+        if self
+            .selected_script_index
+            .is_some_and(|script_index| script_index >= self.scripts.len())
+        {
+            self.selected_script_index = None;
+        }
+
+        let mut result = ScriptsWindowResult::NoAction;
+        let mut is_window_open = true;
+        let mut edited = false;
+        egui::Window::new("Root scripts")
+            .open(&mut is_window_open)
+            .default_size(Vec2::new(600.0, 400.0))
+            .show(egui_ctx, |ui| {
+                ui.horizontal_top(|ui| {
+                    ui.vertical(|ui| {
+                        ui.set_width(150.0);
+                        for (script_index, script) in self.scripts.iter().enumerate() {
+                            let is_selected = self.selected_script_index == Some(script_index);
+                            if ui.selectable_label(is_selected, &script.name).clicked() {
+                                self.selected_script_index = Some(script_index);
+                            }
+                        }
+                        if ui.button("Add script").clicked() {
+                            self.scripts.push(FrameScript {
+                                name: format!("Script {}", self.scripts.len() + 1),
+                                code: String::new(),
+                            });
+                            self.selected_script_index = Some(self.scripts.len() - 1);
+                            edited = true;
+                        }
+                    });
+                    ui.separator();
+                    ui.vertical(|ui| {
+                        let Some(script_index) = self.selected_script_index else {
+                            ui.label("Select a script to edit it.");
+                            return;
+                        };
+                        let script = &mut self.scripts[script_index];
+                        let mut is_removed = false;
+                        ui.horizontal(|ui| {
+                            ui.label("Name:");
+                            if ui.text_edit_singleline(&mut script.name).lost_focus() {
+                                edited = true;
+                            }
+                            if ui.button("Remove script").clicked() {
+                                is_removed = true;
+                            }
+                        });
+                        if is_removed {
+                            self.scripts.remove(script_index);
+                            self.selected_script_index = None;
+                            edited = true;
+                            return;
+                        }
+                        ui.weak("Runs on the root timeline, use _root instead of this.");
+                        let mut layouter = |ui: &egui::Ui, code: &str, wrap_width: f32| {
+                            let mut layout_job = highlight(code);
+                            layout_job.wrap.max_width = wrap_width;
+                            ui.fonts(|fonts| fonts.layout_job(layout_job))
+                        };
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            let response = ui.add(
+                                egui::TextEdit::multiline(&mut self.scripts[script_index].code)
+                                    .code_editor()
+                                    .desired_width(f32::INFINITY)
+                                    .desired_rows(16)
+                                    .layouter(&mut layouter),
+                            );
+                            if response.lost_focus() {
+                                edited = true;
+                            }
+                        });
+                    });
+                });
+            });
+
+        if edited && self.scripts != self.before_edit {
+            result = ScriptsWindowResult::Edit(MovieEdit::EditRootScripts(RootScriptsEdit {
+                before: self.before_edit.clone(),
+                after: self.scripts.clone(),
+            }));
+            self.before_edit = self.scripts.clone();
+        }
+        if !is_window_open {
+            result = ScriptsWindowResult::Close;
+        }
+        result
+    }
+}
+
+// colors keywords, strings and comments
+fn highlight(code: &str) -> LayoutJob {
+    let mut layout_job = LayoutJob::default();
+    let font_id = FontId::monospace(12.0);
+    let mut append = |text: &str, color: Color32| {
+        layout_job.append(text, 0.0, TextFormat::simple(font_id.clone(), color));
+    };
+    let mut rest = code;
+    while let Some(character) = rest.chars().next() {
+        let (length, color) = if rest.starts_with("//") {
+            (rest.find('\n').unwrap_or(rest.len()), COMMENT_COLOR)
+        } else if character == '"' || character == '\'' {
+            // the closing quote is part of the string, an unclosed string continues to the end
+            let length = rest[1..]
+                .find(character)
+                .map(|index| index + 2)
+                .unwrap_or(rest.len());
+            (length, STRING_COLOR)
+        } else if character.is_alphabetic() || character == '_' || character == '$' {
+            let length = rest
+                .find(|character: char| {
+                    !(character.is_alphanumeric() || character == '_' || character == '$')
+                })
+                .unwrap_or(rest.len());
+            if KEYWORDS.contains(&&rest[..length]) {
+                (length, KEYWORD_COLOR)
+            } else {
+                (length, Color32::GRAY)
+            }
+        } else {
+            (character.len_utf8(), Color32::GRAY)
+        };
+        append(&rest[..length], color);
+        rest = &rest[length..];
+    }
+    layout_job
+}
+
+pub enum ScriptsWindowResult {
+    NoAction,
+    Edit(MovieEdit),
+    Close,
+}
@@ -0,0 +1,37 @@
+use crate::editor::menu_bar::MENUS;
+
+#[derive(Default)]
+pub struct ShortcutsWindow {}
+impl ShortcutsWindow {
+    pub fn do_ui(&mut self, egui_ctx: &egui::Context) -> ShortcutsWindowResult {
+        let mut is_window_open = true;
+        egui::Window::new("Keyboard shortcuts")
+            .collapsible(false)
+            .open(&mut is_window_open)
+            .show(egui_ctx, |ui| {
+                egui::Grid::new("shortcuts_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for menu in MENUS {
+                            for item in menu.items {
+                                let Some(keyboard_shortcut) = item.keyboard_shortcut else {
+                                    continue;
+                                };
+                                ui.label(format!("{} > {}", menu.name, item.name));
+                                ui.label(egui_ctx.format_shortcut(&keyboard_shortcut));
+                                ui.end_row();
+                            }
+                        }
+                    });
+            });
+        if !is_window_open {
+            return ShortcutsWindowResult::Close;
+        }
+        ShortcutsWindowResult::NoAction
+    }
+}
+
+pub enum ShortcutsWindowResult {
+    NoAction,
+    Close,
+}
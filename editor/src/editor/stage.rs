@@ -1,8 +1,12 @@
-use std::path::PathBuf;
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
 use flits_core::{
-    BitmapCacheStatus, CachedBitmap, EditorTransform, Movie, MovieProperties, PlaceSymbol,
-    PlacedSymbolIndex, Symbol, SymbolIndex, SymbolIndexOrRoot, TextProperties,
+    bounds::Bounds, BitmapCacheStatus, CachedBitmap, EditorTransform, Movie, MovieProperties,
+    PixelSnappingMode, PlaceSymbol, PlacedSymbolIndex, Symbol, SymbolIndex, SymbolIndexOrRoot,
+    TextProperties,
 };
 use flits_text_rendering::TextRenderer;
 use ruffle_render::{
@@ -12,52 +16,53 @@ use ruffle_render::{
     matrix::Matrix,
     transform::Transform,
 };
-use swf::{Color, ColorTransform, Twips};
-use winit::event::{ElementState, MouseButton};
+use swf::{Color, ColorTransform, Fixed8, Twips};
+use winit::event::{ElementState, MouseButton, MouseScrollDelta};
 
 use crate::{
     edit::{AddPlacedSymbolEdit, MovieEdit, MultiEdit, MultiEditEdit, PlacedSymbolEdit},
     editor::{
-        stage::{camera::Camera, text_rendering::FontsConverterBuilder},
-        BitmapHandleWrapper, Context, MutableContext, RenderContext, Renderer, StageSize,
-        EDIT_EPSILON, EMPTY_CLIP_HEIGHT, EMPTY_CLIP_WIDTH, LIBRARY_WIDTH,
+        stage::{
+            camera::Camera,
+            resize_handle::{ResizeHandle, HANDLE_SIZE},
+            text_rendering::{EditTextProperties, FontsConverterBuilder},
+        },
+        BitmapHandleWrapper, Context, MutableContext, RenderContext, Renderer, Selection,
+        StageSize, EDIT_EPSILON, EMPTY_CLIP_HEIGHT, EMPTY_CLIP_WIDTH, LIBRARY_WIDTH,
+        PROPERTIES_PANEL_HEIGHT,
     },
     message::EditorMessage,
     MENU_HEIGHT,
 };
 
-mod camera;
+pub(super) mod camera;
+mod resize_handle;
 mod text_rendering;
 
+// touchpads scroll in pixels instead of lines
+const PIXELS_PER_SCROLL_LINE: f64 = 50.0;
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+const MAX_NESTING_DEPTH: usize = 64;
+// space between symbols when dropping multiple symbols at once
+const DROP_GRID_GAP: f64 = 10.0;
+// how visible the scene around the movieclip you are editing is
+const OUTSIDE_EDITING_CLIP_ALPHA: f32 = 0.3;
+// edit text ids have the placed symbol index in the lower half and the clip in the upper half
+const EDIT_TEXT_ID_SCOPE_SHIFT: u32 = usize::BITS / 2;
+
 pub enum StageMessage {
     ZoomIn,
     ZoomOut,
     ResetZoom,
-    ReleaseSymbolDragDrop(egui::Pos2, SymbolIndex),
+    ReleaseSymbolDragDrop(egui::Pos2, Vec<SymbolIndex>),
 }
 
-#[derive(Clone, Copy)]
-struct Bounds {
-    min_x: f64,
-    min_y: f64,
-    max_x: f64,
-    max_y: f64,
-}
-impl Bounds {
-    fn contains(&self, other: &Self) -> bool {
-        other.min_x >= self.min_x
-            && other.min_y >= self.min_y
-            && other.max_x <= self.max_x
-            && other.max_y <= self.max_y
-    }
-    fn from_points(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
-        Bounds {
-            min_x: x1.min(x2),
-            min_y: y1.min(y2),
-            max_x: x1.max(x2),
-            max_y: y1.max(y2),
-        }
-    }
+// measured while rendering for the performance overlay
+#[derive(Default)]
+pub struct RenderStats {
+    pub command_count: usize,
+    pub submit_time: Duration,
+    pub text_arena_size_bytes: usize,
 }
 
 struct BoxSelection {
@@ -78,16 +83,36 @@ struct DragData {
     place_symbol_index: SymbolIndex,
 }
 
+struct ResizeData {
+    handle: ResizeHandle,
+    place_symbol_index: PlacedSymbolIndex,
+    symbol_start_transform: EditorTransform,
+    start_bounds: Bounds,
+}
+
 pub struct Stage {
     camera: Camera,
     // Option because we need the renderer to intialize it
-    text_renderer: Option<TextRenderer>,
+    text_renderer: Option<TextRenderer<EditTextProperties>>,
 
     directory: PathBuf,
     box_selection: Option<BoxSelection>,
 
     // one DragData per selected PlacedSymbol
     drag_datas: Option<Vec<DragData>>,
+    // a single selected placed symbol can be resized with the handles around it
+    resize_data: Option<ResizeData>,
+    hovered_handle: Option<ResizeHandle>,
+
+    // for detecting double clicks
+    last_click: Option<(Instant, PlacedSymbolIndex)>,
+
+    // the part of the window that isn't covered by panels, in egui coordinates
+    screen_rect: egui::Rect,
+    // in pixels, the library can be resized
+    library_width: u32,
+    // in pixels, the properties panel can be resized and collapsed
+    properties_panel_height: u32,
 }
 impl Stage {
     pub fn new(movie_properties: &MovieProperties, directory: PathBuf) -> Self {
@@ -97,9 +122,24 @@ impl Stage {
             directory,
             box_selection: None,
             drag_datas: None,
+            resize_data: None,
+            hovered_handle: None,
+            last_click: None,
+            screen_rect: egui::Rect::EVERYTHING,
+            library_width: LIBRARY_WIDTH,
+            properties_panel_height: PROPERTIES_PANEL_HEIGHT as u32,
         }
     }
-    pub fn render(&mut self, ctx: &mut RenderContext) {
+    pub fn set_screen_rect(&mut self, screen_rect: egui::Rect) {
+        self.screen_rect = screen_rect;
+    }
+    pub fn set_library_width(&mut self, library_width: u32) {
+        self.library_width = library_width;
+    }
+    pub fn set_properties_panel_height(&mut self, properties_panel_height: u32) {
+        self.properties_panel_height = properties_panel_height;
+    }
+    pub fn render(&mut self, ctx: &mut RenderContext) -> RenderStats {
         let symbols = &mut ctx.movie.symbols;
 
         if self.text_renderer.is_none() {
@@ -134,14 +174,19 @@ impl Stage {
             // when editing a clip, fade the stage background
             stage_color.a = 4;
         }
-        let world_to_screen_matrix =
-            self.camera
-                .world_to_screen_matrix(Self::stage_size_from_viewport_dimensions(
-                    viewport_dimensions,
-                ));
+        let world_to_screen_matrix = self
+            .camera
+            .world_to_screen_matrix(self.stage_size_from_viewport_dimensions(viewport_dimensions));
+        // when editing a movieclip, the scene is shown around the first place the movieclip is placed
+        let scene_to_screen_matrix = match ctx.selection.stage_symbol_index {
+            Some(symbol_index) => Self::find_placement_matrix(ctx.movie, None, symbol_index, 0)
+                .and_then(|placement_matrix| placement_matrix.inverse())
+                .map(|clip_to_scene_matrix| world_to_screen_matrix * clip_to_scene_matrix),
+            None => None,
+        };
         commands.commands.push(Command::DrawRect {
             color: stage_color,
-            matrix: world_to_screen_matrix
+            matrix: scene_to_screen_matrix.unwrap_or(world_to_screen_matrix)
                 * Matrix::create_box(
                     ctx.movie.properties.width as f32,
                     ctx.movie.properties.height as f32,
@@ -202,6 +247,24 @@ impl Stage {
             }
         }
 
+        if let Some(scene_to_screen_matrix) = scene_to_screen_matrix {
+            // the scene is dimmed, the movieclip you are editing is rendered on top of it
+            commands.commands.extend(Self::render_placed_symbols(
+                ctx.renderer,
+                self.text_renderer.as_mut().unwrap(), // we initialized this above
+                ctx.movie,
+                None,
+                Transform {
+                    matrix: scene_to_screen_matrix,
+                    color_transform: ColorTransform {
+                        a_multiply: Fixed8::from_f32(OUTSIDE_EDITING_CLIP_ALPHA),
+                        ..ColorTransform::IDENTITY
+                    },
+                },
+                ctx.selection.stage_symbol_index,
+                &self.directory,
+            ));
+        }
         commands.commands.extend(Self::render_placed_symbols(
             ctx.renderer,
             self.text_renderer.as_mut().unwrap(), // we initialized this above
@@ -211,6 +274,7 @@ impl Stage {
                 matrix: world_to_screen_matrix,
                 color_transform: ColorTransform::IDENTITY,
             },
+            None,
             &self.directory,
         ));
 
@@ -218,11 +282,21 @@ impl Stage {
             .commands
             .extend(self.render_selection(ctx, world_to_screen_matrix));
 
+        let command_count = commands.commands.len();
+        let submit_start = Instant::now();
         ctx.renderer
             .submit_frame(Color::from_rgb(0x222222, 255), commands, vec![]);
+        let submit_time = submit_start.elapsed();
 
         // we created this earlier in this function
-        self.text_renderer.as_mut().unwrap().finish_frame();
+        let text_renderer = self.text_renderer.as_mut().unwrap();
+        text_renderer.finish_frame();
+
+        RenderStats {
+            command_count,
+            submit_time,
+            text_arena_size_bytes: text_renderer.arena_size_bytes(),
+        }
     }
 
     fn render_selection(
@@ -236,13 +310,17 @@ impl Stage {
             .get_placed_symbols(ctx.selection.stage_symbol_index);
         for i in &ctx.selection.placed_symbols {
             let place_symbol = placed_symbols.get(*i).unwrap();
-            let bounds = self.bounds_of_placed_symbol(ctx.movie, place_symbol);
+            let bounds = Self::bounds_of_placed_symbol(ctx.movie, place_symbol);
             if let Some(bounds) = bounds {
                 let mut rect = self.render_selection_rectangle(world_to_screen_matrix, bounds);
                 commands.append(&mut rect);
             }
         }
 
+        if let Some(bounds) = Self::resizable_bounds(ctx.movie, ctx.selection) {
+            commands.extend(Self::render_resize_handles(world_to_screen_matrix, bounds));
+        }
+
         // render box selection
         if let Some(box_selection) = &self.box_selection {
             let mut rect =
@@ -253,6 +331,73 @@ impl Stage {
         commands
     }
 
+    // white squares with a black border, the same size at every zoom level
+    fn render_resize_handles(world_to_screen_matrix: Matrix, bounds: Bounds) -> Vec<Command> {
+        let mut commands = vec![];
+        for handle in ResizeHandle::ALL {
+            let (screen_x, screen_y) =
+                Self::world_to_screen_point(world_to_screen_matrix, handle.position(&bounds));
+            commands.push(Command::DrawRect {
+                color: Color::BLACK,
+                matrix: Matrix::create_box(
+                    HANDLE_SIZE as f32,
+                    HANDLE_SIZE as f32,
+                    Twips::from_pixels(screen_x - HANDLE_SIZE / 2.0),
+                    Twips::from_pixels(screen_y - HANDLE_SIZE / 2.0),
+                ),
+            });
+            commands.push(Command::DrawRect {
+                color: Color::WHITE,
+                matrix: Matrix::create_box(
+                    (HANDLE_SIZE - 2.0) as f32,
+                    (HANDLE_SIZE - 2.0) as f32,
+                    Twips::from_pixels(screen_x - HANDLE_SIZE / 2.0 + 1.0),
+                    Twips::from_pixels(screen_y - HANDLE_SIZE / 2.0 + 1.0),
+                ),
+            });
+        }
+        commands
+    }
+    fn world_to_screen_point(world_to_screen_matrix: Matrix, point: (f64, f64)) -> (f64, f64) {
+        let screen_point = world_to_screen_matrix
+            * Matrix::translate(Twips::from_pixels(point.0), Twips::from_pixels(point.1));
+        (screen_point.tx.to_pixels(), screen_point.ty.to_pixels())
+    }
+    // only a single placed symbol can be resized
+    fn resizable_bounds(movie: &Movie, selection: &Selection) -> Option<Bounds> {
+        let [placed_symbol_index] = selection.placed_symbols.as_slice() else {
+            return None;
+        };
+        let place_symbol =
+            &movie.get_placed_symbols(selection.stage_symbol_index)[*placed_symbol_index];
+        Self::bounds_of_placed_symbol(movie, place_symbol)
+    }
+    fn resize_handle_at_position(
+        &self,
+        movie: &Movie,
+        selection: &Selection,
+        viewport_dimensions: ViewportDimensions,
+        mouse_x: f64,
+        mouse_y: f64,
+    ) -> Option<ResizeHandle> {
+        let bounds = Self::resizable_bounds(movie, selection)?;
+        let world_to_screen_matrix = self
+            .camera
+            .world_to_screen_matrix(self.stage_size_from_viewport_dimensions(viewport_dimensions));
+        ResizeHandle::ALL.into_iter().find(|handle| {
+            let (screen_x, screen_y) =
+                Self::world_to_screen_point(world_to_screen_matrix, handle.position(&bounds));
+            f64::abs(mouse_x - screen_x) <= HANDLE_SIZE / 2.0
+                && f64::abs(mouse_y - screen_y) <= HANDLE_SIZE / 2.0
+        })
+    }
+    pub fn cursor_icon(&self) -> Option<egui::CursorIcon> {
+        match &self.resize_data {
+            Some(resize_data) => Some(resize_data.handle.cursor_icon()),
+            None => self.hovered_handle.map(|handle| handle.cursor_icon()),
+        }
+    }
+
     fn render_selection_rectangle(
         &self,
         world_to_screen_matrix: Matrix,
@@ -325,25 +470,113 @@ impl Stage {
         commands
     }
 
-    fn bounds_of_placed_symbol(&self, movie: &Movie, place_symbol: &PlaceSymbol) -> Option<Bounds> {
-        let local_bounds = self.local_bounds_of_placed_symbol(movie, place_symbol);
+    fn new_placed_symbol(movie: &Movie, symbol_index: SymbolIndex, x: f64, y: f64) -> PlaceSymbol {
+        PlaceSymbol {
+            text: match &movie.symbols[symbol_index] {
+                Symbol::Font(_) => Some(Box::new(TextProperties::new())),
+                _ => None,
+            },
+            ..PlaceSymbol::new(symbol_index)
+                .with_transform(EditorTransform::default().add_offset(x, y))
+        }
+    }
+
+    fn bounds_of_placed_symbol(movie: &Movie, place_symbol: &PlaceSymbol) -> Option<Bounds> {
+        let local_bounds = Self::local_bounds_of_placed_symbol(movie, place_symbol);
         if let Some(local_bounds) = local_bounds {
+            // from_points, because a negative scale swaps min and max
+            let scaled_bounds = Bounds::from_points(
+                local_bounds.min_x * place_symbol.transform.x_scale,
+                local_bounds.min_y * place_symbol.transform.y_scale,
+                local_bounds.max_x * place_symbol.transform.x_scale,
+                local_bounds.max_y * place_symbol.transform.y_scale,
+            );
+            // TODO: rotate with Bounds::rotate_and_bound once placed symbols can be rotated
             return Some(Bounds {
-                min_x: place_symbol.transform.x
-                    + local_bounds.min_x * place_symbol.transform.x_scale,
-                min_y: place_symbol.transform.y
-                    + local_bounds.min_y * place_symbol.transform.y_scale,
-                max_x: place_symbol.transform.x
-                    + local_bounds.max_x * place_symbol.transform.x_scale,
-                max_y: place_symbol.transform.y
-                    + local_bounds.max_y * place_symbol.transform.y_scale,
+                min_x: place_symbol.transform.x + scaled_bounds.min_x,
+                min_y: place_symbol.transform.y + scaled_bounds.min_y,
+                max_x: place_symbol.transform.x + scaled_bounds.max_x,
+                max_y: place_symbol.transform.y + scaled_bounds.max_y,
             });
         }
         None
     }
 
-    fn local_bounds_of_placed_symbol(
-        &self,
+    // the scale of a movieclip as it appears in the root, using the first place it is placed
+    // placed symbols only have a position and scale, so this is enough to convert distances
+    fn accumulated_scale_of_symbol(movie: &Movie, symbol_index: SymbolIndexOrRoot) -> (f64, f64) {
+        let Some(symbol_index) = symbol_index else {
+            return (1.0, 1.0);
+        };
+        match Self::find_accumulated_scale(movie, None, symbol_index, 0) {
+            // a scale of 0 can't be converted back
+            Some((x_scale, y_scale)) if x_scale != 0.0 && y_scale != 0.0 => (x_scale, y_scale),
+            _ => (1.0, 1.0),
+        }
+    }
+    fn find_accumulated_scale(
+        movie: &Movie,
+        parent_symbol_index: SymbolIndexOrRoot,
+        symbol_index: SymbolIndex,
+        depth: usize,
+    ) -> Option<(f64, f64)> {
+        // protect against movieclips that contain themselves
+        if depth > MAX_NESTING_DEPTH {
+            return None;
+        }
+        for place_symbol in movie.get_placed_symbols(parent_symbol_index) {
+            let scale = (
+                place_symbol.transform.x_scale,
+                place_symbol.transform.y_scale,
+            );
+            if place_symbol.symbol_index == symbol_index {
+                return Some(scale);
+            }
+            if let Symbol::MovieClip(_) = movie.symbols[place_symbol.symbol_index] {
+                if let Some(inner_scale) = Self::find_accumulated_scale(
+                    movie,
+                    Some(place_symbol.symbol_index),
+                    symbol_index,
+                    depth + 1,
+                ) {
+                    return Some((scale.0 * inner_scale.0, scale.1 * inner_scale.1));
+                }
+            }
+        }
+        None
+    }
+
+    // the matrix from the space of a movieclip to the root, using the first place it is placed
+    fn find_placement_matrix(
+        movie: &Movie,
+        parent_symbol_index: SymbolIndexOrRoot,
+        symbol_index: SymbolIndex,
+        depth: usize,
+    ) -> Option<Matrix> {
+        // protect against movieclips that contain themselves
+        if depth > MAX_NESTING_DEPTH {
+            return None;
+        }
+        for place_symbol in movie.get_placed_symbols(parent_symbol_index) {
+            let matrix = <swf::Matrix as Into<Matrix>>::into(place_symbol.transform.clone().into());
+            if place_symbol.symbol_index == symbol_index {
+                return Some(matrix);
+            }
+            if let Symbol::MovieClip(_) = movie.symbols[place_symbol.symbol_index] {
+                if let Some(inner_matrix) = Self::find_placement_matrix(
+                    movie,
+                    Some(place_symbol.symbol_index),
+                    symbol_index,
+                    depth + 1,
+                ) {
+                    return Some(matrix * inner_matrix);
+                }
+            }
+        }
+        None
+    }
+
+    pub(crate) fn local_bounds_of_placed_symbol(
         movie: &Movie,
         place_symbol: &PlaceSymbol,
     ) -> Option<Bounds> {
@@ -362,39 +595,20 @@ impl Stage {
                 None => None,
             },
             Symbol::MovieClip(movieclip) => {
-                if movieclip.place_symbols.len() == 0 {
-                    return Some(Bounds {
-                        min_x: -EMPTY_CLIP_WIDTH / 2.0,
-                        min_y: -EMPTY_CLIP_HEIGHT / 2.0,
-                        max_x: EMPTY_CLIP_WIDTH / 2.0,
-                        max_y: EMPTY_CLIP_HEIGHT / 2.0,
+                // the bounds are calculated from the children every time,
+                // so they are up to date as soon as a child is added
+                let total_bounds =
+                    Bounds::from_placed_symbols(&movieclip.place_symbols, |inner_place_symbol| {
+                        Self::bounds_of_placed_symbol(movie, inner_place_symbol)
                     });
-                }
-                let mut total_bounds = Bounds {
-                    min_x: 0.0,
-                    min_y: 0.0,
-                    max_x: 0.0,
-                    max_y: 0.0,
+                // children without a size yet (like bitmaps that aren't loaded)
+                // shouldn't make the clip impossible to select
+                let Some(total_bounds) = total_bounds else {
+                    return Some(Self::empty_clip_bounds());
                 };
-                for inner_place_symbol in &movieclip.place_symbols {
-                    let bounds = self.bounds_of_placed_symbol(movie, inner_place_symbol);
-                    let Some(bounds) = bounds else {
-                        continue;
-                    };
-                    if bounds.min_x < total_bounds.min_x {
-                        total_bounds.min_x = bounds.min_x;
-                    }
-                    if bounds.min_y < total_bounds.min_y {
-                        total_bounds.min_y = bounds.min_y;
-                    }
-                    if bounds.max_x > total_bounds.max_x {
-                        total_bounds.max_x = bounds.max_x;
-                    }
-                    if bounds.max_y > total_bounds.max_y {
-                        total_bounds.max_y = bounds.max_y;
-                    }
-                }
-                Some(total_bounds)
+                // the origin is always included
+                let origin = Bounds::from_points(0.0, 0.0, 0.0, 0.0);
+                Some(total_bounds.union(&origin))
             }
             Symbol::Font(_) => {
                 let text_properties = place_symbol.text.as_ref().unwrap();
@@ -405,6 +619,18 @@ impl Stage {
                     max_y: text_properties.height / 2.0,
                 })
             }
+            Symbol::StaticText(static_text) => Some(Bounds::of_static_text(static_text)),
+            // sounds can't be placed
+            Symbol::Sound(_) => None,
+        }
+    }
+
+    fn empty_clip_bounds() -> Bounds {
+        Bounds {
+            min_x: -EMPTY_CLIP_WIDTH / 2.0,
+            min_y: -EMPTY_CLIP_HEIGHT / 2.0,
+            max_x: EMPTY_CLIP_WIDTH / 2.0,
+            max_y: EMPTY_CLIP_HEIGHT / 2.0,
         }
     }
 
@@ -425,12 +651,15 @@ impl Stage {
         )));
     }
 
+    // placed symbols of skipped_clip aren't rendered,
+    // used to render the scene around the movieclip that is being edited
     fn render_placed_symbols(
         renderer: &mut Renderer,
-        text_renderer: &mut TextRenderer,
+        text_renderer: &mut TextRenderer<EditTextProperties>,
         movie: &Movie,
         symbol_index: SymbolIndexOrRoot,
         transform: Transform,
+        skipped_clip: Option<SymbolIndex>,
         directory: &PathBuf,
     ) -> Vec<Command> {
         let mut commands = vec![];
@@ -441,13 +670,19 @@ impl Stage {
                 .symbols
                 .get(place_symbol.symbol_index as usize)
                 .expect("Invalid symbol placed");
+            if skipped_clip == Some(place_symbol.symbol_index) {
+                continue;
+            }
             match symbol {
                 Symbol::Bitmap(bitmap) => {
+                    // bitmaps that aren't loaded or registered with the renderer yet (first frame,
+                    // after reloading assets) or can't be loaded are skipped,
+                    // the other placed symbols should still be rendered
                     let BitmapCacheStatus::Cached(cached_bitmap) = &bitmap.cache else {
-                        break;
+                        continue;
                     };
                     let Some(bitmap_handle) = &cached_bitmap.bitmap_handle else {
-                        break;
+                        continue;
                     };
                     let bitmap_handle: &BitmapHandle =
                         match bitmap_handle.as_any().downcast_ref::<BitmapHandleWrapper>() {
@@ -476,8 +711,12 @@ impl Stage {
                                     )),
                             color_transform: transform.color_transform,
                         },
-                        smoothing: false,
-                        pixel_snapping: PixelSnapping::Never, // TODO: figure out a good default
+                        smoothing: place_symbol.smoothing,
+                        pixel_snapping: match place_symbol.pixel_snapping {
+                            PixelSnappingMode::Never => PixelSnapping::Never,
+                            PixelSnappingMode::Always => PixelSnapping::Always,
+                            PixelSnappingMode::Auto => PixelSnapping::Auto,
+                        },
                     });
                 }
                 Symbol::MovieClip(clip) => {
@@ -511,6 +750,7 @@ impl Stage {
                             matrix: transform.matrix * place_symbol_matrix,
                             color_transform: transform.color_transform,
                         },
+                        skipped_clip,
                         directory,
                     ));
                 }
@@ -522,16 +762,16 @@ impl Stage {
                             place_symbol.transform.clone()
                         ));
                     let text_properties = place_symbol.text.as_ref().unwrap();
-                    // TODO: ids should be unique for the entire project or reset when switching to a different clip
-                    // TODO: don't update the edit texts every frame
-                    // TODO: nested edit text works right now because we add the text right before
-                    // rendering it, but this won't work when we cache it
-                    text_renderer
-                        .add_edit_text(i, (place_symbol.symbol_index, *text_properties.clone()));
+                    let edit_text_id = Self::edit_text_id(symbol_index, i);
+                    text_renderer.update_edit_text(
+                        edit_text_id,
+                        (place_symbol.symbol_index, text_properties),
+                        || (place_symbol.symbol_index, *text_properties.clone()),
+                    );
                     commands.extend(
                         text_renderer
                             .render(
-                                i,
+                                edit_text_id,
                                 Transform {
                                     matrix: transform.matrix
                                         * place_symbol_matrix
@@ -561,16 +801,67 @@ impl Stage {
                         })
                     }*/
                 }
+                Symbol::StaticText(static_text) => {
+                    let Some(font_index) = static_text.font_index else {
+                        continue;
+                    };
+                    let place_symbol_matrix =
+                        <swf::Matrix as Into<Matrix>>::into(<EditorTransform as Into<
+                            swf::Matrix,
+                        >>::into(
+                            place_symbol.transform.clone()
+                        ));
+                    // preview the static text with a text field, the export uses the glyphs directly
+                    let bounds = Bounds::of_static_text(static_text);
+                    let edit_text_properties = (
+                        font_index,
+                        TextProperties {
+                            text: static_text.text.clone(),
+                            width: bounds.max_x - bounds.min_x,
+                            height: bounds.max_y - bounds.min_y,
+                            size: static_text.size,
+                            color: static_text.color.clone(),
+                            is_multiline: true,
+                            ..TextProperties::new()
+                        },
+                    );
+                    let edit_text_id = Self::edit_text_id(symbol_index, i);
+                    text_renderer.update_edit_text(edit_text_id, &edit_text_properties, || {
+                        edit_text_properties.clone()
+                    });
+                    commands.extend(
+                        text_renderer
+                            .render(
+                                edit_text_id,
+                                Transform {
+                                    matrix: transform.matrix
+                                        * place_symbol_matrix
+                                        * Matrix::translate(
+                                            Twips::from_pixels(static_text.x),
+                                            Twips::from_pixels(static_text.y),
+                                        ),
+                                    color_transform: transform.color_transform,
+                                },
+                                renderer,
+                            )
+                            .commands,
+                    );
+                }
+                Symbol::Sound(_) => {}
             }
         }
         commands
     }
 
-    fn stage_size_from_viewport_dimensions(viewport_dimensions: ViewportDimensions) -> StageSize {
+    fn stage_size_from_viewport_dimensions(
+        &self,
+        viewport_dimensions: ViewportDimensions,
+    ) -> StageSize {
         StageSize {
-            width: viewport_dimensions.width - LIBRARY_WIDTH,
-            // we don't know the height of the properties panel, so just use an approximation
-            height: viewport_dimensions.height - 65,
+            width: viewport_dimensions.width.saturating_sub(self.library_width),
+            height: viewport_dimensions
+                .height
+                .saturating_sub(self.properties_panel_height),
         }
     }
 
@@ -585,57 +876,148 @@ impl Stage {
             StageMessage::ResetZoom => {
                 self.camera.reset_zoom();
             }
-            StageMessage::ReleaseSymbolDragDrop(mouse_pos, symbol_index) => {
-                // TODO: handle drag that doesn't end on stage
-                let mut matrix =
-                    self.camera
-                        .screen_to_world_matrix(Self::stage_size_from_viewport_dimensions(
-                            ctx.viewport_dimensions,
-                        ))
-                        * Matrix::translate(
-                            Twips::from_pixels(mouse_pos.x as f64),
-                            Twips::from_pixels(
-                                // TODO: don't hardcode the menu height
-                                mouse_pos.y as f64 - MENU_HEIGHT as f64,
-                            ),
-                        );
+            StageMessage::ReleaseSymbolDragDrop(mouse_pos, symbol_indices) => {
+                // dropping on a panel cancels the drag
+                if !self.screen_rect.contains(mouse_pos) {
+                    return;
+                }
+                // sounds are played with sound actions instead
+                let symbol_indices: Vec<SymbolIndex> = symbol_indices
+                    .into_iter()
+                    .filter(|symbol_index| {
+                        !matches!(ctx.movie.symbols[*symbol_index], Symbol::Sound(_))
+                    })
+                    .collect();
+                if symbol_indices.is_empty() {
+                    return;
+                }
+                let mut matrix = self.camera.screen_to_world_matrix(
+                    self.stage_size_from_viewport_dimensions(ctx.viewport_dimensions),
+                ) * Matrix::translate(
+                    Twips::from_pixels(mouse_pos.x as f64),
+                    Twips::from_pixels(
+                        // TODO: don't hardcode the menu height
+                        mouse_pos.y as f64 - MENU_HEIGHT as f64,
+                    ),
+                );
                 // reset zoom (otherwise when you are zoomed in the symbol becomes smaller)
                 matrix.a = Matrix::IDENTITY.a;
                 matrix.b = Matrix::IDENTITY.b;
                 matrix.c = Matrix::IDENTITY.c;
                 matrix.d = Matrix::IDENTITY.d;
-                ctx.message_bus
-                    .publish(EditorMessage::Edit(MovieEdit::AddPlacedSymbol(
-                        AddPlacedSymbolEdit {
-                            editing_symbol_index: ctx.selection.stage_symbol_index,
-                            placed_symbol: PlaceSymbol {
-                                symbol_index,
-                                transform: EditorTransform {
-                                    x: matrix.tx.to_pixels(),
-                                    y: matrix.ty.to_pixels(),
-                                    x_scale: 1.0,
-                                    y_scale: 1.0,
-                                },
-                                instance_name: "".into(),
-                                text: match &ctx.movie.symbols[symbol_index] {
-                                    Symbol::Font(_) => Some(Box::new(TextProperties::new())),
-                                    _ => None,
-                                },
+                let drop_x = matrix.tx.to_pixels();
+                let drop_y = matrix.ty.to_pixels();
+
+                if symbol_indices.len() == 1 {
+                    ctx.message_bus
+                        .publish(EditorMessage::Edit(MovieEdit::AddPlacedSymbol(
+                            AddPlacedSymbolEdit {
+                                editing_symbol_index: ctx.selection.stage_symbol_index,
+                                placed_symbol: Self::new_placed_symbol(
+                                    ctx.movie,
+                                    symbol_indices[0],
+                                    drop_x,
+                                    drop_y,
+                                ),
+                                placed_symbol_index: None,
                             },
+                        )));
+                    return;
+                }
+
+                // place multiple symbols in a grid centered on the drop position
+                let placed_symbols: Vec<PlaceSymbol> = symbol_indices
+                    .iter()
+                    .map(|symbol_index| Self::new_placed_symbol(ctx.movie, *symbol_index, 0.0, 0.0))
+                    .collect();
+                let (mut cell_width, mut cell_height) = (0.0f64, 0.0f64);
+                for placed_symbol in &placed_symbols {
+                    let bounds = Self::local_bounds_of_placed_symbol(ctx.movie, placed_symbol)
+                        .unwrap_or(Self::empty_clip_bounds());
+                    cell_width = cell_width.max(bounds.max_x - bounds.min_x);
+                    cell_height = cell_height.max(bounds.max_y - bounds.min_y);
+                }
+                cell_width += DROP_GRID_GAP;
+                cell_height += DROP_GRID_GAP;
+                let columns = (placed_symbols.len() as f64).sqrt().ceil() as usize;
+                let rows = placed_symbols.len().div_ceil(columns);
+                let edits = placed_symbols
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, mut placed_symbol)| {
+                        let column = (i % columns) as f64;
+                        let row = (i / columns) as f64;
+                        placed_symbol.transform.x =
+                            drop_x + (column - (columns - 1) as f64 / 2.0) * cell_width;
+                        placed_symbol.transform.y =
+                            drop_y + (row - (rows - 1) as f64 / 2.0) * cell_height;
+                        MultiEditEdit::AddPlacedSymbol(AddPlacedSymbolEdit {
+                            editing_symbol_index: ctx.selection.stage_symbol_index,
+                            placed_symbol,
                             placed_symbol_index: None,
-                        },
-                    )));
+                        })
+                    })
+                    .collect();
+                ctx.message_bus
+                    .publish(EditorMessage::Edit(MovieEdit::Multi(MultiEdit::new(
+                        ctx.selection.stage_symbol_index,
+                        edits,
+                    ))));
             }
         }
     }
 
+    pub fn handle_mouse_wheel(
+        &mut self,
+        ctx: &mut MutableContext,
+        mouse_x: f64,
+        mouse_y: f64,
+        delta: MouseScrollDelta,
+    ) {
+        let lines = match delta {
+            MouseScrollDelta::LineDelta(_, y) => y as f64,
+            MouseScrollDelta::PixelDelta(position) => position.y / PIXELS_PER_SCROLL_LINE,
+        };
+        let stage_size = self.stage_size_from_viewport_dimensions(ctx.viewport_dimensions);
+        self.camera
+            .zoom_towards(lines * 0.1, mouse_x, mouse_y, &stage_size);
+    }
+
     pub fn handle_mouse_move(&mut self, ctx: &mut MutableContext, mouse_x: f64, mouse_y: f64) {
         let world_space_mouse_position =
-            self.camera
-                .screen_to_world_matrix(Self::stage_size_from_viewport_dimensions(
-                    ctx.viewport_dimensions,
-                ))
-                * Matrix::translate(Twips::from_pixels(mouse_x), Twips::from_pixels(mouse_y));
+            self.camera.screen_to_world_matrix(
+                self.stage_size_from_viewport_dimensions(ctx.viewport_dimensions),
+            ) * Matrix::translate(Twips::from_pixels(mouse_x), Twips::from_pixels(mouse_y));
+        // global drag: move the same distance as the mouse in the root instead of in the movieclip
+        let (drag_scale_x, drag_scale_y) = if ctx.modifiers.alt {
+            Self::accumulated_scale_of_symbol(ctx.movie, ctx.selection.stage_symbol_index)
+        } else {
+            (1.0, 1.0)
+        };
+        if let Some(resize_data) = &self.resize_data {
+            let resize = resize_data.handle.resize(
+                &resize_data.start_bounds,
+                world_space_mouse_position.tx.to_pixels(),
+                world_space_mouse_position.ty.to_pixels(),
+            );
+            ctx.movie
+                .get_placed_symbols_mut(ctx.selection.stage_symbol_index)
+                [resize_data.place_symbol_index]
+                .transform = resize_data.symbol_start_transform.scale_around(
+                resize.pivot_x,
+                resize.pivot_y,
+                resize.x_factor,
+                resize.y_factor,
+            );
+        } else if self.drag_datas.is_none() && self.box_selection.is_none() {
+            self.hovered_handle = self.resize_handle_at_position(
+                ctx.movie,
+                ctx.selection,
+                ctx.viewport_dimensions,
+                mouse_x,
+                mouse_y,
+            );
+        }
         let placed_symbols = ctx
             .movie
             .get_placed_symbols_mut(ctx.selection.stage_symbol_index);
@@ -645,11 +1027,11 @@ impl Stage {
                     .get_mut(drag_data.place_symbol_index)
                     .unwrap();
                 place_symbol.transform.x = drag_data.symbol_start_transform.x
-                    + world_space_mouse_position.tx.to_pixels()
-                    - drag_data.start_x;
+                    + (world_space_mouse_position.tx.to_pixels() - drag_data.start_x)
+                        / drag_scale_x;
                 place_symbol.transform.y = drag_data.symbol_start_transform.y
-                    + world_space_mouse_position.ty.to_pixels()
-                    - drag_data.start_y;
+                    + (world_space_mouse_position.ty.to_pixels() - drag_data.start_y)
+                        / drag_scale_y;
             }
         }
 
@@ -672,7 +1054,7 @@ impl Stage {
                 // add placed symbols to selection
                 for i in 0..placed_symbols.len() {
                     if let Some(bounds) =
-                        self.bounds_of_placed_symbol(ctx.movie, &placed_symbols[i])
+                        Self::bounds_of_placed_symbol(ctx.movie, &placed_symbols[i])
                     {
                         if box_selection.bounds.contains(&bounds) {
                             if ctx
@@ -691,7 +1073,7 @@ impl Stage {
                 // remove placed symbols from selection
                 for i in &box_selection.items {
                     if let Some(bounds) =
-                        self.bounds_of_placed_symbol(ctx.movie, &placed_symbols[*i])
+                        Self::bounds_of_placed_symbol(ctx.movie, &placed_symbols[*i])
                     {
                         if !box_selection.bounds.contains(&bounds) {
                             let placed_symbols_selection =
@@ -737,12 +1119,32 @@ impl Stage {
         state: ElementState,
     ) {
         let world_space_mouse_position =
-            self.camera
-                .screen_to_world_matrix(Self::stage_size_from_viewport_dimensions(
-                    ctx.viewport_dimensions,
-                ))
-                * Matrix::translate(Twips::from_pixels(mouse_x), Twips::from_pixels(mouse_y));
+            self.camera.screen_to_world_matrix(
+                self.stage_size_from_viewport_dimensions(ctx.viewport_dimensions),
+            ) * Matrix::translate(Twips::from_pixels(mouse_x), Twips::from_pixels(mouse_y));
         if button == MouseButton::Left && state == ElementState::Pressed {
+            // the handles are on top of the placed symbols
+            if let Some(handle) = self.resize_handle_at_position(
+                ctx.movie,
+                ctx.selection,
+                ctx.viewport_dimensions,
+                mouse_x,
+                mouse_y,
+            ) {
+                let place_symbol_index = ctx.selection.placed_symbols[0];
+                let place_symbol = &ctx
+                    .movie
+                    .get_placed_symbols(ctx.selection.stage_symbol_index)[place_symbol_index];
+                if let Some(start_bounds) = Self::bounds_of_placed_symbol(ctx.movie, place_symbol) {
+                    self.resize_data = Some(ResizeData {
+                        handle,
+                        place_symbol_index,
+                        symbol_start_transform: place_symbol.transform.clone(),
+                        start_bounds,
+                    });
+                    return;
+                }
+            }
             let symbol_index = self.get_placed_symbol_at_position(
                 ctx.movie,
                 ctx.viewport_dimensions,
@@ -751,38 +1153,45 @@ impl Stage {
                 ctx.selection.stage_symbol_index,
             );
             if let Some(symbol_index) = symbol_index {
-                let item_already_selected = ctx.selection.placed_symbols.contains(&symbol_index);
-                let mut placed_symbols_selection = ctx.selection.placed_symbols.clone();
-                if !ctx.modifiers.shift && !item_already_selected {
-                    placed_symbols_selection = Vec::new();
-                } else if item_already_selected && ctx.modifiers.shift {
-                    placed_symbols_selection.retain(|si| *si != symbol_index);
-                }
-                if !item_already_selected {
-                    placed_symbols_selection.push(symbol_index);
+                let now = Instant::now();
+                let is_double_click = self.last_click.is_some_and(|(time, last_symbol_index)| {
+                    last_symbol_index == symbol_index
+                        && now.duration_since(time) < DOUBLE_CLICK_TIME
+                });
+                self.last_click = Some((now, symbol_index));
+                if is_double_click {
+                    // enter the movieclip, like in Adobe Animate
+                    let placed_symbol = &ctx
+                        .movie
+                        .get_placed_symbols(ctx.selection.stage_symbol_index)[symbol_index];
+                    if let Symbol::MovieClip(_) = ctx.movie.symbols[placed_symbol.symbol_index] {
+                        self.last_click = None;
+                        ctx.message_bus
+                            .publish(EditorMessage::ChangeSelectedSymbol(Some(
+                                placed_symbol.symbol_index,
+                            )));
+                        return;
+                    }
                 }
+
+                let placed_symbols_selection = Self::selection_after_click(
+                    &ctx.selection.placed_symbols,
+                    symbol_index,
+                    ctx.modifiers.shift,
+                );
+                // the selection message is handled after this, so use the new selection directly
+                // instead of ctx.selection, a symbol that was shift-clicked away shouldn't be dragged
+                self.drag_datas = Some(Self::drag_datas_for_selection(
+                    ctx.movie,
+                    ctx.selection.stage_symbol_index,
+                    &placed_symbols_selection,
+                    world_space_mouse_position.tx.to_pixels(),
+                    world_space_mouse_position.ty.to_pixels(),
+                ));
                 ctx.message_bus
                     .publish(EditorMessage::ChangeSelectedPlacedSymbols(
-                        placed_symbols_selection.clone(),
+                        placed_symbols_selection,
                     ));
-
-                self.drag_datas = Some(
-                    placed_symbols_selection
-                        .iter()
-                        .map(|placed_symbol_index| {
-                            let place_symbol = &ctx
-                                .movie
-                                .get_placed_symbols(ctx.selection.stage_symbol_index)
-                                [*placed_symbol_index];
-                            DragData {
-                                symbol_start_transform: place_symbol.transform.clone(),
-                                start_x: world_space_mouse_position.tx.to_pixels(),
-                                start_y: world_space_mouse_position.ty.to_pixels(),
-                                place_symbol_index: *placed_symbol_index,
-                            }
-                        })
-                        .collect(),
-                );
             } else {
                 if !ctx.modifiers.shift {
                     ctx.message_bus
@@ -805,7 +1214,36 @@ impl Stage {
             //self.update_selection();
         }
         if button == MouseButton::Left && state == ElementState::Released {
-            if let Some(drag_datas) = self.drag_datas.clone() {
+            if let Some(resize_data) = self.resize_data.take() {
+                let place_symbol = ctx
+                    .movie
+                    .get_placed_symbols(ctx.selection.stage_symbol_index)
+                    [resize_data.place_symbol_index]
+                    .clone();
+                let start = &resize_data.symbol_start_transform;
+                let end = &place_symbol.transform;
+                // only insert an edit if you actually resized the placed symbol
+                if f64::abs(start.x - end.x) > EDIT_EPSILON
+                    || f64::abs(start.y - end.y) > EDIT_EPSILON
+                    || f64::abs(start.x_scale - end.x_scale) > EDIT_EPSILON
+                    || f64::abs(start.y_scale - end.y_scale) > EDIT_EPSILON
+                {
+                    ctx.message_bus.publish(EditorMessage::Edit(
+                        MovieEdit::new_placed_symbol_edit(
+                            ctx.selection.stage_symbol_index,
+                            PlacedSymbolEdit {
+                                editing_symbol_index: ctx.selection.stage_symbol_index,
+                                placed_symbol_index: resize_data.place_symbol_index,
+                                start: place_symbol
+                                    .clone()
+                                    .with_transform(resize_data.symbol_start_transform),
+                                end: place_symbol,
+                            },
+                        ),
+                    ));
+                }
+            }
+            if let Some(drag_datas) = self.drag_datas.take() {
                 let mut edits = Vec::with_capacity(drag_datas.len());
                 for drag_data in drag_datas {
                     let end = EditorTransform {
@@ -833,35 +1271,28 @@ impl Stage {
                     if f64::abs(drag_data.symbol_start_transform.x - end.x) > EDIT_EPSILON
                         || f64::abs(drag_data.symbol_start_transform.y - end.y) > EDIT_EPSILON
                     {
+                        let placed_symbol = ctx
+                            .movie
+                            .get_placed_symbols(ctx.selection.stage_symbol_index)
+                            [drag_data.place_symbol_index]
+                            .clone();
                         edits.push(MultiEditEdit::EditPlacedSymbol(PlacedSymbolEdit {
                             editing_symbol_index: ctx.selection.stage_symbol_index,
                             placed_symbol_index: drag_data.place_symbol_index,
-                            start: PlaceSymbol::from_transform(
-                                ctx.movie
-                                    .get_placed_symbols(ctx.selection.stage_symbol_index)
-                                    [drag_data.place_symbol_index]
-                                    .clone(),
-                                drag_data.symbol_start_transform.clone(),
-                            ),
-                            end: PlaceSymbol::from_transform(
-                                ctx.movie
-                                    .get_placed_symbols(ctx.selection.stage_symbol_index)
-                                    [drag_data.place_symbol_index]
-                                    .clone(),
-                                end,
-                            ),
+                            start: placed_symbol
+                                .clone()
+                                .with_transform(drag_data.symbol_start_transform),
+                            end: placed_symbol.with_transform(end),
                         }));
                     }
                 }
                 if edits.len() > 0 {
                     ctx.message_bus
-                        .publish(EditorMessage::Edit(MovieEdit::Multi(MultiEdit {
-                            editing_symbol_index: ctx.selection.stage_symbol_index,
+                        .publish(EditorMessage::Edit(MovieEdit::Multi(MultiEdit::new(
+                            ctx.selection.stage_symbol_index,
                             edits,
-                        })));
+                        ))));
                 }
-
-                self.drag_datas = None;
             }
             self.box_selection = None;
         }
@@ -873,6 +1304,44 @@ impl Stage {
         }
     }
 
+    // shift adds the clicked symbol to the selection or removes it when it was already selected,
+    // otherwise clicking an unselected symbol selects only that one
+    fn selection_after_click(
+        selection: &[PlacedSymbolIndex],
+        clicked: PlacedSymbolIndex,
+        shift: bool,
+    ) -> Vec<PlacedSymbolIndex> {
+        let item_already_selected = selection.contains(&clicked);
+        let mut new_selection = selection.to_vec();
+        if !shift && !item_already_selected {
+            new_selection = Vec::new();
+        } else if item_already_selected && shift {
+            new_selection.retain(|placed_symbol_index| *placed_symbol_index != clicked);
+        }
+        if !item_already_selected {
+            new_selection.push(clicked);
+        }
+        new_selection
+    }
+    fn drag_datas_for_selection(
+        movie: &Movie,
+        stage_symbol_index: SymbolIndexOrRoot,
+        selection: &[PlacedSymbolIndex],
+        start_x: f64,
+        start_y: f64,
+    ) -> Vec<DragData> {
+        let placed_symbols = movie.get_placed_symbols(stage_symbol_index);
+        selection
+            .iter()
+            .map(|placed_symbol_index| DragData {
+                symbol_start_transform: placed_symbols[*placed_symbol_index].transform.clone(),
+                start_x,
+                start_y,
+                place_symbol_index: *placed_symbol_index,
+            })
+            .collect()
+    }
+
     fn get_placed_symbol_at_position(
         &self,
         movie: &Movie,
@@ -881,12 +1350,10 @@ impl Stage {
         y: f64,
         symbol_index: SymbolIndexOrRoot,
     ) -> SymbolIndexOrRoot {
-        let world_space_position =
-            self.camera
-                .screen_to_world_matrix(Self::stage_size_from_viewport_dimensions(
-                    viewport_dimensions,
-                ))
-                * Matrix::translate(Twips::from_pixels(x), Twips::from_pixels(y));
+        let world_space_position = self
+            .camera
+            .screen_to_world_matrix(self.stage_size_from_viewport_dimensions(viewport_dimensions))
+            * Matrix::translate(Twips::from_pixels(x), Twips::from_pixels(y));
 
         self.get_placed_symbol_at_position_local_space(
             movie,
@@ -915,11 +1382,12 @@ impl Stage {
             match symbol {
                 Symbol::Bitmap(bitmap) => {
                     if let BitmapCacheStatus::Cached(cached_bitmap) = &bitmap.cache {
+                        // a negative scale flips the symbol, the bounds stay the same
                         let half_width = cached_bitmap.image.width() as f64
-                            * place_symbol.transform.x_scale
+                            * place_symbol.transform.x_scale.abs()
                             / 2.0;
                         let half_height = cached_bitmap.image.height() as f64
-                            * place_symbol.transform.y_scale
+                            * place_symbol.transform.y_scale.abs()
                             / 2.0;
                         if x > place_symbol_x - half_width
                             && y > place_symbol_y - half_height
@@ -953,8 +1421,10 @@ impl Stage {
                 }
                 Symbol::Font(_) => {
                     let text_properties = place_symbol.text.as_ref().unwrap();
-                    let half_width = text_properties.width * place_symbol.transform.x_scale / 2.0;
-                    let half_height = text_properties.height * place_symbol.transform.y_scale / 2.0;
+                    let half_width =
+                        text_properties.width * place_symbol.transform.x_scale.abs() / 2.0;
+                    let half_height =
+                        text_properties.height * place_symbol.transform.y_scale.abs() / 2.0;
                     if x > place_symbol_x - half_width
                         && y > place_symbol_y - half_height
                         && x < place_symbol_x + half_width
@@ -963,6 +1433,15 @@ impl Stage {
                         return Some(i);
                     }
                 }
+                Symbol::StaticText(static_text) => {
+                    if Bounds::of_static_text(static_text).contains_point(
+                        (x - place_symbol_x) / place_symbol.transform.x_scale,
+                        (y - place_symbol_y) / place_symbol.transform.y_scale,
+                    ) {
+                        return Some(i);
+                    }
+                }
+                Symbol::Sound(_) => {}
             }
         }
         None
@@ -981,7 +1460,276 @@ impl Stage {
             self.camera.reset_to_center_stage(&ctx.movie.properties);
         }
     }
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+    pub fn set_camera(&mut self, camera: Camera) {
+        self.camera = camera;
+    }
     pub fn reset_text_renderer(&mut self) {
         self.text_renderer = None;
     }
+    // ids of placed symbols that don't exist anymore are never used again
+    pub fn remove_unused_edit_texts(&mut self, movie: &Movie) {
+        let Some(text_renderer) = &mut self.text_renderer else {
+            return;
+        };
+        for edit_text_id in text_renderer.edit_text_ids() {
+            let (symbol_index, placed_symbol_index) = Self::edit_text_placed_symbol(edit_text_id);
+            let placed_symbol_count = match symbol_index {
+                None => movie.root.len(),
+                Some(symbol_index) => match movie.symbols.get(symbol_index) {
+                    Some(Symbol::MovieClip(movieclip)) => movieclip.place_symbols.len(),
+                    _ => 0,
+                },
+            };
+            if placed_symbol_index >= placed_symbol_count {
+                text_renderer.remove_edit_text(edit_text_id);
+            }
+        }
+    }
+    // every clip has its own edit text ids, so a text in the root and a text in a clip at the same
+    // index don't replace each other on every redraw (the root is drawn behind the clip you edit)
+    fn edit_text_id(
+        symbol_index: SymbolIndexOrRoot,
+        placed_symbol_index: PlacedSymbolIndex,
+    ) -> usize {
+        let scope = symbol_index.map_or(0, |symbol_index| symbol_index + 1);
+        (scope << EDIT_TEXT_ID_SCOPE_SHIFT) | placed_symbol_index
+    }
+    fn edit_text_placed_symbol(edit_text_id: usize) -> (SymbolIndexOrRoot, PlacedSymbolIndex) {
+        let scope = edit_text_id >> EDIT_TEXT_ID_SCOPE_SHIFT;
+        let placed_symbol_index = edit_text_id & ((1 << EDIT_TEXT_ID_SCOPE_SHIFT) - 1);
+        (scope.checked_sub(1), placed_symbol_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use flits_core::{Bitmap, BitmapProperties, FlitsFont, FontCharacters, MovieClip};
+
+    use crate::message_bus::MessageBus;
+
+    use super::*;
+
+    #[test]
+    fn movieclip_bounds_update_when_a_child_is_added() {
+        let mut movie = Movie::default();
+        movie
+            .symbols
+            .push(Symbol::MovieClip(MovieClip::new("outer")));
+        movie
+            .symbols
+            .push(Symbol::MovieClip(MovieClip::new("inner")));
+        let outer = PlaceSymbol::new(0);
+        assert_eq!(
+            Stage::local_bounds_of_placed_symbol(&movie, &outer),
+            Some(Stage::empty_clip_bounds())
+        );
+
+        let Symbol::MovieClip(movieclip) = &mut movie.symbols[0] else {
+            unreachable!();
+        };
+        movieclip.place_symbols.push(
+            PlaceSymbol::new(1).with_transform(EditorTransform::default().add_offset(100.0, 0.0)),
+        );
+        assert_eq!(
+            Stage::local_bounds_of_placed_symbol(&movie, &outer),
+            Some(Bounds::from_points(0.0, -8.0, 108.0, 8.0))
+        );
+    }
+
+    #[test]
+    fn shift_clicking_a_selected_symbol_does_not_drag_it() {
+        let mut movie = Movie::default();
+        movie
+            .symbols
+            .push(Symbol::MovieClip(MovieClip::new("clip")));
+        movie.root.push(PlaceSymbol::new(0));
+        movie.root.push(
+            PlaceSymbol::new(0).with_transform(EditorTransform::default().add_offset(100.0, 0.0)),
+        );
+
+        let selection = Stage::selection_after_click(&[0, 1], 1, true);
+        assert_eq!(selection, vec![0]);
+        let drag_datas = Stage::drag_datas_for_selection(&movie, None, &selection, 0.0, 0.0);
+        assert_eq!(drag_datas.len(), 1);
+        assert_eq!(drag_datas[0].place_symbol_index, 0);
+    }
+
+    #[test]
+    fn clicking_an_unselected_symbol_replaces_the_selection() {
+        assert_eq!(Stage::selection_after_click(&[0, 1], 2, false), vec![2]);
+        assert_eq!(
+            Stage::selection_after_click(&[0, 1], 2, true),
+            vec![0, 1, 2]
+        );
+        // without shift an already selected symbol stays selected with the rest, so they can be dragged together
+        assert_eq!(Stage::selection_after_click(&[0, 1], 1, false), vec![0, 1]);
+    }
+
+    #[test]
+    fn dragged_symbol_follows_the_mouse_when_zoomed_and_panned() {
+        let viewport_dimensions = ViewportDimensions {
+            width: 1280,
+            height: 720,
+            scale_factor: 1.0,
+        };
+        for zoom_amount in [0.0, 1.0, 3.0] {
+            let mut movie = Movie::default();
+            movie
+                .symbols
+                .push(Symbol::MovieClip(MovieClip::new("clip")));
+            let mut stage = Stage::new(&movie.properties, PathBuf::new());
+            stage.camera.start_drag(0.0, 0.0);
+            stage.camera.update_drag(30.0, -20.0);
+            stage.camera.stop_drag();
+            stage.camera.zoom(zoom_amount);
+            let zoom_level = stage.camera.zoom_level();
+
+            let stage_size = stage.stage_size_from_viewport_dimensions(viewport_dimensions);
+            let to_world = |x: f64, y: f64| {
+                let position = stage.camera.screen_to_world_matrix(stage_size)
+                    * Matrix::translate(Twips::from_pixels(x), Twips::from_pixels(y));
+                (position.tx.to_pixels(), position.ty.to_pixels())
+            };
+            // put the symbol under the mouse so the click selects it
+            let (start_x, start_y) = to_world(100.0, 100.0);
+            movie.root.push(
+                PlaceSymbol::new(0)
+                    .with_transform(EditorTransform::default().add_offset(start_x, start_y)),
+            );
+
+            let selection = Selection::default();
+            let message_bus = MessageBus::new();
+            let mut ctx = MutableContext {
+                movie: &mut movie,
+                selection: &selection,
+                modifiers: egui::Modifiers::default(),
+                message_bus: &message_bus,
+                viewport_dimensions,
+            };
+            stage.handle_mouse_input(
+                &mut ctx,
+                100.0,
+                100.0,
+                MouseButton::Left,
+                ElementState::Pressed,
+            );
+            stage.handle_mouse_move(&mut ctx, 150.0, 100.0);
+
+            let transform = &movie.root[0].transform;
+            // 50 pixels on the screen, so the symbol stays under the mouse
+            let delta_x = transform.x - start_x;
+            assert!(
+                (delta_x - 50.0 / zoom_level).abs() < 0.05,
+                "moved {} at zoom level {}",
+                delta_x,
+                zoom_level
+            );
+            assert!((delta_x * zoom_level - 50.0).abs() < 0.05);
+            assert_eq!(transform.y, start_y);
+        }
+    }
+
+    #[test]
+    fn flipped_symbols_can_be_selected() {
+        let mut movie = Movie::default();
+        movie.symbols.push(Symbol::Font(FlitsFont {
+            path: "font.ttf".into(),
+            characters: FontCharacters {
+                ascii: true,
+                additional_characters: "".into(),
+                only_used: false,
+            },
+        }));
+        movie
+            .symbols
+            .push(Symbol::MovieClip(MovieClip::new("clip")));
+        let mut text =
+            PlaceSymbol::new(0).with_transform(EditorTransform::default().add_offset(100.0, 100.0));
+        text.text = Some(Box::new(TextProperties::new()));
+        text.transform.x_scale = -1.0;
+        movie.root.push(text);
+        let mut clip = PlaceSymbol::new(1);
+        clip.transform.x_scale = -1.0;
+        clip.transform.y_scale = -1.0;
+        movie.root.push(clip);
+        movie.symbols.push(Symbol::Bitmap(Bitmap {
+            properties: BitmapProperties {
+                name: "image.png".into(),
+                path: "image.png".into(),
+                animation: None,
+                do_not_export: false,
+            },
+            cache: BitmapCacheStatus::Cached(CachedBitmap {
+                image: image::RgbaImage::new(20, 10).into(),
+                bitmap_handle: None,
+            }),
+        }));
+        let mut bitmap =
+            PlaceSymbol::new(2).with_transform(EditorTransform::default().add_offset(300.0, 300.0));
+        bitmap.transform.x_scale = -1.0;
+        movie.root.push(bitmap);
+        let stage = Stage::new(&movie.properties, PathBuf::new());
+
+        // the text field is 280x50 around its position
+        assert_eq!(
+            stage.get_placed_symbol_at_position_local_space(&movie, 200.0, 110.0, None),
+            Some(0)
+        );
+        assert_eq!(
+            stage.get_placed_symbol_at_position_local_space(&movie, 300.0, 110.0, None),
+            None
+        );
+        assert_eq!(
+            stage.get_placed_symbol_at_position_local_space(&movie, 2.0, -2.0, None),
+            Some(1)
+        );
+        // the bitmap is 20x10 around its position
+        assert_eq!(
+            stage.get_placed_symbol_at_position_local_space(&movie, 295.0, 302.0, None),
+            Some(2)
+        );
+        assert_eq!(
+            stage.get_placed_symbol_at_position_local_space(&movie, 289.0, 300.0, None),
+            None
+        );
+    }
+
+    #[test]
+    fn edit_texts_in_different_clips_have_different_ids() {
+        let root_id = Stage::edit_text_id(None, 3);
+        let clip_id = Stage::edit_text_id(Some(0), 3);
+        assert_ne!(root_id, clip_id);
+        assert_eq!(Stage::edit_text_placed_symbol(root_id), (None, 3));
+        assert_eq!(Stage::edit_text_placed_symbol(clip_id), (Some(0), 3));
+    }
+
+    #[test]
+    fn movieclip_with_only_unloaded_bitmaps_can_still_be_selected() {
+        let mut movie = Movie::default();
+        movie.symbols.push(Symbol::Bitmap(Bitmap {
+            properties: BitmapProperties {
+                name: "image.png".into(),
+                path: "image.png".into(),
+                animation: None,
+                do_not_export: false,
+            },
+            cache: BitmapCacheStatus::Uncached,
+        }));
+        movie
+            .symbols
+            .push(Symbol::MovieClip(MovieClip::new("clip")));
+        let Symbol::MovieClip(movieclip) = &mut movie.symbols[1] else {
+            unreachable!();
+        };
+        movieclip.place_symbols.push(
+            PlaceSymbol::new(0).with_transform(EditorTransform::default().add_offset(50.0, 50.0)),
+        );
+        assert_eq!(
+            Stage::local_bounds_of_placed_symbol(&movie, &PlaceSymbol::new(1)),
+            Some(Stage::empty_clip_bounds())
+        );
+    }
 }
@@ -1,15 +1,22 @@
 use ruffle_render::matrix::Matrix;
+use serde::{Deserialize, Serialize};
 use swf::Twips;
 
+#[cfg(test)]
+use flits_core::bounds::Bounds;
 use flits_core::MovieProperties;
 
 use crate::editor::StageSize;
 
+const MIN_ZOOM_LEVEL: f64 = 0.1;
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Camera {
     // x and y are the world coordinates at the center of the screen
     x: f64,
     y: f64,
     zoom_level: f64,
+    #[serde(skip)]
     drag_data: Option<CameraDragData>,
 }
 impl Camera {
@@ -39,6 +46,16 @@ impl Camera {
             .inverse()
             .unwrap_or(Matrix::IDENTITY) // TODO: does this make sense?
     }
+    // the camera doesn't rotate, so transforming the corners is enough
+    // only the tests convert whole rects, the stage converts with the matrices
+    #[cfg(test)]
+    pub fn world_to_screen_rect(&self, world_rect: Bounds, stage_size: &StageSize) -> Bounds {
+        transform_rect(self.world_to_screen_matrix(*stage_size), world_rect)
+    }
+    #[cfg(test)]
+    pub fn screen_to_world_rect(&self, screen_rect: Bounds, stage_size: &StageSize) -> Bounds {
+        transform_rect(self.screen_to_world_matrix(*stage_size), screen_rect)
+    }
 
     pub fn start_drag(&mut self, mouse_x: f64, mouse_y: f64) {
         self.drag_data = Some(CameraDragData {
@@ -76,14 +93,67 @@ impl Camera {
     }
     // TODO: maybe just hardcode the zoom percentages: https://www.uxpin.com/studio/blog/the-strikingly-precise-zoom/
     pub fn zoom(&mut self, zoom_amount: f64) {
-        self.zoom_level += zoom_amount;
+        self.zoom_level = f64::max(self.zoom_level + zoom_amount, MIN_ZOOM_LEVEL);
+    }
+    // zoom while keeping the world position under the cursor at the same place on the screen
+    pub fn zoom_towards(
+        &mut self,
+        zoom_amount: f64,
+        screen_x: f64,
+        screen_y: f64,
+        stage_size: &StageSize,
+    ) {
+        // offset from the center of the screen, the camera position is at the center
+        let offset_x = screen_x - stage_size.width as f64 / 2.0;
+        let offset_y = screen_y - stage_size.height as f64 / 2.0;
+        let world_x = self.x + offset_x / self.zoom_level;
+        let world_y = self.y + offset_y / self.zoom_level;
+        self.zoom(zoom_amount);
+        self.x = world_x - offset_x / self.zoom_level;
+        self.y = world_y - offset_y / self.zoom_level;
     }
 
     pub fn zoom_level(&self) -> f64 {
         self.zoom_level
     }
 }
+#[cfg(test)]
+fn transform_rect(matrix: Matrix, rect: Bounds) -> Bounds {
+    let transform_point = |x: f64, y: f64| {
+        let point = matrix * Matrix::translate(Twips::from_pixels(x), Twips::from_pixels(y));
+        (point.tx.to_pixels(), point.ty.to_pixels())
+    };
+    let (x1, y1) = transform_point(rect.min_x, rect.min_y);
+    let (x2, y2) = transform_point(rect.max_x, rect.max_y);
+    Bounds::from_points(x1, y1, x2, y2)
+}
+
+#[derive(Clone)]
 struct CameraDragData {
     previous_x: f64,
     previous_y: f64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn world_rect_round_trips_through_screen_space() {
+        let mut camera = Camera::new_center_stage(&MovieProperties::default());
+        camera.zoom(1.0);
+        let stage_size = StageSize {
+            width: 800,
+            height: 600,
+        };
+        let world_rect = Bounds::from_points(10.0, 20.0, 110.0, 70.0);
+        let screen_rect = camera.world_to_screen_rect(world_rect, &stage_size);
+        // zoomed in twice as far
+        assert_eq!(screen_rect.width(), 200.0);
+        assert_eq!(screen_rect.height(), 100.0);
+        assert_eq!(
+            camera.screen_to_world_rect(screen_rect, &stage_size),
+            world_rect
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use flits_core::bounds::Bounds;
+
+// in screen pixels, the handles stay the same size when zooming
+pub const HANDLE_SIZE: f64 = 8.0;
+// a resized symbol can't get smaller than this, in world pixels
+const MIN_RESIZE_SIZE: f64 = 1.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ResizeHandle {
+    TopLeft,
+    Top,
+    TopRight,
+    Right,
+    BottomRight,
+    Bottom,
+    BottomLeft,
+    Left,
+}
+impl ResizeHandle {
+    pub const ALL: [ResizeHandle; 8] = [
+        ResizeHandle::TopLeft,
+        ResizeHandle::Top,
+        ResizeHandle::TopRight,
+        ResizeHandle::Right,
+        ResizeHandle::BottomRight,
+        ResizeHandle::Bottom,
+        ResizeHandle::BottomLeft,
+        ResizeHandle::Left,
+    ];
+
+    // the center of the handle in world space
+    pub fn position(&self, bounds: &Bounds) -> (f64, f64) {
+        let (center_x, center_y) = bounds.center();
+        let x = match self.horizontal_edge() {
+            Some(Edge::Min) => bounds.min_x,
+            Some(Edge::Max) => bounds.max_x,
+            None => center_x,
+        };
+        let y = match self.vertical_edge() {
+            Some(Edge::Min) => bounds.min_y,
+            Some(Edge::Max) => bounds.max_y,
+            None => center_y,
+        };
+        (x, y)
+    }
+
+    pub fn cursor_icon(&self) -> egui::CursorIcon {
+        match self {
+            ResizeHandle::TopLeft => egui::CursorIcon::ResizeNorthWest,
+            ResizeHandle::Top => egui::CursorIcon::ResizeNorth,
+            ResizeHandle::TopRight => egui::CursorIcon::ResizeNorthEast,
+            ResizeHandle::Right => egui::CursorIcon::ResizeEast,
+            ResizeHandle::BottomRight => egui::CursorIcon::ResizeSouthEast,
+            ResizeHandle::Bottom => egui::CursorIcon::ResizeSouth,
+            ResizeHandle::BottomLeft => egui::CursorIcon::ResizeSouthWest,
+            ResizeHandle::Left => egui::CursorIcon::ResizeWest,
+        }
+    }
+
+    // the scale factors and the point that stays in place when dragging this handle to the mouse
+    // the opposite side of the bounds doesn't move
+    pub fn resize(&self, start_bounds: &Bounds, mouse_x: f64, mouse_y: f64) -> ResizeResult {
+        let (x_factor, pivot_x) = Self::resize_axis(
+            self.horizontal_edge(),
+            start_bounds.min_x,
+            start_bounds.max_x,
+            mouse_x,
+        );
+        let (y_factor, pivot_y) = Self::resize_axis(
+            self.vertical_edge(),
+            start_bounds.min_y,
+            start_bounds.max_y,
+            mouse_y,
+        );
+        ResizeResult {
+            x_factor,
+            y_factor,
+            pivot_x,
+            pivot_y,
+        }
+    }
+    fn resize_axis(edge: Option<Edge>, min: f64, max: f64, mouse: f64) -> (f64, f64) {
+        let size = max - min;
+        // symbols without a size on this axis can't be scaled
+        if size <= 0.0 {
+            return (1.0, min);
+        }
+        match edge {
+            Some(Edge::Min) => (f64::max(max - mouse, MIN_RESIZE_SIZE) / size, max),
+            Some(Edge::Max) => (f64::max(mouse - min, MIN_RESIZE_SIZE) / size, min),
+            None => (1.0, min),
+        }
+    }
+
+    fn horizontal_edge(&self) -> Option<Edge> {
+        match self {
+            ResizeHandle::TopLeft | ResizeHandle::Left | ResizeHandle::BottomLeft => {
+                Some(Edge::Min)
+            }
+            ResizeHandle::TopRight | ResizeHandle::Right | ResizeHandle::BottomRight => {
+                Some(Edge::Max)
+            }
+            ResizeHandle::Top | ResizeHandle::Bottom => None,
+        }
+    }
+    fn vertical_edge(&self) -> Option<Edge> {
+        match self {
+            ResizeHandle::TopLeft | ResizeHandle::Top | ResizeHandle::TopRight => Some(Edge::Min),
+            ResizeHandle::BottomLeft | ResizeHandle::Bottom | ResizeHandle::BottomRight => {
+                Some(Edge::Max)
+            }
+            ResizeHandle::Left | ResizeHandle::Right => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Edge {
+    Min,
+    Max,
+}
+
+pub struct ResizeResult {
+    pub x_factor: f64,
+    pub y_factor: f64,
+    pub pivot_x: f64,
+    pub pivot_y: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_left_handle_keeps_bottom_right_in_place() {
+        let bounds = Bounds::from_points(0.0, 0.0, 100.0, 50.0);
+        let result = ResizeHandle::TopLeft.resize(&bounds, -100.0, 25.0);
+        assert_eq!(result.x_factor, 2.0);
+        assert_eq!(result.y_factor, 0.5);
+        assert_eq!((result.pivot_x, result.pivot_y), (100.0, 50.0));
+    }
+
+    #[test]
+    fn edge_handle_only_scales_one_axis() {
+        let bounds = Bounds::from_points(0.0, 0.0, 100.0, 50.0);
+        let result = ResizeHandle::Right.resize(&bounds, 150.0, 1000.0);
+        assert_eq!(result.x_factor, 1.5);
+        assert_eq!(result.y_factor, 1.0);
+        assert_eq!(result.pivot_x, 0.0);
+    }
+}
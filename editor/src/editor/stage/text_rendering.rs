@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use flits_core::{FlitsFont, FontContainer};
+use flits_core::{FlitsFont, FontContainer, SymbolIndex, TextProperties};
 use flits_text_rendering::{SwfFontsContainer, SwfFontsContainerBuilder};
 use swf::EditText;
 
@@ -13,8 +13,15 @@ impl FontsConverterBuilder {
         FontsConverterBuilder { fonts, directory }
     }
 }
+// the font of a text field and its properties
+pub type EditTextProperties = (SymbolIndex, TextProperties);
+
 impl SwfFontsContainerBuilder for FontsConverterBuilder {
-    fn build<'a>(&self) -> Box<dyn SwfFontsContainer + 'a> {
+    type EditTextProperties = EditTextProperties;
+
+    fn build<'a>(
+        &self,
+    ) -> Box<dyn SwfFontsContainer<EditTextProperties = EditTextProperties> + 'a> {
         Box::new(FontsConverter::new(
             self.fonts.clone(),
             self.directory.clone(),
@@ -36,6 +43,8 @@ impl FontsConverter {
     }
 }
 impl SwfFontsContainer for FontsConverter {
+    type EditTextProperties = EditTextProperties;
+
     fn convert_fonts(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         self.font_container
             .convert_fonts(&self.fonts, self.directory.clone())
@@ -46,12 +55,8 @@ impl SwfFontsContainer for FontsConverter {
 
     fn convert_edit_text<'a>(
         &'a mut self,
-        properties: Box<dyn std::any::Any>,
+        (font_symbol_index, edit_text_properties): EditTextProperties,
     ) -> Result<EditText<'a>, Box<dyn std::error::Error>> {
-        let Ok(properties) = properties.downcast() else {
-            return Err("".into());
-        };
-        let (font_symbol_index, edit_text_properties) = *properties;
         self.font_container
             .convert_text_field(font_symbol_index, edit_text_properties)
     }
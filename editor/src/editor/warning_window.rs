@@ -0,0 +1,68 @@
+pub struct WarningWindow {
+    warnings: Vec<String>,
+    // names of the symbols that were left out of the export
+    pruned_symbols: Vec<String>,
+    // names of the symbols that changed since the last export
+    changed_symbols: Vec<String>,
+}
+impl WarningWindow {
+    pub fn new(
+        warnings: Vec<String>,
+        pruned_symbols: Vec<String>,
+        changed_symbols: Vec<String>,
+    ) -> Option<Self> {
+        if warnings.is_empty() && pruned_symbols.is_empty() && changed_symbols.is_empty() {
+            return None;
+        }
+        Some(Self {
+            warnings,
+            pruned_symbols,
+            changed_symbols,
+        })
+    }
+}
+pub trait WarningWindowTrait {
+    fn do_ui(&mut self, egui_ctx: &egui::Context);
+}
+impl WarningWindowTrait for Option<WarningWindow> {
+    fn do_ui(&mut self, egui_ctx: &egui::Context) {
+        let Some(me) = self else {
+            return;
+        };
+        // unlike errors, warnings don't block the editor
+        let mut is_window_open = true;
+        egui::Window::new("Export results")
+            .open(&mut is_window_open)
+            .collapsible(false)
+            .show(egui_ctx, |ui| {
+                let warn_color = ui.style().visuals.warn_fg_color;
+                for warning in &me.warnings {
+                    ui.label(egui::RichText::new(warning).color(warn_color));
+                }
+                if !me.pruned_symbols.is_empty() {
+                    ui.label("Left out unused symbols:");
+                    egui::ScrollArea::vertical()
+                        .max_height(150.0)
+                        .show(ui, |ui| {
+                            for name in &me.pruned_symbols {
+                                ui.label(name);
+                            }
+                        });
+                }
+                if !me.changed_symbols.is_empty() {
+                    ui.label("Changed since last export:");
+                    egui::ScrollArea::vertical()
+                        .id_salt("changed_symbols")
+                        .max_height(150.0)
+                        .show(ui, |ui| {
+                            for name in &me.changed_symbols {
+                                ui.label(name);
+                            }
+                        });
+                }
+            });
+        if !is_window_open {
+            *self = None;
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use flits_core::{Movie, SymbolIndexOrRoot};
+use undo::Record;
+
+use crate::edit::{MovieEdit, MoviePropertiesOutput};
+
+// every editing scope (the root or a movieclip) has its own undo stack, so undoing
+// inside a movieclip doesn't undo things in the root and vice versa (like Adobe Animate)
+pub struct MovieHistory {
+    records: HashMap<SymbolIndexOrRoot, Record<MovieEdit>>,
+    // movieclip records are thrown away when symbol indexes change,
+    // this remembers if any of them had unsaved changes
+    discarded_unsaved_changes: bool,
+    // changes that aren't edits, like paths of moved bitmaps that were changed when loading
+    changed_without_edit: bool,
+}
+impl MovieHistory {
+    pub fn new() -> Self {
+        MovieHistory {
+            records: HashMap::new(),
+            discarded_unsaved_changes: false,
+            changed_without_edit: false,
+        }
+    }
+
+    pub fn edit(&mut self, movie: &mut Movie, edit: MovieEdit) -> MoviePropertiesOutput {
+        let scope = edit.scope();
+        // adding a symbol appends it to the end, so the existing indexes stay valid
+        let appends_symbol = matches!(
+            edit,
+            MovieEdit::AddMovieClip(_) | MovieEdit::AddStaticText(_)
+        );
+        let symbol_count = movie.symbols.len();
+        let output = self
+            .records
+            .entry(scope)
+            .or_insert_with(Record::new)
+            .edit(movie, edit);
+        if !appends_symbol {
+            self.discard_movieclip_records_if_symbols_changed(movie, symbol_count);
+        }
+        output
+    }
+
+    pub fn undo(
+        &mut self,
+        movie: &mut Movie,
+        scope: SymbolIndexOrRoot,
+    ) -> Option<MoviePropertiesOutput> {
+        let symbol_count = movie.symbols.len();
+        let output = self.records.get_mut(&scope)?.undo(movie);
+        self.discard_movieclip_records_if_symbols_changed(movie, symbol_count);
+        output
+    }
+
+    pub fn redo(
+        &mut self,
+        movie: &mut Movie,
+        scope: SymbolIndexOrRoot,
+    ) -> Option<MoviePropertiesOutput> {
+        let symbol_count = movie.symbols.len();
+        let output = self.records.get_mut(&scope)?.redo(movie);
+        self.discard_movieclip_records_if_symbols_changed(movie, symbol_count);
+        output
+    }
+
+    pub fn set_saved(&mut self) {
+        for record in self.records.values_mut() {
+            record.set_saved(true);
+        }
+        self.discarded_unsaved_changes = false;
+        self.changed_without_edit = false;
+    }
+
+    // for changes to the movie that can't be undone
+    pub fn set_changed_without_edit(&mut self) {
+        self.changed_without_edit = true;
+    }
+
+    pub fn is_saved(&self) -> bool {
+        !self.discarded_unsaved_changes
+            && !self.changed_without_edit
+            && self.records.values().all(|record| record.is_saved())
+    }
+
+    fn discard_movieclip_records_if_symbols_changed(
+        &mut self,
+        movie: &Movie,
+        symbol_count_before: usize,
+    ) {
+        if movie.symbols.len() == symbol_count_before {
+            return;
+        }
+        // the edits in the movieclip records refer to symbol indexes that are no longer correct
+        let mut discarded_unsaved_changes = false;
+        self.records.retain(|scope, record| {
+            if scope.is_none() {
+                return true;
+            }
+            if !record.is_saved() {
+                discarded_unsaved_changes = true;
+            }
+            false
+        });
+        if discarded_unsaved_changes {
+            self.discarded_unsaved_changes = true;
+        }
+    }
+}
@@ -2,6 +2,7 @@ mod custom_event;
 mod editor;
 
 mod edit;
+mod history;
 mod message;
 mod message_bus;
 
@@ -5,9 +5,16 @@ use crate::{edit::MovieEdit, editor::stage::StageMessage, FlitsEvent};
 pub enum EditorMessage {
     Save,
     Export,
+    OpenExportOptionsWindow,
+    ExportArchive,
     Run,
     OpenNewSymbolWindow,
+    OpenAddFontWindow,
+    OpenBatchRenameWindow,
+    OpenScriptsWindow,
+    OpenShortcutsWindow,
     ChangeSelectedSymbol(SymbolIndexOrRoot),
+    OpenSymbolFile(SymbolIndex),
     ChangeSelectedPlacedSymbols(Vec<SymbolIndex>),
     SelectAll,
     DeleteSelection,
@@ -6,6 +6,7 @@ use swf::{CharacterId, SwfBuf, Tag};
 use typed_arena::Arena;
 
 pub mod comparison_swf;
+pub mod glyph_dump;
 mod swfmill;
 
 pub(super) trait DebugFontSwfBuilder<'a>: FontSwfBuilder<'a> {
@@ -25,7 +26,7 @@ pub(super) fn compare_swfmill_font<'a>(
     swfmill::build_font_swfmill(
         name,
         path,
-        characters.clone(),
+        characters,
         character_id,
         swf_builder,
         allocator,
@@ -58,25 +59,44 @@ pub(super) fn compare_swfmill_font<'a>(
         return Err("SWFMill and Flits fonts do not have the same number of glyphs".into());
     }
 
+    println!();
+    println!("| Metric | Flits | SWFMill |");
+    println!("| --- | --- | --- |");
     println!(
-        "Layout: Flits: {:?} SWFMill: {:?}",
-        flits_font.layout, swfmill_font.layout
+        "| Ascent | {} | {} |",
+        debug_layout(flits_font, |layout| layout.ascent as i32),
+        debug_layout(swfmill_font, |layout| layout.ascent as i32)
     );
+    println!(
+        "| Descent | {} | {} |",
+        debug_layout(flits_font, |layout| layout.descent as i32),
+        debug_layout(swfmill_font, |layout| layout.descent as i32)
+    );
+    println!(
+        "| Leading | {} | {} |",
+        debug_layout(flits_font, |layout| layout.leading as i32),
+        debug_layout(swfmill_font, |layout| layout.leading as i32)
+    );
+    println!(
+        "| x-height | {} | {} |",
+        debug_x_height(flits_font),
+        debug_x_height(swfmill_font)
+    );
+    println!();
 
-    for index in 0..flits_font.glyphs.len() {
-        println!("Glyph: {}", characters.chars().skip(index).next().unwrap());
-        let flits_glyph = &flits_font.glyphs[index];
-        let swfmill_glyph = &swfmill_font.glyphs[index];
-        println!(
-            "Advance: Flits: {:?} SWFMill: {:?}",
-            flits_glyph.advance, swfmill_glyph.advance
-        );
+    println!("| Glyph | Advance (Flits) | Advance (SWFMill) | Bounds (Flits) | Bounds (SWFMill) |");
+    println!("| --- | --- | --- | --- | --- |");
+    for (flits_glyph, swfmill_glyph) in flits_font.glyphs.iter().zip(&swfmill_font.glyphs) {
         println!(
-            "Bounds: Flits: {} SWFMill: {}",
+            "| {} | {} | {} | {} | {} |",
+            char::from_u32(flits_glyph.code as u32).unwrap_or(char::REPLACEMENT_CHARACTER),
+            flits_glyph.advance,
+            swfmill_glyph.advance,
             debug_twips_rect(flits_glyph.bounds),
             debug_twips_rect(swfmill_glyph.bounds)
-        )
+        );
     }
+    println!();
 
     Ok(())
 }
@@ -93,6 +113,23 @@ fn debug_twips_rect(rect: Option<swf::Rectangle<swf::Twips>>) -> String {
         None => "None".into(),
     }
 }
+fn debug_layout(font: &swf::Font, metric: impl Fn(&swf::FontLayout) -> i32) -> String {
+    match &font.layout {
+        Some(layout) => metric(layout).to_string(),
+        None => "None".into(),
+    }
+}
+/// The height of the 'x' glyph, only available when 'x' is one of the converted characters
+fn debug_x_height(font: &swf::Font) -> String {
+    font.glyphs
+        .iter()
+        .find(|glyph| glyph.code == 'x' as u16)
+        .and_then(|glyph| glyph.bounds)
+        .map_or_else(
+            || "-".into(),
+            |bounds| (-bounds.y_min.to_pixels()).to_string(),
+        )
+}
 fn print_props(font: &Box<swf::Font>) {
     for flag in font.flags.iter_names() {
         println!("  {}", flag.0);
@@ -0,0 +1,117 @@
+use swf::{Font, Glyph, ShapeRecord};
+
+// width of a glyph in characters, the height follows from the bounds of the glyph
+const DUMP_WIDTH: usize = 40;
+const MAX_DUMP_HEIGHT: usize = 40;
+// curves are split into this many straight lines
+const CURVE_SEGMENTS: usize = 8;
+
+/// Prints every glyph of the font as ascii art
+pub fn print_glyph_dump(font: &Font) {
+    for glyph in &font.glyphs {
+        println!(
+            "Glyph: '{}'",
+            char::from_u32(glyph.code as u32).unwrap_or(char::REPLACEMENT_CHARACTER)
+        );
+        for line in glyph_to_ascii(glyph) {
+            println!("{}", line);
+        }
+        println!();
+    }
+}
+
+fn glyph_to_ascii(glyph: &Glyph) -> Vec<String> {
+    let edges = glyph_edges(glyph);
+    let Some(bounds) = glyph.bounds.filter(|bounds| {
+        bounds.x_max > bounds.x_min && bounds.y_max > bounds.y_min && !edges.is_empty()
+    }) else {
+        return vec!["(empty)".into()];
+    };
+    let x_min = bounds.x_min.to_pixels();
+    let y_min = bounds.y_min.to_pixels();
+    let width = bounds.x_max.to_pixels() - x_min;
+    let height = bounds.y_max.to_pixels() - y_min;
+    // characters in a terminal are about twice as high as they are wide
+    let rows =
+        ((DUMP_WIDTH as f64 * height / width / 2.0).round() as usize).clamp(1, MAX_DUMP_HEIGHT);
+
+    (0..rows)
+        .map(|row| {
+            let y = y_min + (row as f64 + 0.5) * height / rows as f64;
+            (0..DUMP_WIDTH)
+                .map(|column| {
+                    let x = x_min + (column as f64 + 0.5) * width / DUMP_WIDTH as f64;
+                    if is_inside(&edges, x, y) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+type Edge = ((f64, f64), (f64, f64));
+
+/// Converts the shape records to straight edges in pixels
+fn glyph_edges(glyph: &Glyph) -> Vec<Edge> {
+    let mut edges = vec![];
+    let mut x = 0.0;
+    let mut y = 0.0;
+    for shape_record in &glyph.shape_records {
+        match shape_record {
+            ShapeRecord::StyleChange(style_change_data) => {
+                if let Some(move_to) = &style_change_data.move_to {
+                    x = move_to.x.to_pixels();
+                    y = move_to.y.to_pixels();
+                }
+            }
+            ShapeRecord::StraightEdge { delta } => {
+                let end = (x + delta.dx.to_pixels(), y + delta.dy.to_pixels());
+                edges.push(((x, y), end));
+                (x, y) = end;
+            }
+            ShapeRecord::CurvedEdge {
+                control_delta,
+                anchor_delta,
+            } => {
+                let control = (
+                    x + control_delta.dx.to_pixels(),
+                    y + control_delta.dy.to_pixels(),
+                );
+                let anchor = (
+                    control.0 + anchor_delta.dx.to_pixels(),
+                    control.1 + anchor_delta.dy.to_pixels(),
+                );
+                let mut previous = (x, y);
+                for segment in 1..=CURVE_SEGMENTS {
+                    let t = segment as f64 / CURVE_SEGMENTS as f64;
+                    let mt = 1.0 - t;
+                    let point = (
+                        mt * mt * x + 2.0 * mt * t * control.0 + t * t * anchor.0,
+                        mt * mt * y + 2.0 * mt * t * control.1 + t * t * anchor.1,
+                    );
+                    edges.push((previous, point));
+                    previous = point;
+                }
+                (x, y) = anchor;
+            }
+        }
+    }
+    edges
+}
+
+/// Even-odd fill rule, casts a ray to the right and counts the crossed edges
+fn is_inside(edges: &[Edge], x: f64, y: f64) -> bool {
+    let mut inside = false;
+    for ((x0, y0), (x1, y1)) in edges {
+        if (*y0 > y) != (*y1 > y) {
+            let crossing_x = x0 + (y - y0) / (y1 - y0) * (x1 - x0);
+            if crossing_x > x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
@@ -1,7 +1,8 @@
 use std::path::PathBuf;
 
 use swf::{
-    CharacterId, ExportedAsset, Font, Point, PointDelta, Rectangle, ShapeRecord, SwfStr, Tag, Twips,
+    CharacterId, ExportedAsset, Font, KerningRecord, Point, PointDelta, Rectangle, ShapeRecord,
+    SwfStr, Tag, Twips,
 };
 
 pub trait FontSwfBuilder<'a> {
@@ -12,6 +13,7 @@ pub trait FontAllocator {
 }
 
 // adapted from: https://github.com/djcsdy/swfmill/blob/53d769029adc9d817972e1ccd648b7b335bf78b7/src/swft/swft_import_ttf.cpp#L289
+// returns the family name of the font, which is the name text fields use to reference it
 pub fn font_to_swf<'a>(
     name: String,
     path: PathBuf,
@@ -19,10 +21,13 @@ pub fn font_to_swf<'a>(
     character_id: CharacterId,
     swf_builder: &mut impl FontSwfBuilder<'a>,
     allocator: &'a impl FontAllocator,
-) -> Result<(), Box<dyn std::error::Error>> {
-    if path.extension().is_none_or(|extension| extension != "ttf") {
+) -> Result<String, Box<dyn std::error::Error>> {
+    if path
+        .extension()
+        .is_none_or(|extension| extension != "ttf" && extension != "otf")
+    {
         return Err(format!(
-            "Only ttf files are supported, got {}",
+            "Only ttf and otf files are supported, got {}",
             path.to_str().unwrap_or("Unable to convert path to string")
         )
         .into());
@@ -95,6 +100,9 @@ pub fn font_to_swf<'a>(
         // if we still haven' found the right thing, give up
         return Err("Unable to get font family name (even with workaround)".into());
     }
+    let font_family_name = font_family
+        .to_string()
+        .ok_or("Unable to convert font name to unicode")?;
 
     // TODO: find out correct flags, plus we should be able to handle non-ascii characters
     let mut flags = swf::FontFlag::HAS_LAYOUT | swf::FontFlag::IS_ANSI;
@@ -108,18 +116,14 @@ pub fn font_to_swf<'a>(
     swf_builder.add_tag(swf::Tag::DefineFont2(Box::new(Font {
         version: 2, // TODO: Why doesn't this work if it's 3?
         id: character_id,
-        name: allocator.alloc_swf_string(
-            font_family
-                .to_string()
-                .ok_or("Unable to convert font name to unicode")?,
-        ),
+        name: allocator.alloc_swf_string(font_family_name.clone()),
         language: swf::Language::Unknown, // swfmill doesn't seem to set this
         layout: Some(swf::FontLayout {
             ascent: (face.ascender() as i32 * scaling_factor / face.units_per_em() as i32) as u16,
             descent: (-face.descender() as i32 * scaling_factor / face.units_per_em() as i32)
                 as u16,
             leading: (face.line_gap() as i32 * scaling_factor / face.units_per_em() as i32) as i16,
-            kerning: vec![], // TODO: swfmill has a TODO for kerning
+            kerning: extract_kerning_pairs(&face, &characters),
         }),
         glyphs,
         flags,
@@ -132,14 +136,62 @@ pub fn font_to_swf<'a>(
         name: allocator.alloc_swf_string(name),
     }]));
 
-    Ok(())
+    Ok(font_family_name)
 }
 
+/// Returns the kerning of every pair of the given characters that has kerning in the font.
+/// Only the legacy kern table is supported, kerning in the GPOS table is ignored.
+pub fn extract_kerning_pairs(face: &ttf_parser::Face, characters: &str) -> Vec<KerningRecord> {
+    let Some(kern) = face.tables().kern else {
+        return vec![];
+    };
+    let mut characters_vec: Vec<char> = characters.chars().collect();
+    characters_vec.sort();
+    characters_vec.dedup();
+    let glyph_ids: Vec<(char, ttf_parser::GlyphId)> = characters_vec
+        .into_iter()
+        .filter_map(|character| Some((character, face.glyph_index(character)?)))
+        .collect();
+
+    let mut kerning_records = vec![];
+    for (left_character, left_glyph_id) in &glyph_ids {
+        for (right_character, right_glyph_id) in &glyph_ids {
+            let adjustment = kern
+                .subtables
+                .into_iter()
+                .filter(|subtable| {
+                    subtable.horizontal && !subtable.variable && !subtable.has_cross_stream
+                })
+                .find_map(|subtable| subtable.glyphs_kerning(*left_glyph_id, *right_glyph_id));
+            let Some(adjustment) = adjustment else {
+                continue;
+            };
+            if adjustment == 0 {
+                continue;
+            }
+            kerning_records.push(KerningRecord {
+                left_code: *left_character as u16,
+                right_code: *right_character as u16,
+                // same scaling as the advance of the glyphs
+                adjustment: Twips::new(
+                    (adjustment as f64 * (1024.0 / face.units_per_em() as f64)).round() as i32,
+                ),
+            });
+        }
+    }
+    kerning_records
+}
+
+// cubic curves are approximated with this many quadratic curves
+const CUBIC_CURVE_SEGMENTS: u32 = 4;
+
 struct ShapeRecordBuilder {
     shape_scaling_factor_x: f64,
     shape_scaling_factor_y: f64,
     last_x: f32,
     last_y: f32,
+    start_x: f32,
+    start_y: f32,
     shape_records: Vec<ShapeRecord>,
 }
 impl ShapeRecordBuilder {
@@ -149,6 +201,8 @@ impl ShapeRecordBuilder {
             shape_scaling_factor_y,
             last_x: 0.0,
             last_y: 0.0,
+            start_x: 0.0,
+            start_y: 0.0,
             shape_records: vec![],
         }
     }
@@ -157,6 +211,8 @@ impl ttf_parser::OutlineBuilder for ShapeRecordBuilder {
     fn move_to(&mut self, x: f32, y: f32) {
         self.last_x = x;
         self.last_y = y;
+        self.start_x = x;
+        self.start_y = y;
         self.shape_records
             .push(ShapeRecord::StyleChange(Box::new(swf::StyleChangeData {
                 move_to: Some(Point::new(
@@ -202,15 +258,60 @@ impl ttf_parser::OutlineBuilder for ShapeRecordBuilder {
         })
     }
 
-    fn curve_to(&mut self, _x1: f32, _y1: f32, _x2: f32, _y2: f32, _x: f32, _y: f32) {
-        // ttf doesn't support cubic splines according to this:
-        // https://github.com/godotengine/godot/issues/97420
-        // https://typedrawers.com/discussion/4167/why-does-truetype-use-quadratic-splines
-        // so we don't need to deal with this
-        panic!("According to the internet, ttf fonts don't support cubic splines. If you see this it turns out that was wrong.");
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        // ttf fonts only use quadratic splines, but cff based otf fonts use cubic ones
+        // swf only supports quadratic curves, so split the curve into pieces
+        // and approximate every piece with a quadratic curve
+        let start = (self.last_x, self.last_y);
+        let point = |t: f32| {
+            let mt = 1.0 - t;
+            let a = mt * mt * mt;
+            let b = 3.0 * mt * mt * t;
+            let c = 3.0 * mt * t * t;
+            let d = t * t * t;
+            (
+                a * start.0 + b * x1 + c * x2 + d * x,
+                a * start.1 + b * y1 + c * y2 + d * y,
+            )
+        };
+        let derivative = |t: f32| {
+            let mt = 1.0 - t;
+            let a = 3.0 * mt * mt;
+            let b = 6.0 * mt * t;
+            let c = 3.0 * t * t;
+            (
+                a * (x1 - start.0) + b * (x2 - x1) + c * (x - x2),
+                a * (y1 - start.1) + b * (y2 - y1) + c * (y - y2),
+            )
+        };
+        for segment in 0..CUBIC_CURVE_SEGMENTS {
+            let t0 = segment as f32 / CUBIC_CURVE_SEGMENTS as f32;
+            let t1 = (segment + 1) as f32 / CUBIC_CURVE_SEGMENTS as f32;
+            let third = (t1 - t0) / 3.0;
+            let p0 = point(t0);
+            let p3 = point(t1);
+            let d0 = derivative(t0);
+            let d3 = derivative(t1);
+            // the control points of this piece of the cubic curve
+            let c1 = (p0.0 + d0.0 * third, p0.1 + d0.1 * third);
+            let c2 = (p3.0 - d3.0 * third, p3.1 - d3.1 * third);
+            // the quadratic control point that is closest to both cubic control points
+            let control_x = (3.0 * (c1.0 + c2.0) - p0.0 - p3.0) / 4.0;
+            let control_y = (3.0 * (c1.1 + c2.1) - p0.1 - p3.1) / 4.0;
+            if segment == CUBIC_CURVE_SEGMENTS - 1 {
+                // use the exact end point to avoid drifting away from it
+                self.quad_to(control_x, control_y, x, y);
+            } else {
+                self.quad_to(control_x, control_y, p3.0, p3.1);
+            }
+        }
     }
 
     fn close(&mut self) {
+        // ttf contours end at their starting point, but cff contours are closed implicitly
+        if self.last_x != self.start_x || self.last_y != self.start_y {
+            self.line_to(self.start_x, self.start_y);
+        }
         // Flash player expects the last point to match up with the first point, otherwise it shows weird lines.
         // we don't match up exactly due to floating point math
         // compensate by finding how much the last point is off and then move it to match up
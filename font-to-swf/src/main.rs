@@ -13,11 +13,14 @@ mod font_to_swf;
 /// Converts all fonts in example/assets to swf with both Flits Editor and swfmill and
 /// writes an swf with all the fonts in text fields to example/output.swf
 /// for visual comparison. Also prints metrics from both conversions.
+/// With --glyph-dump the glyphs converted by Flits Editor are also printed as ascii art.
 fn main() {
+    let glyph_dump = std::env::args().any(|arg| arg == "--glyph-dump");
     let allocator = DebugAllocator {
         string_arena: Arena::new(),
     };
-    let characters: String = "0123456789".into();
+    // x is included to measure the x-height
+    let characters: String = "0123456789x".into();
     let directory: PathBuf = "example/assets".into();
     let fs_assets = std::fs::read_dir(directory).unwrap();
     let mut swf_builder = DebugFontSwfBuilderImpl { tags: Vec::new() };
@@ -30,7 +33,7 @@ fn main() {
             .into_string()
             .map_err(|original_os_string| format!("Non utf-8 filename: '{:?}'", original_os_string))
             .unwrap();
-        if !file_name.ends_with(".ttf") {
+        if !file_name.ends_with(".ttf") && !file_name.ends_with(".otf") {
             continue;
         }
 
@@ -48,6 +51,16 @@ fn main() {
         )
         .unwrap();
 
+        if glyph_dump {
+            let font = swf_builder.tags.iter().find_map(|tag| match tag {
+                Tag::DefineFont2(font) if font.id == character_id => Some(font),
+                _ => None,
+            });
+            if let Some(font) = font {
+                debug::glyph_dump::print_glyph_dump(font);
+            }
+        }
+
         debug::compare_swfmill_font(
             font_name,
             font_path,
@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+
+use font_to_swf::{font_to_swf, FontAllocator, FontSwfBuilder};
+use swf::{ShapeRecord, Tag};
+use typed_arena::Arena;
+
+struct TestSwfBuilder<'a> {
+    tags: Vec<Tag<'a>>,
+}
+impl<'a> FontSwfBuilder<'a> for TestSwfBuilder<'a> {
+    fn add_tag(&mut self, tag: Tag<'a>) {
+        self.tags.push(tag);
+    }
+}
+
+struct TestAllocator {
+    string_arena: Arena<String>,
+}
+impl FontAllocator for TestAllocator {
+    fn alloc_swf_string(&self, string: String) -> &swf::SwfStr {
+        swf::SwfStr::from_utf8_str(self.string_arena.alloc(string))
+    }
+}
+
+// cubic.otf is a minimal cff based font with a single glyph for 'A' that contains a cubic curve
+#[test]
+fn converts_otf_font() {
+    let allocator = TestAllocator {
+        string_arena: Arena::new(),
+    };
+    let mut swf_builder = TestSwfBuilder { tags: vec![] };
+    let font_name = font_to_swf(
+        "cubic.otf".into(),
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fonts/cubic.otf"),
+        "A".into(),
+        1,
+        &mut swf_builder,
+        &allocator,
+    )
+    .unwrap();
+
+    let font = match &swf_builder.tags[0] {
+        Tag::DefineFont2(font) => font,
+        _ => panic!("Expected the first tag to be a font"),
+    };
+    assert_eq!(font.name.to_string_lossy(swf::UTF_8), "Flits Test");
+    assert_eq!(font_name, "Flits Test");
+    assert_eq!(font.glyphs.len(), 1);
+    let glyph = &font.glyphs[0];
+    assert_eq!(glyph.code, 'A' as u16);
+    assert!(glyph
+        .shape_records
+        .iter()
+        .any(|record| matches!(record, ShapeRecord::CurvedEdge { .. })));
+
+    // the contour should end where it started
+    let (dx, dy) = glyph
+        .shape_records
+        .iter()
+        .fold((0, 0), |(dx, dy), record| match record {
+            ShapeRecord::StyleChange(_) => (dx, dy),
+            ShapeRecord::StraightEdge { delta } => (dx + delta.dx.get(), dy + delta.dy.get()),
+            ShapeRecord::CurvedEdge {
+                control_delta,
+                anchor_delta,
+            } => (
+                dx + control_delta.dx.get() + anchor_delta.dx.get(),
+                dy + control_delta.dy.get() + anchor_delta.dy.get(),
+            ),
+        });
+    assert_eq!((dx, dy), (0, 0));
+}
+
+#[test]
+fn rejects_unsupported_extensions() {
+    let allocator = TestAllocator {
+        string_arena: Arena::new(),
+    };
+    let mut swf_builder = TestSwfBuilder { tags: vec![] };
+    let result = font_to_swf(
+        "font.woff".into(),
+        "font.woff".into(),
+        "A".into(),
+        1,
+        &mut swf_builder,
+        &allocator,
+    );
+    assert!(result.is_err());
+}
@@ -1,4 +1,9 @@
-use std::{any::Any, collections::HashMap, ops::DerefMut, sync::Arc};
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    ops::DerefMut,
+    sync::Arc,
+};
 
 use compat::{Library, RenderContext, UpdateContext};
 use edit_text::EditText;
@@ -33,27 +38,36 @@ struct TextRendererWorld<'gc> {
 }
 
 pub trait SwfFontsContainerBuilder {
-    fn build<'a>(&self) -> Box<dyn SwfFontsContainer + 'a>;
+    type EditTextProperties;
+
+    fn build<'a>(
+        &self,
+    ) -> Box<dyn SwfFontsContainer<EditTextProperties = Self::EditTextProperties> + 'a>;
 }
 // TODO: more accurate name
 pub trait SwfFontsContainer {
+    // what the calling code uses to describe an edit text
+    type EditTextProperties;
+
     fn convert_fonts(&mut self) -> Result<(), Box<dyn std::error::Error>>;
     fn get_fonts<'b>(&'b self) -> Vec<swf::Font<'b>>;
 
     fn convert_edit_text<'a>(
         &'a mut self,
-        // this needs to be any because otherwise we need a generic through gc_arena and that doesn't seem to work
-        edit_text_properties: Box<dyn Any>,
+        edit_text_properties: Self::EditTextProperties,
     ) -> Result<swf::EditText<'a>, Box<dyn std::error::Error>>;
 }
 
-pub struct TextRenderer {
+// the properties are only used while converting, so the arena doesn't need to know about them
+pub struct TextRenderer<P> {
     arena: Arena<Rootable![TextRendererWorld<'_>]>,
-    fonts_container: Box<dyn SwfFontsContainer>,
+    fonts_container: Box<dyn SwfFontsContainer<EditTextProperties = P>>,
+    // hashes of the properties the edit texts were created with, to know when they need to be updated
+    edit_text_hashes: HashMap<usize, u64>,
 }
-impl TextRenderer {
+impl<P> TextRenderer<P> {
     pub fn new(
-        fonts_container_builder: Box<dyn SwfFontsContainerBuilder>,
+        fonts_container_builder: Box<dyn SwfFontsContainerBuilder<EditTextProperties = P>>,
         renderer: &mut Box<dyn RenderBackend>,
     ) -> Self {
         let mut arena = Arena::new(|_gc_context| TextRendererWorld {
@@ -79,17 +93,22 @@ impl TextRenderer {
         Self {
             arena,
             fonts_container,
+            edit_text_hashes: HashMap::new(),
         }
     }
-    pub fn add_edit_text<T>(&mut self, edit_text_id: usize, edit_text_properties: T)
-    where
-        T: 'static,
-    {
+    pub fn add_edit_text(&mut self, edit_text_id: usize, edit_text_properties: P) {
+        // we don't know the properties hash, so the next update always converts it again
+        self.edit_text_hashes.remove(&edit_text_id);
         self.arena.mutate_root(|gc_context, world| {
-            let swf_edit_text = self
-                .fonts_container
-                .convert_edit_text(Box::new(edit_text_properties))
-                .unwrap();
+            let swf_edit_text = match self.fonts_container.convert_edit_text(edit_text_properties) {
+                Ok(swf_edit_text) => swf_edit_text,
+                Err(err) => {
+                    // the edit text isn't shown, which is better than crashing
+                    tracing::error!("Unable to convert edit text {}: {}", edit_text_id, err);
+                    world.edit_texts.remove(&edit_text_id);
+                    return;
+                }
+            };
             let mut update_context = UpdateContext {
                 gc_context,
                 library: &mut world.library,
@@ -107,6 +126,35 @@ impl TextRenderer {
             );
         })
     }
+    // only converts the edit text when it doesn't exist yet or the hashed properties changed,
+    // the properties are only created when they are needed so nothing is allocated otherwise
+    pub fn update_edit_text<H: Hash>(
+        &mut self,
+        edit_text_id: usize,
+        hashed_properties: H,
+        edit_text_properties: impl FnOnce() -> P,
+    ) {
+        let mut hasher = DefaultHasher::new();
+        hashed_properties.hash(&mut hasher);
+        let hash = hasher.finish();
+        if self.edit_text_hashes.get(&edit_text_id) == Some(&hash) {
+            return;
+        }
+        self.add_edit_text(edit_text_id, edit_text_properties());
+        self.edit_text_hashes.insert(edit_text_id, hash);
+    }
+    pub fn has_edit_text(&self, edit_text_id: usize) -> bool {
+        self.edit_text_hashes.contains_key(&edit_text_id)
+    }
+    pub fn edit_text_ids(&self) -> Vec<usize> {
+        self.edit_text_hashes.keys().copied().collect()
+    }
+    pub fn remove_edit_text(&mut self, edit_text_id: usize) {
+        self.edit_text_hashes.remove(&edit_text_id);
+        self.arena.mutate_root(|_, world| {
+            world.edit_texts.remove(&edit_text_id);
+        });
+    }
     pub fn render(
         &self,
         edit_text_id: usize,
@@ -121,24 +169,23 @@ impl TextRenderer {
         };
         render_context.transform_stack.push(&transform);
         self.arena.mutate(|_, world| {
-            world
-                .edit_texts
-                .get(&edit_text_id)
-                .unwrap()
-                .render_self(&mut render_context);
+            // edit texts that couldn't be converted don't exist
+            if let Some(edit_text) = world.edit_texts.get(&edit_text_id) {
+                edit_text.render_self(&mut render_context);
+            }
         });
 
         render_context.commands
     }
 
     pub fn finish_frame(&mut self) {
-        self.arena.mutate_root(|_, world| {
-            // reset the edit texts because the caching doesn't work properly yet
-            world.edit_texts = HashMap::new();
-        });
-
         // run the garbage collector
         // TODO: use collect_debt instead to only do a bit each frame?
         self.arena.finish_cycle();
     }
+
+    // the memory gc_arena has allocated for the fonts and edit texts, for debugging
+    pub fn arena_size_bytes(&self) -> usize {
+        self.arena.metrics().total_allocation()
+    }
 }
@@ -127,6 +127,14 @@ impl Player for MyPlayer {
         self.mouse_x = mouse_x;
         self.mouse_y = mouse_y;
     }
+    fn handle_mouse_wheel(
+        &mut self,
+        _mouse_x: f64,
+        _mouse_y: f64,
+        _delta: winit::event::MouseScrollDelta,
+    ) {
+    }
+    fn handle_key_input(&mut self, _event: winit::event::KeyEvent) {}
 }
 struct MyPlayerController {
     descriptors: Arc<Descriptors>,
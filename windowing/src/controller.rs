@@ -23,6 +23,10 @@ pub struct GuiController<G: RuffleGui> {
     window: Arc<Window>,
     last_update: Instant,
     repaint_after: Duration,
+    // set by events and explicit requests, these need a new frame even if egui doesn't
+    force_redraw: bool,
+    // whether the next RedrawRequested event is one we asked for, the others come from the os
+    redraw_requested: bool,
     surface: wgpu::Surface<'static>,
     surface_format: wgpu::TextureFormat,
     present_mode: wgpu::PresentMode,
@@ -100,7 +104,7 @@ impl<G: RuffleGui> GuiController<G> {
         let movie_view_renderer = Arc::new(MovieViewRenderer::new(
             &descriptors.device,
             surface_format,
-            Self::height_offset_scaled_without_self(
+            height_offset_scaled_without_self(
                 window.fullscreen().is_some(),
                 window.scale_factor(),
                 config.height_offset_unscaled,
@@ -121,6 +125,8 @@ impl<G: RuffleGui> GuiController<G> {
             window,
             last_update: Instant::now(),
             repaint_after: Duration::ZERO,
+            force_redraw: true,
+            redraw_requested: false,
             surface,
             surface_format,
             present_mode: config.present_mode,
@@ -157,6 +163,10 @@ impl<G: RuffleGui> GuiController<G> {
     }
 
     pub fn reconfigure_surface(&mut self) {
+        // configuring a surface with a size of zero crashes, this happens when the window is minimized
+        if self.size.width == 0 || self.size.height == 0 {
+            return;
+        }
         self.surface.configure(
             &self.descriptors.device,
             &wgpu::SurfaceConfiguration {
@@ -181,8 +191,15 @@ impl<G: RuffleGui> GuiController<G> {
         if let WindowEvent::Resized(size) = &event {
             self.resize(*size);
         }
+        // the height offset is scaled, so the movie view has to be moved
+        // even when the size in physical pixels stays the same
+        if let WindowEvent::ScaleFactorChanged { .. } = &event {
+            self.reconfigure_surface();
+        }
 
+        // still let egui have it when typing in a text field, so you can tab to the next one
         if self.send_tab_to_player
+            && !self.egui_winit.egui_ctx().wants_keyboard_input()
             && matches!(
                 &event,
                 WindowEvent::KeyboardInput {
@@ -200,7 +217,7 @@ impl<G: RuffleGui> GuiController<G> {
 
         let response = self.egui_winit.on_window_event(&self.window, event);
         if response.repaint {
-            self.window.request_redraw();
+            self.request_redraw();
         }
         response.consumed
     }
@@ -231,7 +248,7 @@ impl<G: RuffleGui> GuiController<G> {
     }
 
     pub fn height_offset_scaled(&self) -> f64 {
-        Self::height_offset_scaled_without_self(
+        height_offset_scaled_without_self(
             self.window.fullscreen().is_some(),
             self.window.scale_factor(),
             self.height_offset_unscaled,
@@ -239,17 +256,9 @@ impl<G: RuffleGui> GuiController<G> {
         )
     }
 
-    fn height_offset_scaled_without_self(
-        is_fullscreen: bool,
-        scale_factor: f64,
-        height_offset_unscaled: u32,
-        no_gui: bool,
-    ) -> f64 {
-        if is_fullscreen || no_gui {
-            0.0
-        } else {
-            height_offset_unscaled as f64 * scale_factor
-        }
+    // the part of the window below the menu where the movie is shown, in physical pixels
+    pub fn movie_viewport_size(&self) -> PhysicalSize<u32> {
+        movie_viewport_size_without_self(self.size, self.height_offset_scaled())
     }
 
     pub fn set_height_offset_unscaled(&mut self, height_offset_unscaled: u32) {
@@ -269,6 +278,9 @@ impl<G: RuffleGui> GuiController<G> {
     }
 
     pub fn render(&mut self, mut player: Option<MutexGuard<G::Player>>) -> NeedsRedraw {
+        if !self.should_render() {
+            return NeedsRedraw::No;
+        }
         let surface_texture = match self.surface.get_current_texture() {
             Ok(surface_texture) => surface_texture,
             Err(e @ (SurfaceError::Lost | SurfaceError::Outdated)) => {
@@ -301,6 +313,8 @@ impl<G: RuffleGui> GuiController<G> {
             }
         };
 
+        self.force_redraw = false;
+        self.last_update = Instant::now();
         let mut needs_redraw = NeedsRedraw::No;
 
         let raw_input = self.egui_winit.take_egui_input(&self.window);
@@ -413,6 +427,23 @@ impl<G: RuffleGui> GuiController<G> {
     pub fn needs_render(&self) -> bool {
         Instant::now().duration_since(self.last_update) >= self.repaint_after
     }
+    // when nothing happened and egui doesn't want to repaint yet, the frame would be the same as the last one
+    pub fn should_render(&self) -> bool {
+        self.force_redraw || self.needs_render()
+    }
+    pub fn request_redraw(&mut self) {
+        self.force_redraw = true;
+        self.redraw_requested = true;
+        self.window.request_redraw();
+    }
+    // the os sends redraws when the window is uncovered or exposed, the contents of the window
+    // can be gone then, so those are always rendered
+    pub fn on_redraw_requested(&mut self) {
+        if !self.redraw_requested {
+            self.force_redraw = true;
+        }
+        self.redraw_requested = false;
+    }
 
     pub fn set_ime_allowed(&self, allowed: bool) {
         self.window.set_ime_allowed(allowed);
@@ -464,3 +495,54 @@ fn try_wgpu_backend(backend: wgpu::Backends) -> Option<wgpu::Instance> {
         Some(instance)
     }
 }
+
+fn height_offset_scaled_without_self(
+    is_fullscreen: bool,
+    scale_factor: f64,
+    height_offset_unscaled: u32,
+    no_gui: bool,
+) -> f64 {
+    if is_fullscreen || no_gui {
+        0.0
+    } else {
+        // whole pixels, so the movie view and the viewport of the player have the same height
+        // with fractional scale factors like 1.5
+        (height_offset_unscaled as f64 * scale_factor).round()
+    }
+}
+
+fn movie_viewport_size_without_self(
+    size: PhysicalSize<u32>,
+    height_offset_scaled: f64,
+) -> PhysicalSize<u32> {
+    PhysicalSize::new(
+        size.width,
+        size.height.saturating_sub(height_offset_scaled as u32),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a 800x600 window with a 30 pixel menu on a display with a scale factor of 2
+    const SCALE_FACTOR: f64 = 2.0;
+    const SIZE: PhysicalSize<u32> = PhysicalSize::new(1600, 1200);
+    const MENU_HEIGHT: u32 = 30;
+
+    #[test]
+    fn movie_viewport_has_the_logical_size_below_the_menu() {
+        let height_offset_scaled =
+            height_offset_scaled_without_self(false, SCALE_FACTOR, MENU_HEIGHT, false);
+        assert_eq!(height_offset_scaled, 60.0);
+        let viewport_size = movie_viewport_size_without_self(SIZE, height_offset_scaled);
+        let logical_size = viewport_size.to_logical::<u32>(SCALE_FACTOR);
+        assert_eq!((logical_size.width, logical_size.height), (800, 570));
+    }
+
+    #[test]
+    fn height_offset_is_whole_pixels_with_fractional_scale_factors() {
+        let height_offset_scaled = height_offset_scaled_without_self(false, 1.5, 25, false);
+        assert_eq!(height_offset_scaled, 38.0);
+    }
+}
@@ -2,7 +2,7 @@ use egui::CursorIcon;
 use ruffle_render::backend::{RenderBackend, ViewportDimensions};
 use std::sync::{Arc, MutexGuard};
 use winit::{
-    event::{ElementState, MouseButton},
+    event::{ElementState, KeyEvent, MouseButton, MouseScrollDelta},
     window::Window,
 };
 
@@ -51,6 +51,8 @@ pub trait Player {
         button: MouseButton,
         state: ElementState,
     );
+    fn handle_mouse_wheel(&mut self, mouse_x: f64, mouse_y: f64, delta: MouseScrollDelta);
+    fn handle_key_input(&mut self, event: KeyEvent);
 }
 pub trait PlayerController {
     type Player: Player;
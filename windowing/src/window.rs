@@ -40,13 +40,15 @@ where
     }
     pub fn window_event(&mut self, event_loop: &ActiveEventLoop, event: WindowEvent) -> bool {
         if matches!(event, WindowEvent::RedrawRequested) {
+            self.gui.on_redraw_requested();
             // Don't render when minimized to avoid potential swap chain errors in `wgpu`.
-            if !self.minimized {
+            // Also skip redraws we asked for that wouldn't change anything, so an idle editor doesn't use the cpu.
+            if !self.minimized && self.gui.should_render() {
                 if let Some(mut player) = self.player.get() {
                     // Even if the movie is paused, user interaction with debug tools can change the render output
                     player.render();
                     match self.gui.render(Some(player)) {
-                        crate::NeedsRedraw::Yes => self.gui.window().request_redraw(),
+                        crate::NeedsRedraw::Yes => self.gui.request_redraw(),
                         crate::NeedsRedraw::No => (),
                     }
                 } else {
@@ -75,12 +77,16 @@ where
             WindowEvent::CloseRequested => {
                 event_loop.exit();
             }
+            WindowEvent::ScaleFactorChanged { .. } => {
+                self.update_viewport_dimensions();
+                self.gui.request_redraw();
+            }
             WindowEvent::Resized(size) => {
                 // TODO: Change this when winit adds a `Window::minimized` or `WindowEvent::Minimize`.
-                self.minimized = size.width == 0 && size.height == 0;
+                self.minimized = size.width == 0 || size.height == 0;
 
                 self.update_viewport_dimensions();
-                self.gui.window().request_redraw();
+                self.gui.request_redraw();
             }
             WindowEvent::CursorMoved { .. } => {
                 let (mouse_x, mouse_y) = self.gui.window_to_movie_position(self.mouse_pos);
@@ -96,6 +102,19 @@ where
                     .unwrap()
                     .handle_mouse_input(mouse_x, mouse_y, button, state);
             }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let (mouse_x, mouse_y) = self.gui.window_to_movie_position(self.mouse_pos);
+                self.player
+                    .get()
+                    .unwrap()
+                    .handle_mouse_wheel(mouse_x, mouse_y, delta);
+                self.gui.request_redraw();
+            }
+            // only keys that the gui doesn't use end up here
+            WindowEvent::KeyboardInput { event, .. } => {
+                self.player.get().unwrap().handle_key_input(event);
+                self.gui.request_redraw();
+            }
             _ => (),
         }
 
@@ -105,12 +124,14 @@ where
     fn update_viewport_dimensions(&mut self) {
         if let Some(mut player) = self.player.get() {
             let viewport_scale_factor = self.gui.window().scale_factor();
-            let size = self.gui.window().inner_size();
+            let size = self.gui.movie_viewport_size();
+            // the renderer can't create textures with a size of zero, this happens when the window is minimized
+            if size.width == 0 || size.height == 0 {
+                return;
+            }
             player.set_viewport_dimensions(ViewportDimensions {
                 width: size.width,
-                height: size
-                    .height
-                    .saturating_sub(self.gui.height_offset_scaled() as u32),
+                height: size.height,
                 scale_factor: viewport_scale_factor,
             });
         }
@@ -124,6 +145,11 @@ where
         let dt = new_time.duration_since(self.time).as_nanos();
         if dt > 0 {
             self.time = new_time;
+            // the tick below moves the player to its next frame, which has to be drawn
+            // even if the gui didn't change
+            let reached_next_frame = self
+                .next_frame_time
+                .is_some_and(|next_frame_time| new_time >= next_frame_time);
             if let Some(mut player) = self.player.get() {
                 player.tick(dt as f64 / 1_000_000.0);
                 self.next_frame_time = match player.time_til_next_frame() {
@@ -133,7 +159,11 @@ where
             } else {
                 self.next_frame_time = None;
             }
-            self.check_redraw();
+            if reached_next_frame {
+                self.gui.request_redraw();
+            } else {
+                self.check_redraw();
+            }
         }
 
         // The event loop is finished; let's find out how long we need to wait for.
@@ -141,19 +171,24 @@ where
         // only place where we're setting control flow, and events cancel wait.
         // Note: the control flow might be set to `ControlFlow::WaitUntil` with a
         // timestamp in the past! Take that into consideration when changing this code.
-        if let Some(next_frame_time) = self.next_frame_time {
-            event_loop.set_control_flow(ControlFlow::WaitUntil(next_frame_time));
+        // without frames to wait for, only wake up for events,
+        // otherwise a WaitUntil from an earlier frame keeps waking up the loop
+        match self.next_frame_time {
+            Some(next_frame_time) => {
+                event_loop.set_control_flow(ControlFlow::WaitUntil(next_frame_time))
+            }
+            None => event_loop.set_control_flow(ControlFlow::Wait),
         }
     }
 
-    pub fn check_redraw(&self) {
+    pub fn check_redraw(&mut self) {
         if self.gui.needs_render() {
-            self.gui.window().request_redraw();
+            self.gui.request_redraw();
         }
     }
 
-    pub fn request_redraw(&self) {
-        self.gui.window().request_redraw();
+    pub fn request_redraw(&mut self) {
+        self.gui.request_redraw();
     }
 
     pub fn player_mut(&mut self) -> &mut P {